@@ -0,0 +1,130 @@
+use std::io::{Error, Read};
+use std::process::{Command, Stdio};
+
+use tracing::debug;
+
+use crate::model::{SceneDetection, SilenceDetection};
+
+// Analyze the input with FFmpeg detection filters
+
+/// Run `silencedetect` over the first audio stream
+pub(crate) fn detect_silence(
+    input: &str,
+    threshold: i32,
+    duration: f64,
+    input_duration: Option<f64>,
+) -> Result<SilenceDetection, Error> {
+    let filter = format!("silencedetect=n={threshold}dB:d={duration}");
+    debug!(input, filter, "detect silence");
+    let output = run_ffmpeg(input, &["-map", "0:a:0", "-af", &filter])?;
+    Ok(SilenceDetection {
+        threshold,
+        duration,
+        ranges: parse_silence(&output, input_duration),
+    })
+}
+
+/// Select the frames of the first video stream with the `scene` score above the threshold
+pub(crate) fn detect_scenes(input: &str, threshold: f64) -> Result<SceneDetection, Error> {
+    let filter = format!("select='gt(scene,{threshold})',showinfo");
+    debug!(input, filter, "detect scenes");
+    let output = run_ffmpeg(input, &["-map", "0:v:0", "-vf", &filter])?;
+    Ok(SceneDetection {
+        threshold,
+        times: parse_scenes(&output),
+    })
+}
+
+/// Decode the input with the output arguments and return the log
+fn run_ffmpeg(input: &str, args: &[&str]) -> Result<String, Error> {
+    let mut child = Command::new("ffmpeg")
+        .args(["-hide_banner", "-nostats", "-i", input])
+        .args(args)
+        .args(["-f", "null", "-"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let mut output = String::new();
+    if let Some(mut stderr) = child.stderr.take() {
+        stderr.read_to_string(&mut output)?;
+    }
+
+    let status = child.wait()?;
+    if status.success() {
+        Ok(output)
+    } else {
+        Err(Error::other(format!("ffmpeg exited with status: {status}")))
+    }
+}
+
+/// Collect silence ranges from the `silencedetect` log,
+/// the silence lasting till the end may have no `silence_end`
+fn parse_silence(output: &str, input_duration: Option<f64>) -> Vec<(f64, f64)> {
+    let value = |line: &str, key: &str| {
+        let (_, rest) = line.split_once(key)?;
+        rest.split_whitespace().next()?.parse::<f64>().ok()
+    };
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for line in output.lines() {
+        if let Some(s) = value(line, "silence_start:") {
+            start = Some(s.max(0.0));
+        } else if let Some(end) = value(line, "silence_end:")
+            && let Some(s) = start.take()
+        {
+            ranges.push((s, end));
+        }
+    }
+    if let (Some(s), Some(end)) = (start, input_duration) {
+        ranges.push((s, end));
+    }
+    ranges
+}
+
+/// Collect the times of the frames passed by `select` from the `showinfo` log
+fn parse_scenes(output: &str) -> Vec<f64> {
+    output
+        .lines()
+        .filter(|line| line.contains("Parsed_showinfo"))
+        .filter_map(|line| {
+            let (_, rest) = line.split_once("pts_time:")?;
+            rest.split_whitespace().next()?.parse::<f64>().ok()
+        })
+        .filter(|time| *time > 0.0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_silence() {
+        let output = "Input #0, mov,mp4,m4a,3gp,3g2,mj2, from 'input.mp4':
+[silencedetect @ 0x600001] silence_start: -0.00133333
+[silencedetect @ 0x600001] silence_end: 1.52 | silence_duration: 1.52133
+size=N/A time=00:00:10.00 bitrate=N/A speed= 512x
+[silencedetect @ 0x600001] silence_start: 4.2
+[silencedetect @ 0x600001] silence_end: 5 | silence_duration: 0.8
+[silencedetect @ 0x600001] silence_start: 9.1
+";
+        assert_eq!(
+            parse_silence(output, Some(10.0)),
+            vec![(0.0, 1.52), (4.2, 5.0), (9.1, 10.0)]
+        );
+        assert_eq!(parse_silence(output, None).len(), 2);
+    }
+
+    #[test]
+    fn test_parse_scenes() {
+        let output = "Input #0, mov,mp4,m4a,3gp,3g2,mj2, from 'input.mp4':
+[Parsed_showinfo_1 @ 0x600001] config in time_base: 1/12800, frame_rate: 25/1
+[Parsed_showinfo_1 @ 0x600001] n:   0 pts:  54272 pts_time:4.24    duration:    512 pos: 1 fmt:yuv420p
+[Parsed_showinfo_1 @ 0x600001] n:   1 pts: 160000 pts_time:12.5    duration:    512 pos: 2 fmt:yuv420p
+frame=    2 fps=0.0 q=-0.0 Lsize=N/A time=00:00:12.54 bitrate=N/A speed= 120x
+";
+        assert_eq!(parse_scenes(output), vec![4.24, 12.5]);
+    }
+}
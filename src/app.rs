@@ -10,21 +10,26 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{DefaultTerminal, widgets::ListState};
 use tracing::debug;
 
+use crate::analysis::{detect_scenes, detect_silence};
+use crate::features::Features;
 use crate::info::Info;
-use crate::model::{AppEvent, Pane};
+use crate::model::{
+    AppEvent, OutputFiles, Pane, SceneDetection, SilenceData, SilenceDetection, SplitData,
+};
 use crate::params::{
-    Parameter, ParameterData, Trim, apply_visitor, create_params, get_output_format,
-    recheck_params, save_preset,
+    Aspect, AudioEq, Frames, Metadata, Overlay, Parameter, ParameterData, RemoveSilence, Split,
+    Text, Trim, apply_visitor, create_params, get_output_format, recheck_params, save_preset,
 };
 use crate::source::Source;
 use crate::ui::Theme;
 use crate::ui::modal::{
-    AlertKind, AlertModal, CopyModal, CustomSelectModal, HelpModal, ModalResult, SaveAsFileModal,
-    TrimModal, UiModal,
+    AlertKind, AlertModal, AspectModal, ConfirmModal, CopyModal, CustomSelectModal, EqModal,
+    FramesModal, HelpModal, MetadataModal, ModalResult, OverlayModal, SaveAsFileModal,
+    SilenceModal, SplitModal, TextModal, TrimModal, UiModal, VariantFiles,
 };
 use crate::ui::state::{InfoPaneState, OutputPaneState};
 
-use crate::visitors::{CommandBuilder, VisitorContext};
+use crate::visitors::{CommandBuilder, VisitorContext, quote_arg};
 
 pub(crate) struct App<'a> {
     // App state
@@ -39,10 +44,14 @@ pub(crate) struct App<'a> {
     // Params
     pub params: Vec<Parameter>,
     pub params_list_state: ListState,
+    // Outputs rendered by the same command, the active one is swapped into `params`
+    variants: Vec<Variant>,
+    current_variant: usize,
     // Input
     pub original_filename: Box<str>,
     pub source: Source,
     pub info: &'a Info,
+    features: Features,
     pub info_state: InfoPaneState<'a>,
     // Output
     pub out_state: OutputPaneState,
@@ -53,8 +62,24 @@ pub(crate) struct App<'a> {
     render_stdin: Option<ChildStdin>,
 }
 
+/// Command builders of the variants with their output names, and the warnings
+type VariantBuilders = (Vec<(CommandBuilder, String)>, Vec<String>);
+
+/// Output rendered with its own parameters from the same input
+#[derive(Default)]
+struct Variant {
+    params: Vec<Parameter>,
+    output_filename: String,
+}
+
 impl<'a> App<'a> {
-    pub fn new(tx: Sender<AppEvent>, info: &'a Info, source: Source, preset: Option<&str>) -> Self {
+    pub fn new(
+        tx: Sender<AppEvent>,
+        info: &'a Info,
+        features: Features,
+        source: Source,
+        presets: &[String],
+    ) -> Self {
         let mut list_state = ListState::default();
         list_state.select_first();
         let folder = source.input_folder();
@@ -66,6 +91,20 @@ impl<'a> App<'a> {
         } else {
             filename.clone().into()
         };
+        // Each preset is a variant, the first one is active
+        let mut variants: Vec<Variant> = if presets.is_empty() {
+            vec![None]
+        } else {
+            presets.iter().map(|preset| Some(preset.as_str())).collect()
+        }
+        .into_iter()
+        .enumerate()
+        .map(|(i, preset)| Variant {
+            params: create_params(info, features, preset, fileext.as_str()),
+            output_filename: Self::variant_filename(&filename, i),
+        })
+        .collect();
+        let active = mem::take(&mut variants[0]);
         Self {
             running: false,
             event_sender: tx,
@@ -76,17 +115,20 @@ impl<'a> App<'a> {
             modal: None,
             theme,
             // Params
-            params: create_params(info, preset, fileext.as_str()),
+            params: active.params,
             params_list_state: list_state,
+            variants,
+            current_variant: 0,
             // Info
             original_filename,
             source,
             info,
+            features,
             info_state,
             // Output
             out_state: OutputPaneState::new(String::new()),
             output_folder: folder,
-            output_filename: format!("{filename}_out"),
+            output_filename: active.output_filename,
             output_fileext: fileext.clone(),
             save_ongoing: false,
             render_stdin: None,
@@ -99,6 +141,8 @@ impl<'a> App<'a> {
         rx: &Receiver<AppEvent>,
     ) -> Result<(), Box<dyn Error>> {
         self.running = true;
+        self.start_silence_detection();
+        self.start_scene_detection();
         while self.running {
             terminal.draw(|frame| {
                 frame.render_widget(&mut self, frame.area());
@@ -111,8 +155,38 @@ impl<'a> App<'a> {
                 Ok(AppEvent::AddOutput(output)) => self.out_state.add_output(&output),
                 Ok(AppEvent::SaveCompleted(success)) => self.on_save_complete(success),
                 Ok(AppEvent::OpenTrimModal(data)) => {
-                    self.modal = Some(Box::new(TrimModal::new(data, self.info.get_duration())));
+                    self.modal = Some(Box::new(TrimModal::new(
+                        data,
+                        self.info.get_duration(),
+                        &self.info.chapters,
+                    )));
+                }
+                Ok(AppEvent::OpenOverlayModal(data)) => {
+                    self.modal = Some(Box::new(OverlayModal::new(data)));
+                }
+                Ok(AppEvent::OpenTextModal(data)) => {
+                    self.modal = Some(Box::new(TextModal::new(data)));
+                }
+                Ok(AppEvent::OpenAspectModal(data)) => {
+                    self.modal = Some(Box::new(AspectModal::new(data)));
+                }
+                Ok(AppEvent::OpenEqModal(data)) => {
+                    self.modal = Some(Box::new(EqModal::new(data)));
+                }
+                Ok(AppEvent::OpenSilenceModal(data)) => {
+                    self.modal = Some(Box::new(SilenceModal::new(data)));
                 }
+                Ok(AppEvent::OpenMetadataModal(data)) => {
+                    self.modal = Some(Box::new(MetadataModal::new(data)));
+                }
+                Ok(AppEvent::OpenSplitModal(data)) => {
+                    self.modal = Some(Box::new(SplitModal::new(data)));
+                }
+                Ok(AppEvent::OpenFramesModal(data)) => {
+                    self.modal = Some(Box::new(FramesModal::new(data)));
+                }
+                Ok(AppEvent::SilenceDetected(result)) => self.on_silence_detected(result),
+                Ok(AppEvent::ScenesDetected(result)) => self.on_scenes_detected(result),
                 Ok(AppEvent::OpenCustomSelectModal(data)) => {
                     self.modal = Some(Box::new(CustomSelectModal::from(data)));
                 }
@@ -124,7 +198,26 @@ impl<'a> App<'a> {
     }
 
     pub fn run_cli(&mut self) {
-        let args = self.build_ffmpeg_command(false, false);
+        // Detection results are shared by the variants with the same settings
+        for index in 0..self.variants.len() {
+            self.switch_variant(index);
+            self.run_cli_detection();
+        }
+        self.switch_variant(0);
+        let (args, warnings) = match self.build_ffmpeg_command(false, false) {
+            Ok(command) => command,
+            Err(msg) => {
+                println!("{msg}");
+                return;
+            }
+        };
+        for warning in warnings {
+            println!("Warning: {warning}");
+        }
+        if let Err(e) = self.create_package_folders() {
+            println!("Failed to create the output folder: {e}");
+            return;
+        }
         println!("Starting FFmpeg\nCommand: ffmpeg {}", args.join(" "));
         Command::new("ffmpeg")
             .args(args)
@@ -135,6 +228,38 @@ impl<'a> App<'a> {
             .expect("Failed to start FFmpeg");
     }
 
+    fn run_cli_detection(&mut self) {
+        if let Some(silence) = self.silence_data()
+            && silence.needs_detection()
+        {
+            println!("Detecting silence...");
+            match detect_silence(
+                &self.source.input,
+                silence.threshold,
+                silence.duration,
+                self.info.get_duration(),
+            ) {
+                Ok(detection) => {
+                    println!("Silence detected: {} parts", detection.ranges.len());
+                    self.on_silence_detected(Ok(detection));
+                }
+                Err(e) => println!("Silence detection failed: {e}"),
+            }
+        }
+        if let Some(split) = self.split_data()
+            && split.needs_detection()
+        {
+            println!("Detecting scene changes...");
+            match detect_scenes(&self.source.input, split.scene) {
+                Ok(detection) => {
+                    println!("Scene changes detected: {}", detection.times.len());
+                    self.on_scenes_detected(Ok(detection));
+                }
+                Err(e) => println!("Scene detection failed: {e}"),
+            }
+        }
+    }
+
     fn on_key_event(&mut self, key: KeyEvent) {
         if let Some(modal) = &mut self.modal {
             match modal.handle_key(key) {
@@ -152,6 +277,81 @@ impl<'a> App<'a> {
                     }
                     self.modal = None;
                 }
+                ModalResult::Overlay => {
+                    if let Some(param) = self.params.iter_mut().find(|p| p.id == Overlay::ID)
+                        && let ParameterData::Overlay(data) = &mut param.data
+                        && let Some(overlay) = modal.downcast_ref::<OverlayModal>()
+                    {
+                        *data = overlay.into();
+                    }
+                    self.modal = None;
+                }
+                ModalResult::Text => {
+                    if let Some(param) = self.params.iter_mut().find(|p| p.id == Text::ID)
+                        && let ParameterData::Text(data) = &mut param.data
+                        && let Some(text) = modal.downcast_ref::<TextModal>()
+                    {
+                        *data = text.into();
+                    }
+                    self.modal = None;
+                }
+                ModalResult::Aspect => {
+                    if let Some(param) = self.params.iter_mut().find(|p| p.id == Aspect::ID)
+                        && let ParameterData::Aspect(data) = &mut param.data
+                        && let Some(aspect) = modal.downcast_ref::<AspectModal>()
+                    {
+                        *data = aspect.into();
+                    }
+                    self.modal = None;
+                }
+                ModalResult::Eq => {
+                    if let Some(param) = self.params.iter_mut().find(|p| p.id == AudioEq::ID)
+                        && let ParameterData::Eq(data) = &mut param.data
+                        && let Some(eq) = modal.downcast_ref::<EqModal>()
+                    {
+                        *data = eq.into();
+                    }
+                    self.modal = None;
+                }
+                ModalResult::Silence => {
+                    if let Some(param) = self.params.iter_mut().find(|p| p.id == RemoveSilence::ID)
+                        && let ParameterData::Silence(data) = &mut param.data
+                        && let Some(silence) = modal.downcast_ref::<SilenceModal>()
+                    {
+                        *data = silence.into();
+                    }
+                    self.modal = None;
+                    self.start_silence_detection();
+                }
+                ModalResult::Split => {
+                    if let Some(param) = self.params.iter_mut().find(|p| p.id == Split::ID)
+                        && let ParameterData::Split(data) = &mut param.data
+                        && let Some(split) = modal.downcast_ref::<SplitModal>()
+                    {
+                        *data = split.into();
+                    }
+                    self.modal = None;
+                    recheck_params(&mut self.params);
+                    self.start_scene_detection();
+                }
+                ModalResult::Frames => {
+                    if let Some(param) = self.params.iter_mut().find(|p| p.id == Frames::ID)
+                        && let ParameterData::Frames(data) = &mut param.data
+                        && let Some(frames) = modal.downcast_ref::<FramesModal>()
+                    {
+                        *data = frames.into();
+                    }
+                    self.modal = None;
+                }
+                ModalResult::Metadata => {
+                    if let Some(param) = self.params.iter_mut().find(|p| p.id == Metadata::ID)
+                        && let ParameterData::Metadata(data) = &mut param.data
+                        && let Some(metadata) = modal.downcast_ref::<MetadataModal>()
+                    {
+                        *data = metadata.into();
+                    }
+                    self.modal = None;
+                }
                 ModalResult::CustomSelect(value) => {
                     if let Some(selected) = self.params_list_state.selected()
                         && let Some(param) = self.params.get_mut(selected)
@@ -167,6 +367,10 @@ impl<'a> App<'a> {
                 ModalResult::CopyPreset => self.copy_preset(),
                 ModalResult::CopyInfo => self.copy_info(),
                 ModalResult::CopyOutput => self.copy_output(),
+                ModalResult::RemoveVariant => {
+                    self.modal = None;
+                    self.remove_variant();
+                }
                 ModalResult::None => {}
             }
             return;
@@ -192,6 +396,10 @@ impl<'a> App<'a> {
             (Pane::Params, _, KeyCode::Left | KeyCode::Char('h')) => self.prev_option(),
             (Pane::Params, _, KeyCode::Right | KeyCode::Char('l')) => self.next_option(),
             (Pane::Params, _, KeyCode::Enter) => self.open_param_modal(),
+            (Pane::Params, _, KeyCode::Char('a')) => self.add_variant(),
+            (Pane::Params, _, KeyCode::Char('x')) => self.confirm_remove_variant(),
+            (Pane::Params, _, KeyCode::Char('[')) => self.prev_variant(),
+            (Pane::Params, _, KeyCode::Char(']')) => self.next_variant(),
             _ => {}
         }
     }
@@ -202,7 +410,7 @@ impl<'a> App<'a> {
 
     fn copy_preset(&mut self) {
         let (kind, msg) = match self.clipboard.as_mut().map(|ctx| {
-            let visitor_ctx = VisitorContext::new(self.info);
+            let visitor_ctx = VisitorContext::new(self.info, self.features);
             let preset = save_preset(visitor_ctx, &mut self.params);
             ctx.set_text(format!("--preset \"{preset}\""))
         }) {
@@ -213,7 +421,13 @@ impl<'a> App<'a> {
     }
 
     fn copy_command(&mut self) {
-        let args = self.build_ffmpeg_command(false, true);
+        let args = match self.build_ffmpeg_command(false, true) {
+            Ok((args, _)) => args,
+            Err(msg) => {
+                self.modal = Some(Box::new(AlertModal::new(AlertKind::Error, msg)));
+                return;
+            }
+        };
         let (kind, msg) = match self
             .clipboard
             .as_mut()
@@ -295,6 +509,120 @@ impl<'a> App<'a> {
         }
     }
 
+    /// Params pane title, shows the active variant when there are several
+    pub fn params_title(&self) -> String {
+        if self.variants.len() > 1 {
+            format!(
+                "Params [{}/{}] {}",
+                self.current_variant + 1,
+                self.variants.len(),
+                self.output_filename
+            )
+        } else {
+            "Params".to_owned()
+        }
+    }
+
+    fn variant_filename(filename: &str, index: usize) -> String {
+        if index == 0 {
+            format!("{filename}_out")
+        } else {
+            format!("{filename}_out{}", index + 1)
+        }
+    }
+
+    /// Exchange the active variant with its slot in the list
+    fn swap_variant(&mut self) {
+        let variant = &mut self.variants[self.current_variant];
+        mem::swap(&mut variant.params, &mut self.params);
+        mem::swap(&mut variant.output_filename, &mut self.output_filename);
+    }
+
+    fn switch_variant(&mut self, index: usize) {
+        self.swap_variant();
+        self.current_variant = index;
+        self.swap_variant();
+    }
+
+    fn select_variant(&mut self, index: usize) {
+        self.switch_variant(index);
+        self.on_variant_changed();
+    }
+
+    /// Keep the params selection and start the detection the variant needs
+    fn on_variant_changed(&mut self) {
+        if let Some(selected) = self.params_list_state.selected() {
+            self.params_list_state
+                .select(Some(selected.min(self.params.len() - 1)));
+        }
+        self.start_silence_detection();
+        self.start_scene_detection();
+    }
+
+    /// New variant with the parameters of the active one, placed after it
+    fn add_variant(&mut self) {
+        let ctx = VisitorContext::new(self.info, self.features);
+        let preset = save_preset(ctx, &mut self.params);
+        let (filename, _) = self.source.input_name_and_ext(self.info);
+        let mut index = self.variants.len();
+        let is_taken = |name: &str| {
+            name == self.output_filename || self.variants.iter().any(|v| v.output_filename == name)
+        };
+        while is_taken(&Self::variant_filename(&filename, index)) {
+            index += 1;
+        }
+        let variant = Variant {
+            params: create_params(
+                self.info,
+                self.features,
+                Some(&preset),
+                &self.output_fileext,
+            ),
+            output_filename: Self::variant_filename(&filename, index),
+        };
+        self.variants.insert(self.current_variant + 1, variant);
+        self.select_variant(self.current_variant + 1);
+    }
+
+    /// Ask before dropping the parameters of the active variant
+    fn confirm_remove_variant(&mut self) {
+        if self.variants.len() < 2 {
+            return;
+        }
+        let message = format!("Remove variant {}?", self.output_filename);
+        self.modal = Some(Box::new(ConfirmModal::new(
+            message,
+            ModalResult::RemoveVariant,
+        )));
+    }
+
+    /// Drop the active variant, the last one is kept
+    fn remove_variant(&mut self) {
+        if self.variants.len() < 2 {
+            return;
+        }
+        self.variants.remove(self.current_variant);
+        let index = self.current_variant.min(self.variants.len() - 1);
+        let variant = mem::take(&mut self.variants[index]);
+        self.current_variant = index;
+        self.params = variant.params;
+        self.output_filename = variant.output_filename;
+        self.on_variant_changed();
+    }
+
+    fn prev_variant(&mut self) {
+        if self.variants.len() > 1 {
+            let count = self.variants.len();
+            self.select_variant((self.current_variant + count - 1) % count);
+        }
+    }
+
+    fn next_variant(&mut self) {
+        if self.variants.len() > 1 {
+            self.select_variant((self.current_variant + 1) % self.variants.len());
+        }
+    }
+
     fn prev_pane(&mut self) {
         if self.current_pane == Pane::Params {
             self.active_out_pane = Pane::Output;
@@ -339,13 +667,28 @@ impl<'a> App<'a> {
         if self.save_ongoing {
             return;
         }
+        let (args, warnings) = match self.build_ffmpeg_command(true, false) {
+            Ok(command) => command,
+            Err(msg) => {
+                self.modal = Some(Box::new(AlertModal::new(AlertKind::Error, msg)));
+                return;
+            }
+        };
         self.modal = None;
         self.save_ongoing = true;
         self.active_out_pane = Pane::Output;
 
-        let args = self.build_ffmpeg_command(true, false);
         debug!(?args, "Starting FFmpeg");
         self.out_state.set_output("Starting FFmpeg...\n");
+        for warning in warnings {
+            self.out_state.add_output(&format!("Warning: {warning}\n"));
+        }
+        if let Err(e) = self.create_package_folders() {
+            self.out_state
+                .add_output(&format!("Failed to create the output folder: {e}\n"));
+            self.save_ongoing = false;
+            return;
+        }
 
         let tx = self.event_sender.clone();
         thread::spawn(move || {
@@ -389,6 +732,29 @@ impl<'a> App<'a> {
     }
 
     fn save_as(&mut self) {
+        let (mut builders, _) = match self.variant_builders() {
+            Ok(builders) => builders,
+            Err(msg) => {
+                self.modal = Some(Box::new(AlertModal::new(AlertKind::Error, msg)));
+                return;
+            }
+        };
+        // Only the active variant is renamed, the files of the others are checked as they are
+        let output_files = match builders
+            .iter()
+            .position(|(command_builder, _)| command_builder.variant == self.current_variant)
+        {
+            Some(index) => builders.remove(index).0.output_files(),
+            None => self.command_builder().output_files(),
+        };
+        let other_files = builders
+            .into_iter()
+            .map(|(command_builder, filename)| VariantFiles {
+                filename,
+                files: command_builder.output_files(),
+                ext: command_builder.ext,
+            })
+            .collect();
         let output_ext =
             get_output_format(&self.params).map_or(&self.output_fileext, |option| &option.value);
         self.modal = Some(Box::new(SaveAsFileModal::new(
@@ -396,9 +762,23 @@ impl<'a> App<'a> {
             &self.output_folder,
             &self.output_filename,
             output_ext,
+            output_files,
+            other_files,
         )));
     }
 
+    /// Streaming packages are written into a folder named after the output
+    fn create_package_folders(&mut self) -> std::io::Result<()> {
+        let (builders, _) = self.variant_builders().map_err(std::io::Error::other)?;
+        for (command_builder, filename) in builders {
+            if let OutputFiles::Package(_) = command_builder.output_files() {
+                let folder = PathBuf::new().join(&*self.output_folder).join(filename);
+                std::fs::create_dir_all(folder)?;
+            }
+        }
+        Ok(())
+    }
+
     fn on_save_complete(&mut self, success: bool) {
         let msg = if success {
             "FFmpeg finished successfully!\n\n"
@@ -410,6 +790,123 @@ impl<'a> App<'a> {
         self.render_stdin = None;
     }
 
+    fn silence_data(&self) -> Option<&SilenceData> {
+        self.params
+            .iter()
+            .filter(|p| p.id == RemoveSilence::ID && p.enabled)
+            .find_map(|p| match &p.data {
+                ParameterData::Silence(data) => Some(data),
+                _ => None,
+            })
+    }
+
+    /// Run `silencedetect` in background, the video is cut by its results
+    fn start_silence_detection(&mut self) {
+        let Some(silence) = self.silence_data().filter(|s| s.needs_detection()) else {
+            return;
+        };
+        let (threshold, duration) = (silence.threshold, silence.duration);
+        self.active_out_pane = Pane::Output;
+        self.out_state.add_output("Detecting silence...\n");
+        let input = self.source.input.clone();
+        let input_duration = self.info.get_duration();
+        let tx = self.event_sender.clone();
+        thread::spawn(move || {
+            let result = detect_silence(&input, threshold, duration, input_duration);
+            let _ = tx.send(AppEvent::SilenceDetected(result.map_err(|e| e.to_string())));
+        });
+    }
+
+    fn on_silence_detected(&mut self, result: Result<SilenceDetection, String>) {
+        let detection = match result {
+            Ok(detection) => detection,
+            Err(e) => {
+                self.out_state
+                    .add_output(&format!("Silence detection failed: {e}\n"));
+                return;
+            }
+        };
+        let mut applied = false;
+        for param in self.all_params_mut().filter(|p| p.id == RemoveSilence::ID) {
+            if let ParameterData::Silence(data) = &mut param.data
+                // Ignore outdated results, the settings were changed meanwhile
+                && data.threshold == detection.threshold
+                && data.duration == detection.duration
+            {
+                data.detection = Some(detection.clone());
+                applied = true;
+            }
+        }
+        if applied {
+            self.out_state.add_output(&format!(
+                "Silence detected: {} parts\n",
+                detection.ranges.len()
+            ));
+        }
+    }
+
+    fn split_data(&self) -> Option<&SplitData> {
+        self.params
+            .iter()
+            .filter(|p| p.id == Split::ID && p.enabled)
+            .find_map(|p| match &p.data {
+                ParameterData::Split(data) => Some(data),
+                _ => None,
+            })
+    }
+
+    /// Run the scene change detection in background, the output is split by its results
+    fn start_scene_detection(&mut self) {
+        let Some(split) = self.split_data().filter(|s| s.needs_detection()) else {
+            return;
+        };
+        let threshold = split.scene;
+        self.active_out_pane = Pane::Output;
+        self.out_state.add_output("Detecting scene changes...\n");
+        let input = self.source.input.clone();
+        let tx = self.event_sender.clone();
+        thread::spawn(move || {
+            let result = detect_scenes(&input, threshold);
+            let _ = tx.send(AppEvent::ScenesDetected(result.map_err(|e| e.to_string())));
+        });
+    }
+
+    fn on_scenes_detected(&mut self, result: Result<SceneDetection, String>) {
+        let detection = match result {
+            Ok(detection) => detection,
+            Err(e) => {
+                self.out_state
+                    .add_output(&format!("Scene detection failed: {e}\n"));
+                return;
+            }
+        };
+        let mut applied = false;
+        for param in self.all_params_mut().filter(|p| p.id == Split::ID) {
+            if let ParameterData::Split(data) = &mut param.data
+                // Ignore outdated results, the threshold was changed meanwhile
+                && data.scene == detection.threshold
+            {
+                data.detection = Some(detection.clone());
+                applied = true;
+            }
+        }
+        if applied {
+            self.out_state.add_output(&format!(
+                "Scene changes detected: {}\n",
+                detection.times.len()
+            ));
+        }
+    }
+
+    /// Parameters of every variant, detection results apply to all of them
+    fn all_params_mut(&mut self) -> impl Iterator<Item = &mut Parameter> {
+        self.params.iter_mut().chain(
+            self.variants
+                .iter_mut()
+                .flat_map(|variant| variant.params.iter_mut()),
+        )
+    }
+
     fn quit(&mut self) {
         if self.save_ongoing
             && let Some(mut stdin) = self.render_stdin.take()
@@ -421,31 +918,171 @@ impl<'a> App<'a> {
         }
     }
 
-    fn build_ffmpeg_command(&mut self, overwrite: bool, quote: bool) -> Vec<String> {
-        let ctx = VisitorContext::new(self.info);
+    fn command_builder(&mut self) -> CommandBuilder {
+        let ctx = VisitorContext::new(self.info, self.features);
         let mut command_builder = CommandBuilder::new(ctx);
         apply_visitor(&mut command_builder, &mut self.params);
+        command_builder
+    }
+
+    /// Command builders of the variants rendered together, with their output names.
+    /// The variants share the input, different trims seek the output instead
+    fn variant_builders(&mut self) -> Result<VariantBuilders, &'static str> {
+        let same_input = |(builders, _): &VariantBuilders| {
+            builders
+                .windows(2)
+                .all(|pair| pair[0].0.pre_input_args == pair[1].0.pre_input_args)
+        };
+        let mut result = self.build_variants(false);
+        if !same_input(&result) {
+            result = self.build_variants(true);
+        }
+        if same_input(&result) {
+            Ok(result)
+        } else {
+            Err(
+                "Variants have different input options (loop or hardware decoding)\n\
+                 and can't be rendered together",
+            )
+        }
+    }
+
+    fn build_variants(&mut self, seek_output: bool) -> VariantBuilders {
+        self.swap_variant();
+        let mut builders: Vec<(CommandBuilder, String)> = Vec::new();
+        let mut warnings = Vec::new();
+        let mut input_offset = 0;
+        for (i, variant) in self.variants.iter_mut().enumerate() {
+            let ctx = VisitorContext::new(self.info, self.features);
+            let mut command_builder = CommandBuilder::new(ctx);
+            command_builder.variant = i;
+            command_builder.input_offset = input_offset;
+            command_builder.seek_output = seek_output;
+            apply_visitor(&mut command_builder, &mut variant.params);
+            let filename = &variant.output_filename;
+            if builders
+                .iter()
+                .any(|(other, name)| name == filename && other.ext == command_builder.ext)
+            {
+                warnings.push(format!(
+                    "{filename}.{} is written by several variants and is rendered once",
+                    command_builder.ext
+                ));
+                continue;
+            }
+            input_offset += command_builder.extra_inputs.len();
+            builders.push((command_builder, filename.clone()));
+        }
+        self.swap_variant();
+        (builders, warnings)
+    }
+
+    /// Build FFmpeg arguments, also returns warnings about the chosen parameters.
+    /// The input is opened and decoded once, every variant adds its outputs
+    fn build_ffmpeg_command(
+        &mut self,
+        overwrite: bool,
+        quote: bool,
+    ) -> Result<(Vec<String>, Vec<String>), &'static str> {
+        let (mut builders, mut warnings) = self.variant_builders()?;
         let input = self.source.input.clone();
-        let mut path = PathBuf::new()
-            .join(&*self.output_folder)
-            .join(&*self.output_filename);
-        path.add_extension(&*command_builder.ext);
-        let output_file = path.display().to_string();
 
         let mut args: Vec<String> = Vec::new();
         if overwrite {
             args.push("-y".into());
         }
         args.push("-hide_banner".into());
-        args.extend(command_builder.build_pre_input_args().iter().cloned());
+        if let Some((first, _)) = builders.first() {
+            args.extend(first.build_pre_input_args().iter().cloned());
+        }
         args.push("-i".into());
-        args.push(if quote { format!("\"{input}\"") } else { input });
-        args.extend(command_builder.build_args(quote));
-        args.push(if quote {
-            format!("\"{output_file}\"")
-        } else {
-            output_file
-        });
-        args
+        args.push(if quote { quote_arg(&input) } else { input });
+        for extra_input in builders.iter().flat_map(|(cb, _)| &cb.extra_inputs) {
+            args.extend(extra_input.args.iter().cloned());
+            args.push("-i".into());
+            let path = if extra_input.source {
+                &self.source.input
+            } else {
+                &extra_input.path
+            };
+            args.push(if quote { quote_arg(path) } else { path.clone() });
+        }
+        let several = builders.len() > 1;
+        if several
+            && let Some(graph) =
+                CommandBuilder::share_source(builders.iter_mut().map(|(cb, _)| cb).collect())
+        {
+            args.push("-filter_complex".into());
+            args.push(if quote { quote_arg(&graph) } else { graph });
+        }
+        for (command_builder, filename) in builders {
+            self.push_outputs(&mut args, &command_builder, &filename, quote);
+            if several {
+                warnings.extend(
+                    command_builder
+                        .warnings
+                        .iter()
+                        .map(|warning| format!("{filename}: {warning}")),
+                );
+            } else {
+                warnings.extend(command_builder.warnings);
+            }
+        }
+        Ok((args, warnings))
+    }
+
+    /// Output options and files of a variant
+    fn push_outputs(
+        &self,
+        args: &mut Vec<String>,
+        command_builder: &CommandBuilder,
+        filename: &str,
+        quote: bool,
+    ) {
+        let output_file = |name: &str| {
+            let mut path = PathBuf::new().join(&*self.output_folder).join(name);
+            path.add_extension(&*command_builder.ext);
+            let output_file = path.display().to_string();
+            if quote {
+                quote_arg(&output_file)
+            } else {
+                output_file
+            }
+        };
+        match command_builder.output_files() {
+            OutputFiles::Single => {
+                args.extend(command_builder.build_args(quote));
+                args.push(output_file(filename));
+            }
+            OutputFiles::Segments(_) => {
+                args.extend(command_builder.build_args(quote));
+                args.push(output_file(&OutputFiles::segment_pattern(filename)));
+            }
+            OutputFiles::Frames(_) => {
+                args.extend(command_builder.build_args(quote));
+                args.push(output_file(&OutputFiles::frame_pattern(filename)));
+            }
+            OutputFiles::Package(format) => {
+                args.extend(command_builder.build_args(quote));
+                if let Some((option, segment)) = format.segment_option() {
+                    args.push(option.to_owned());
+                    let path = PathBuf::new()
+                        .join(&*self.output_folder)
+                        .join(filename)
+                        .join(segment)
+                        .display()
+                        .to_string();
+                    args.push(if quote { quote_arg(&path) } else { path });
+                }
+                args.push(output_file(&format!("{}/{}", filename, format.output())));
+            }
+            OutputFiles::Parts(_) => {
+                let parts_args = command_builder.build_parts_args(quote);
+                for (part, part_args) in command_builder.output_parts.iter().zip(parts_args) {
+                    args.extend(part_args);
+                    args.push(output_file(&format!("{}{}", filename, part.suffix)));
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,105 @@
+use std::io::{Error, Read};
+use std::process::{Command, Stdio};
+
+use tracing::{debug, warn};
+
+// Detect optional FFmpeg build features
+
+/// Optional FFmpeg features, everything is considered available if detection fails
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Features {
+    /// librubberband pitch shifting filter
+    pub rubberband: bool,
+    /// libsoxr high quality resampler
+    pub soxr: bool,
+}
+
+impl Default for Features {
+    fn default() -> Self {
+        Self {
+            rubberband: true,
+            soxr: true,
+        }
+    }
+}
+
+impl Features {
+    /// Detect features by `ffmpeg -filters` and `ffmpeg -version` output
+    pub fn from_output(filters: &str, version: &str) -> Self {
+        // " T.C rubberband        A->A       Apply time-stretching and pitch-shifting."
+        let has_filter = |name: &str| {
+            filters.lines().any(|line| {
+                let mut parts = line.split_whitespace();
+                parts.nth(1) == Some(name) && parts.next().is_some_and(|io| io.contains("->"))
+            })
+        };
+        Self {
+            rubberband: has_filter("rubberband"),
+            soxr: version.contains("--enable-libsoxr"),
+        }
+    }
+}
+
+pub(crate) fn get_features() -> Features {
+    match run_ffmpeg("-filters").and_then(|filters| Ok((filters, run_ffmpeg("-version")?))) {
+        Ok((filters, version)) => {
+            let features = Features::from_output(&filters, &version);
+            debug!(?features, "ffmpeg features");
+            features
+        }
+        Err(e) => {
+            warn!("Unable to detect ffmpeg features: {e}");
+            Features::default()
+        }
+    }
+}
+
+fn run_ffmpeg(arg: &str) -> Result<String, Error> {
+    let mut child = Command::new("ffmpeg")
+        .args(["-hide_banner", arg])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    let mut output = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        stdout.read_to_string(&mut output)?;
+    }
+
+    let status = child.wait()?;
+    if status.success() {
+        Ok(output)
+    } else {
+        Err(Error::other(format!("ffmpeg exited with status: {status}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_output() {
+        let filters = "Filters:
+  T.. = Timeline support
+  .S. = Slice threading
+  A = Audio input/output
+  | = Source or sink filter
+ ... abench            A->A       Benchmark part of a filtergraph.
+ TSC rubberband        A->A       Apply time-stretching and pitch-shifting.
+";
+        let version = "ffmpeg version 7.1 Copyright (c) 2000-2024 the FFmpeg developers
+configuration: --prefix=/usr --enable-gpl --enable-libsoxr --enable-libx264
+";
+        let features = Features::from_output(filters, version);
+        assert!(features.rubberband);
+        assert!(features.soxr);
+        let features = Features::from_output(
+            &filters.replace("rubberband", "atempo"),
+            &version.replace("--enable-libsoxr ", ""),
+        );
+        assert!(!features.rubberband);
+        assert!(!features.soxr);
+    }
+}
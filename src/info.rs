@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::io::{Error, Read};
+use std::path::Path;
 use std::process::{Command, Stdio};
 
 use ratatui::text::{Line, Span, Text};
@@ -15,6 +16,8 @@ pub(crate) struct Info {
     pub format: InfoFormat,
     #[serde(default)]
     pub streams: Vec<InfoStream>,
+    #[serde(default)]
+    pub chapters: Vec<InfoChapter>,
 }
 
 #[derive(Deserialize, Default, Clone, Debug)]
@@ -24,6 +27,8 @@ pub(crate) struct InfoFormat {
     pub duration: Option<String>,
     pub size: Option<String>,
     pub bit_rate: Option<String>,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -46,6 +51,34 @@ pub(crate) struct InfoStream {
     pub other: HashMap<String, serde_json::Value>,
 }
 
+#[derive(Deserialize, Default, Clone, Debug)]
+pub(crate) struct InfoChapter {
+    pub start_time: String,
+    pub end_time: String,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+}
+
+impl InfoChapter {
+    pub fn start(&self) -> f64 {
+        self.start_time.parse().unwrap_or_default()
+    }
+
+    pub fn end(&self) -> f64 {
+        self.end_time.parse().unwrap_or_default()
+    }
+
+    /// Chapter title or its number, counting from 1
+    pub fn title(&self, index: usize) -> String {
+        self.tags
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("title"))
+            .map(|(_, v)| v.trim())
+            .filter(|v| !v.is_empty())
+            .map_or_else(|| format!("Chapter {}", index + 1), ToOwned::to_owned)
+    }
+}
+
 impl Info {
     pub fn parse(json: &str) -> Result<Self, Error> {
         serde_json::from_str(json).map_err(Error::other)
@@ -72,13 +105,7 @@ impl Info {
             let fps = s
                 .avg_frame_rate
                 .as_deref()
-                .and_then(|fr| {
-                    // avg_frame_rate 25/1
-                    let (num, den) = fr.split_once('/')?;
-                    let num = num.parse::<f64>().ok()?;
-                    let den = den.parse::<f64>().ok()?;
-                    if den == 0.0 { None } else { Some(num / den) }
-                })
+                .and_then(Self::parse_frame_rate)
                 .unwrap_or(0.0);
 
             let duration = s
@@ -99,6 +126,165 @@ impl Info {
             .and_then(|dur_str| dur_str.parse::<f64>().ok())
     }
 
+    /// Short description of the input: filename, duration, resolution and size
+    pub fn summary(&self) -> String {
+        let filename = &self.format.filename;
+        let mut parts = vec![Path::new(filename).file_name().map_or_else(
+            || filename.clone(),
+            |name| name.to_string_lossy().into_owned(),
+        )];
+        if let Some(duration) = self.get_duration() {
+            parts.push(format_timestamp(duration));
+        }
+        if let Some((width, height)) = self.video_size() {
+            parts.push(format!("{width}x{height}"));
+        }
+        if let Some(size) = self.format.size.as_deref().and_then(|s| s.parse().ok()) {
+            parts.push(format_size(size));
+        }
+        parts.join(" | ")
+    }
+
+    /// Display size of the first video stream, rotated if needed
+    pub fn video_size(&self) -> Option<(u32, u32)> {
+        let stream = self.video_stream()?;
+        let (width, height) = (stream.width?, stream.height?);
+        let rotation = stream
+            .other
+            .get("side_data_list")
+            .and_then(serde_json::Value::as_array)
+            .and_then(|list| list.iter().find_map(|d| d.get("rotation")?.as_i64()))
+            .or_else(|| {
+                stream
+                    .other
+                    .get("tags")
+                    .and_then(|t| t.get("rotate")?.as_str()?.parse().ok())
+            })
+            .unwrap_or(0);
+        if rotation.abs() % 180 == 90 {
+            Some((height, width))
+        } else {
+            Some((width, height))
+        }
+    }
+
+    /// Average frame rate of the first video stream
+    pub fn frame_rate(&self) -> Option<f64> {
+        self.video_stream()?
+            .avg_frame_rate
+            .as_deref()
+            .and_then(Self::parse_frame_rate)
+            .filter(|fps| *fps > 0.0)
+    }
+
+    /// Video bitrate in bits per second.
+    /// Not all containers store it per stream, so fall back to the overall bitrate without audio
+    pub fn video_bitrate(&self) -> Option<u64> {
+        let stream = self.video_stream()?;
+        Self::parse_bitrate(stream.bit_rate.as_deref()).or_else(|| {
+            let total = Self::parse_bitrate(self.format.bit_rate.as_deref())?;
+            let audio: u64 = self
+                .streams
+                .iter()
+                .filter(|s| matches!(&s.codec_type, Some(t) if t == "audio"))
+                .filter_map(|s| Self::parse_bitrate(s.bit_rate.as_deref()))
+                .sum();
+            Some(total.saturating_sub(audio)).filter(|b| *b > 0)
+        })
+    }
+
+    /// Audio bitrate of the first audio stream in bits per second
+    pub fn audio_bitrate(&self) -> Option<u64> {
+        let stream = self
+            .streams
+            .iter()
+            .find(|s| matches!(&s.codec_type, Some(t) if t == "audio"))?;
+        Self::parse_bitrate(stream.bit_rate.as_deref()).or_else(|| {
+            if self.has_audio_only() {
+                Self::parse_bitrate(self.format.bit_rate.as_deref())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Sample rate of the first audio stream
+    pub fn sample_rate(&self) -> Option<u32> {
+        self.streams
+            .iter()
+            .find(|s| matches!(&s.codec_type, Some(t) if t == "audio"))?
+            .sample_rate
+            .as_deref()?
+            .parse()
+            .ok()
+    }
+
+    /// Channel count of the first audio stream
+    pub fn audio_channels(&self) -> Option<u32> {
+        self.streams
+            .iter()
+            .find(|s| matches!(&s.codec_type, Some(t) if t == "audio"))?
+            .channels
+            .filter(|c| *c > 0)
+    }
+
+    /// Format tag, the key case depends on the container (`title` in MP4, `TITLE` in FLAC)
+    pub fn format_tag(&self, key: &str) -> Option<&str> {
+        self.format
+            .tags
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Tag of the first audio stream, e.g. language
+    pub fn audio_tag(&self, key: &str) -> Option<&str> {
+        self.streams
+            .iter()
+            .find(|s| matches!(&s.codec_type, Some(t) if t == "audio"))?
+            .other
+            .get("tags")?
+            .as_object()?
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))?
+            .1
+            .as_str()
+    }
+
+    /// Has an attached picture (cover art)
+    pub fn has_cover_art(&self) -> bool {
+        self.streams.iter().any(Self::is_attached_pic)
+    }
+
+    /// First video stream, except for attached pictures (cover art)
+    fn video_stream(&self) -> Option<&InfoStream> {
+        self.streams
+            .iter()
+            .find(|s| matches!(&s.codec_type, Some(t) if t == "video") && !Self::is_attached_pic(s))
+    }
+
+    fn is_attached_pic(stream: &InfoStream) -> bool {
+        matches!(&stream.codec_type, Some(t) if t == "video")
+            && stream
+                .other
+                .get("disposition")
+                .and_then(|d| d.get("attached_pic"))
+                .and_then(serde_json::Value::as_u64)
+                == Some(1)
+    }
+
+    /// Parse frame rate fraction, e.g. avg_frame_rate 25/1
+    fn parse_frame_rate(fr: &str) -> Option<f64> {
+        let (num, den) = fr.split_once('/')?;
+        let num = num.parse::<f64>().ok()?;
+        let den = den.parse::<f64>().ok()?;
+        if den == 0.0 { None } else { Some(num / den) }
+    }
+
+    fn parse_bitrate(bitrate: Option<&str>) -> Option<u64> {
+        bitrate?.parse::<u64>().ok().filter(|b| *b > 0)
+    }
+
     fn has_stream_type(&self, stream_type: &str) -> bool {
         if self.format.nb_streams == 0 {
             false
@@ -132,6 +318,24 @@ impl Info {
         format_val!(format.duration, "duration");
         format_val!(format.size, "size");
         format_val!(format.bit_rate, "bit_rate");
+        let mut tags: Vec<_> = format.tags.iter().collect();
+        tags.sort();
+        for (tag, value) in tags {
+            add(&format!("tag_{tag}"), value, 0);
+        }
+
+        for (i, chapter) in self.chapters.iter().enumerate() {
+            add(
+                &format!("chapter_{}", i + 1),
+                &format!(
+                    "{} - {} {}",
+                    format_timestamp(chapter.start()),
+                    format_timestamp(chapter.end()),
+                    chapter.title(i)
+                ),
+                0,
+            );
+        }
 
         for stream in &self.streams {
             let index = stream.index;
@@ -179,6 +383,36 @@ impl Info {
     }
 }
 
+/// Format seconds as HH:MM:SS.mmm, the format of the trim values
+pub(crate) fn format_timestamp(seconds: f64) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+/// Format bytes with decimal units, e.g. 12.3 MB
+pub(crate) fn format_size(bytes: u64) -> String {
+    let mut size = bytes as f64;
+    let mut unit = "B";
+    for next in ["KB", "MB", "GB", "TB"] {
+        if size < 1000.0 {
+            break;
+        }
+        size /= 1000.0;
+        unit = next;
+    }
+    if unit == "B" {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {unit}")
+    }
+}
+
 pub(crate) fn get_info(input_file: &str) -> Result<Info, Error> {
     let mut child = Command::new("ffprobe")
         .args([
@@ -188,6 +422,7 @@ pub(crate) fn get_info(input_file: &str) -> Result<Info, Error> {
             "json=compact=1",
             "-show_format",
             "-show_streams",
+            "-show_chapters",
         ])
         .arg(input_file)
         .stdin(Stdio::null())
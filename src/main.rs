@@ -11,7 +11,9 @@ use crossterm::event::{Event, KeyEventKind};
 
 use crate::{model::AppEvent, source::Source};
 
+mod analysis;
 mod app;
+mod features;
 mod info;
 mod logging;
 mod model;
@@ -27,9 +29,9 @@ mod visitors;
     about = "A small and friendly terminal FFmpeg helper that simplifies common tasks"
 )]
 struct Cli {
-    /// Specify parameter values
+    /// Specify parameter values, repeat to render several variants at once
     #[arg(short, long)]
-    preset: Option<String>,
+    preset: Vec<String>,
 
     /// Apply preset immediately without UI
     #[arg(long, requires = "preset")]
@@ -57,9 +59,11 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
     };
 
+    let features = features::get_features();
+
     if cli.apply {
         let (tx, _) = mpsc::channel();
-        App::new(tx, &ffprobe_info, source, cli.preset.as_deref()).run_cli();
+        App::new(tx, &ffprobe_info, features, source, &cli.preset).run_cli();
         process::exit(0);
     }
 
@@ -67,7 +71,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         let (tx, rx) = mpsc::channel();
         let event_tx = tx.clone();
         thread::spawn(move || handle_crossterm_events(&event_tx));
-        App::new(tx, &ffprobe_info, source, cli.preset.as_deref()).run(terminal, &rx)
+        App::new(tx, &ffprobe_info, features, source, &cli.preset).run(terminal, &rx)
     })
 }
 
@@ -206,6 +206,1559 @@ impl TimeValue {
     }
 }
 
+/// Position of an overlay relative to the video frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum OverlayPosition {
+    TopLeft,
+    Top,
+    TopRight,
+    BottomLeft,
+    Bottom,
+    #[default]
+    BottomRight,
+    Center,
+}
+
+impl OverlayPosition {
+    pub(crate) const ALL: [OverlayPosition; 7] = [
+        OverlayPosition::TopLeft,
+        OverlayPosition::Top,
+        OverlayPosition::TopRight,
+        OverlayPosition::BottomLeft,
+        OverlayPosition::Bottom,
+        OverlayPosition::BottomRight,
+        OverlayPosition::Center,
+    ];
+
+    /// Short name used in presets
+    pub(crate) fn id(self) -> &'static str {
+        match self {
+            OverlayPosition::TopLeft => "tl",
+            OverlayPosition::Top => "t",
+            OverlayPosition::TopRight => "tr",
+            OverlayPosition::BottomLeft => "bl",
+            OverlayPosition::Bottom => "b",
+            OverlayPosition::BottomRight => "br",
+            OverlayPosition::Center => "center",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            OverlayPosition::TopLeft => "top left",
+            OverlayPosition::Top => "top",
+            OverlayPosition::TopRight => "top right",
+            OverlayPosition::BottomLeft => "bottom left",
+            OverlayPosition::Bottom => "bottom",
+            OverlayPosition::BottomRight => "bottom right",
+            OverlayPosition::Center => "center",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.id() == id)
+    }
+
+    pub(crate) fn prev(self) -> Self {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub(crate) fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Overlay filter x:y expressions, `W`/`H` is the main video size, `w`/`h` is the overlay size
+    pub(crate) fn overlay_expr(self, margin: u32) -> String {
+        self.position_expr(margin, ("W", "H"), ("w", "h"))
+    }
+
+    /// Drawtext filter x:y expressions, `w`/`h` is the video size, `tw`/`th` is the text size
+    pub(crate) fn drawtext_expr(self, margin: u32) -> String {
+        self.position_expr(margin, ("w", "h"), ("tw", "th"))
+    }
+
+    fn position_expr(self, margin: u32, (fw, fh): (&str, &str), (w, h): (&str, &str)) -> String {
+        let x = match self {
+            OverlayPosition::TopLeft | OverlayPosition::BottomLeft => margin.to_string(),
+            OverlayPosition::TopRight | OverlayPosition::BottomRight => {
+                format!("{fw}-{w}-{margin}")
+            }
+            OverlayPosition::Top | OverlayPosition::Bottom | OverlayPosition::Center => {
+                format!("({fw}-{w})/2")
+            }
+        };
+        let y = match self {
+            OverlayPosition::TopLeft | OverlayPosition::Top | OverlayPosition::TopRight => {
+                margin.to_string()
+            }
+            OverlayPosition::BottomLeft
+            | OverlayPosition::Bottom
+            | OverlayPosition::BottomRight => {
+                format!("{fh}-{h}-{margin}")
+            }
+            OverlayPosition::Center => format!("({fh}-{h})/2"),
+        };
+        format!("x={x}:y={y}")
+    }
+}
+
+/// Image overlay (watermark/logo) parameters
+#[derive(Debug, Clone)]
+pub(crate) struct OverlayData {
+    pub(crate) path: Option<String>,
+    pub(crate) position: OverlayPosition,
+    pub(crate) margin: u32,
+    /// Overlay height in percents of the video height, 0 - keep original size
+    pub(crate) scale: u32,
+    /// Opacity in percents
+    pub(crate) opacity: u32,
+}
+
+impl Default for OverlayData {
+    fn default() -> Self {
+        Self {
+            path: None,
+            position: OverlayPosition::default(),
+            margin: 10,
+            scale: 10,
+            opacity: 100,
+        }
+    }
+}
+
+impl OverlayData {
+    pub(crate) fn validate(
+        path: &str,
+        margin: &str,
+        scale: &str,
+        opacity: &str,
+    ) -> Result<(u32, u32, u32), &'static str> {
+        if !path.is_empty() && std::fs::metadata(path).is_err() {
+            return Err("Image file does not exist");
+        }
+        let margin = margin
+            .parse::<u32>()
+            .ok()
+            .filter(|m| *m <= 1000)
+            .ok_or("Margin must be in range 0..1000")?;
+        let scale = scale
+            .parse::<u32>()
+            .ok()
+            .filter(|s| *s <= 100)
+            .ok_or("Scale must be in range 0..100, or 0 - original size")?;
+        let opacity = opacity
+            .parse::<u32>()
+            .ok()
+            .filter(|o| (1..=100).contains(o))
+            .ok_or("Opacity must be in range 1..100")?;
+        Ok((margin, scale, opacity))
+    }
+}
+
+impl Display for OverlayData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Some(path) = &self.path else {
+            return write!(f, "none");
+        };
+        let name = std::path::Path::new(path)
+            .file_name()
+            .map_or_else(|| path.clone(), |n| n.to_string_lossy().to_string());
+        write!(f, "{name}, {}", self.position.label())?;
+        if self.scale != 0 {
+            write!(f, ", {}%h", self.scale)?;
+        }
+        if self.opacity != 100 {
+            write!(f, ", {}% opacity", self.opacity)?;
+        }
+        Ok(())
+    }
+}
+
+/// Text overlay (caption or timecode) parameters
+#[derive(Debug, Clone)]
+pub(crate) struct TextData {
+    pub(crate) text: String,
+    /// Append a running timecode to the text
+    pub(crate) timecode: bool,
+    pub(crate) font: Option<String>,
+    pub(crate) size: u32,
+    pub(crate) color: String,
+    /// Draw a semi-transparent box under the text
+    pub(crate) boxed: bool,
+    pub(crate) position: OverlayPosition,
+    pub(crate) start: Option<String>,
+    pub(crate) end: Option<String>,
+}
+
+impl Default for TextData {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            timecode: false,
+            font: None,
+            size: 32,
+            color: "white".to_owned(),
+            boxed: false,
+            position: OverlayPosition::Bottom,
+            start: None,
+            end: None,
+        }
+    }
+}
+
+impl TextData {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.text.is_empty() && !self.timecode
+    }
+
+    pub(crate) fn validate(
+        font: &str,
+        size: &str,
+        color: &str,
+        start: &str,
+        end: &str,
+    ) -> Result<u32, &'static str> {
+        if !font.is_empty() && std::fs::metadata(font).is_err() {
+            return Err("Font file does not exist");
+        }
+        let size = size
+            .parse::<u32>()
+            .ok()
+            .filter(|s| (6..=500).contains(s))
+            .ok_or("Font size must be in range 6..500")?;
+        if !is_valid_color(color) {
+            return Err("Color must be a name or #RRGGBB[AA], optionally with @opacity");
+        }
+        let is_time =
+            |v: &str| v.is_empty() || (TrimData::valid_value(v) && !v.contains(['-', '%']));
+        if !is_time(start) || !is_time(end) {
+            return Err("Incorrect start/end time format");
+        }
+        Ok(size)
+    }
+}
+
+impl Display for TextData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "none");
+        }
+        if !self.text.is_empty() {
+            let text: String = self.text.chars().take(16).collect();
+            let ellipsis = if self.text.chars().count() > 16 {
+                "…"
+            } else {
+                ""
+            };
+            write!(f, "\"{text}{ellipsis}\"")?;
+            if self.timecode {
+                write!(f, " + ")?;
+            }
+        }
+        if self.timecode {
+            write!(f, "timecode")?;
+        }
+        write!(f, ", {}", self.position.label())?;
+        if self.start.is_some() || self.end.is_some() {
+            write!(
+                f,
+                ", {}..{}",
+                self.start.as_deref().unwrap_or("start"),
+                self.end.as_deref().unwrap_or("end")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// How the video is fitted into the target aspect ratio
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum AspectMode {
+    #[default]
+    Pad,
+    Blur,
+    Crop,
+}
+
+impl AspectMode {
+    pub(crate) const ALL: [AspectMode; 3] = [AspectMode::Pad, AspectMode::Blur, AspectMode::Crop];
+
+    pub(crate) fn id(self) -> &'static str {
+        match self {
+            AspectMode::Pad => "pad",
+            AspectMode::Blur => "blur",
+            AspectMode::Crop => "crop",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            AspectMode::Pad => "pad with color",
+            AspectMode::Blur => "pad with blur",
+            AspectMode::Crop => "center crop",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.id() == id)
+    }
+
+    pub(crate) fn prev(self) -> Self {
+        let index = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub(crate) fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Target aspect ratio parameters
+#[derive(Debug, Clone)]
+pub(crate) struct AspectData {
+    /// Target ratio as width:height, None - keep the original
+    pub(crate) ratio: Option<(u32, u32)>,
+    pub(crate) mode: AspectMode,
+    /// Padding color for `AspectMode::Pad`
+    pub(crate) color: String,
+    /// Source video size, used to calculate the resulting resolution
+    pub(crate) source: Option<(u32, u32)>,
+}
+
+impl Default for AspectData {
+    fn default() -> Self {
+        Self {
+            ratio: None,
+            mode: AspectMode::default(),
+            color: "black".to_owned(),
+            source: None,
+        }
+    }
+}
+
+impl AspectData {
+    pub(crate) const RATIOS: [(u32, u32); 6] = [(9, 16), (4, 5), (1, 1), (4, 3), (16, 9), (21, 9)];
+
+    pub(crate) fn ratio_label(ratio: Option<(u32, u32)>) -> String {
+        ratio.map_or_else(|| "original".to_owned(), |(w, h)| format!("{w}:{h}"))
+    }
+
+    pub(crate) fn parse_ratio(value: &str) -> Option<(u32, u32)> {
+        let (w, h) = value.split_once(':')?;
+        let ratio = (w.parse().ok()?, h.parse().ok()?);
+        Self::RATIOS.contains(&ratio).then_some(ratio)
+    }
+
+    pub(crate) fn prev_ratio(ratio: Option<(u32, u32)>) -> Option<(u32, u32)> {
+        match ratio.and_then(|r| Self::RATIOS.iter().position(|x| *x == r)) {
+            None => Self::RATIOS.last().copied(),
+            Some(0) => None,
+            Some(index) => Some(Self::RATIOS[index - 1]),
+        }
+    }
+
+    pub(crate) fn next_ratio(ratio: Option<(u32, u32)>) -> Option<(u32, u32)> {
+        match ratio.and_then(|r| Self::RATIOS.iter().position(|x| *x == r)) {
+            None => Self::RATIOS.first().copied(),
+            Some(index) => Self::RATIOS.get(index + 1).copied(),
+        }
+    }
+
+    pub(crate) fn validate(color: &str) -> Result<(), &'static str> {
+        if is_valid_color(color) {
+            Ok(())
+        } else {
+            Err("Color must be a name or #RRGGBB[AA]")
+        }
+    }
+
+    /// Resulting video size, or None if it is the same as the source one.
+    /// Padding keeps the longer side of the source, cropping fits into the source frame
+    pub(crate) fn target_size(&self) -> Option<(u32, u32)> {
+        let (rw, rh) = self.ratio?;
+        let (w, h) = self.source?;
+        let even = |v: u64| u32::try_from(v / 2 * 2).unwrap_or(u32::MAX).max(2);
+        let (w64, h64, rw, rh) = (u64::from(w), u64::from(h), u64::from(rw), u64::from(rh));
+        let size = match self.mode {
+            AspectMode::Pad | AspectMode::Blur => {
+                let long = w64.max(h64);
+                if rw >= rh {
+                    (even(long), even(long * rh / rw))
+                } else {
+                    (even(long * rw / rh), even(long))
+                }
+            }
+            AspectMode::Crop => (even(w64.min(h64 * rw / rh)), even(h64.min(w64 * rh / rw))),
+        };
+        (size != (w, h)).then_some(size)
+    }
+}
+
+impl Display for AspectData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.ratio.is_none() {
+            return write!(f, "original");
+        }
+        write!(f, "{} {}", Self::ratio_label(self.ratio), self.mode.id())?;
+        if let Some((w, h)) = self.target_size() {
+            write!(f, ", {w}x{h}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Equalizer preset, selecting one sets the bands to its values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum EqPreset {
+    #[default]
+    Off,
+    BassBoost,
+    TrebleBoost,
+    Voice,
+    Telephone,
+    Radio,
+    Custom,
+}
+
+impl EqPreset {
+    pub(crate) const ALL: [EqPreset; 7] = [
+        EqPreset::Off,
+        EqPreset::BassBoost,
+        EqPreset::TrebleBoost,
+        EqPreset::Voice,
+        EqPreset::Telephone,
+        EqPreset::Radio,
+        EqPreset::Custom,
+    ];
+
+    pub(crate) fn id(self) -> &'static str {
+        match self {
+            EqPreset::Off => "off",
+            EqPreset::BassBoost => "bass",
+            EqPreset::TrebleBoost => "treble",
+            EqPreset::Voice => "voice",
+            EqPreset::Telephone => "phone",
+            EqPreset::Radio => "radio",
+            EqPreset::Custom => "custom",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            EqPreset::Off => "off",
+            EqPreset::BassBoost => "bass boost",
+            EqPreset::TrebleBoost => "treble boost",
+            EqPreset::Voice => "voice",
+            EqPreset::Telephone => "telephone",
+            EqPreset::Radio => "radio",
+            EqPreset::Custom => "custom",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.id() == id)
+    }
+
+    pub(crate) fn prev(self) -> Self {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub(crate) fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Band values of the preset, None for the custom one
+    pub(crate) fn bands(self) -> Option<EqBands> {
+        let off = EqBands::default();
+        match self {
+            EqPreset::Off => Some(off),
+            EqPreset::BassBoost => Some(EqBands { bass: 6, ..off }),
+            EqPreset::TrebleBoost => Some(EqBands { treble: 6, ..off }),
+            EqPreset::Voice => Some(EqBands {
+                mid: 3,
+                highpass: 100,
+                ..off
+            }),
+            EqPreset::Telephone => Some(EqBands {
+                highpass: 300,
+                lowpass: 3400,
+                ..off
+            }),
+            EqPreset::Radio => Some(EqBands {
+                mid: 3,
+                highpass: 200,
+                lowpass: 5000,
+                ..off
+            }),
+            EqPreset::Custom => None,
+        }
+    }
+}
+
+/// Equalizer bands: gains in dB and cutoff frequencies in Hz, 0 - no filter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct EqBands {
+    pub(crate) bass: i32,
+    pub(crate) mid: i32,
+    pub(crate) treble: i32,
+    pub(crate) highpass: u32,
+    pub(crate) lowpass: u32,
+}
+
+impl EqBands {
+    pub(crate) const MAX_GAIN: i32 = 12;
+    pub(crate) const HIGHPASS: [u32; 7] = [0, 40, 80, 100, 150, 200, 300];
+    pub(crate) const LOWPASS: [u32; 6] = [0, 3400, 5000, 8000, 12000, 16000];
+    const MID_FREQUENCY: u32 = 1000;
+
+    /// FFmpeg audio filters for the bands
+    pub(crate) fn filters(&self) -> Vec<String> {
+        let mut filters = Vec::new();
+        if self.highpass > 0 {
+            filters.push(format!("highpass=f={}", self.highpass));
+        }
+        if self.lowpass > 0 {
+            filters.push(format!("lowpass=f={}", self.lowpass));
+        }
+        if self.bass != 0 {
+            filters.push(format!("bass=g={}", self.bass));
+        }
+        if self.mid != 0 {
+            filters.push(format!(
+                "equalizer=f={}:t=o:w=2:g={}",
+                Self::MID_FREQUENCY,
+                self.mid
+            ));
+        }
+        if self.treble != 0 {
+            filters.push(format!("treble=g={}", self.treble));
+        }
+        filters
+    }
+
+    /// Parse "bass,mid,treble,highpass,lowpass"
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let parts: Vec<&str> = value.split(',').collect();
+        let [bass, mid, treble, highpass, lowpass] = parts[..] else {
+            return None;
+        };
+        let gain = |v: &str| v.parse::<i32>().ok().filter(|g| g.abs() <= Self::MAX_GAIN);
+        let cutoff =
+            |v: &str, allowed: &[u32]| v.parse::<u32>().ok().filter(|f| allowed.contains(f));
+        Some(Self {
+            bass: gain(bass)?,
+            mid: gain(mid)?,
+            treble: gain(treble)?,
+            highpass: cutoff(highpass, &Self::HIGHPASS)?,
+            lowpass: cutoff(lowpass, &Self::LOWPASS)?,
+        })
+    }
+
+    pub(crate) fn gain_label(gain: i32) -> String {
+        if gain == 0 {
+            "0dB".to_owned()
+        } else {
+            format!("{gain:+}dB")
+        }
+    }
+
+    pub(crate) fn cutoff_label(frequency: u32) -> String {
+        if frequency == 0 {
+            "off".to_owned()
+        } else {
+            format!("{frequency}Hz")
+        }
+    }
+
+    /// Step a cutoff frequency through the allowed values
+    pub(crate) fn step_cutoff(frequency: u32, allowed: &[u32], forward: bool) -> u32 {
+        let index = allowed.iter().position(|f| *f == frequency).unwrap_or(0);
+        if forward {
+            allowed[(index + 1).min(allowed.len() - 1)]
+        } else {
+            allowed[index.saturating_sub(1)]
+        }
+    }
+
+    pub(crate) fn step_gain(gain: i32, forward: bool) -> i32 {
+        let gain = if forward { gain + 1 } else { gain - 1 };
+        gain.clamp(-Self::MAX_GAIN, Self::MAX_GAIN)
+    }
+}
+
+impl Display for EqBands {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{},{},{},{},{}",
+            self.bass, self.mid, self.treble, self.highpass, self.lowpass
+        )
+    }
+}
+
+/// Audio equalizer parameters
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct EqData {
+    pub(crate) preset: EqPreset,
+    pub(crate) bands: EqBands,
+}
+
+impl EqData {
+    pub(crate) fn with_preset(preset: EqPreset) -> Self {
+        Self {
+            preset,
+            bands: preset.bands().unwrap_or_default(),
+        }
+    }
+
+    /// Custom bands, matching a preset are shown as the preset
+    pub(crate) fn with_bands(bands: EqBands) -> Self {
+        let preset = EqPreset::ALL
+            .into_iter()
+            .find(|p| p.bands() == Some(bands))
+            .unwrap_or(EqPreset::Custom);
+        Self { preset, bands }
+    }
+
+    pub(crate) fn is_off(&self) -> bool {
+        self.bands == EqBands::default()
+    }
+}
+
+impl Display for EqData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.preset != EqPreset::Custom {
+            return write!(f, "{}", self.preset.label());
+        }
+        let bands = &self.bands;
+        let mut parts = Vec::new();
+        for (name, gain) in [
+            ("bass", bands.bass),
+            ("mid", bands.mid),
+            ("treble", bands.treble),
+        ] {
+            if gain != 0 {
+                parts.push(format!("{name} {}", EqBands::gain_label(gain)));
+            }
+        }
+        if bands.highpass > 0 {
+            parts.push(format!("hp {}Hz", bands.highpass));
+        }
+        if bands.lowpass > 0 {
+            parts.push(format!("lp {}Hz", bands.lowpass));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Which silent parts are removed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum SilenceScope {
+    #[default]
+    Off,
+    /// Leading and trailing silence only
+    Edges,
+    /// Every silent gap
+    All,
+}
+
+impl SilenceScope {
+    pub(crate) const ALL: [SilenceScope; 3] =
+        [SilenceScope::Off, SilenceScope::Edges, SilenceScope::All];
+
+    pub(crate) fn id(self) -> &'static str {
+        match self {
+            SilenceScope::Off => "off",
+            SilenceScope::Edges => "edges",
+            SilenceScope::All => "all",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            SilenceScope::Off => "off",
+            SilenceScope::Edges => "leading/trailing",
+            SilenceScope::All => "all gaps",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.id() == id)
+    }
+
+    pub(crate) fn prev(self) -> Self {
+        let index = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub(crate) fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Silent ranges found by `silencedetect` for the given threshold and duration
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SilenceDetection {
+    pub(crate) threshold: i32,
+    pub(crate) duration: f64,
+    /// Start and end of the silent parts, seconds
+    pub(crate) ranges: Vec<(f64, f64)>,
+}
+
+/// Silence removal parameters
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SilenceData {
+    pub(crate) scope: SilenceScope,
+    /// Noise level considered as silence, dB
+    pub(crate) threshold: i32,
+    /// Minimal silence duration, seconds
+    pub(crate) duration: f64,
+    /// Input has video, the silence must be detected first to keep it in sync
+    pub(crate) video: bool,
+    pub(crate) detection: Option<SilenceDetection>,
+}
+
+impl Default for SilenceData {
+    fn default() -> Self {
+        Self {
+            scope: SilenceScope::default(),
+            threshold: -40,
+            duration: 0.5,
+            video: false,
+            detection: None,
+        }
+    }
+}
+
+impl SilenceData {
+    pub(crate) const THRESHOLDS: [i32; 9] = [-60, -55, -50, -45, -40, -35, -30, -25, -20];
+    pub(crate) const DURATIONS: [f64; 7] = [0.1, 0.25, 0.5, 1.0, 2.0, 3.0, 5.0];
+
+    pub(crate) fn is_off(&self) -> bool {
+        self.scope == SilenceScope::Off
+    }
+
+    /// Detected ranges if they match the current threshold and duration
+    pub(crate) fn detected_ranges(&self) -> Option<&[(f64, f64)]> {
+        self.detection
+            .as_ref()
+            .filter(|d| d.threshold == self.threshold && d.duration == self.duration)
+            .map(|d| d.ranges.as_slice())
+    }
+
+    /// Video inputs need a `silencedetect` run before the command can be built
+    pub(crate) fn needs_detection(&self) -> bool {
+        self.video && !self.is_off() && self.detected_ranges().is_none()
+    }
+
+    /// Ranges to cut according to the scope, `duration` is the input one
+    pub(crate) fn cut_ranges(&self, duration: Option<f64>) -> Vec<(f64, f64)> {
+        const EDGE: f64 = 0.1;
+        let Some(ranges) = self.detected_ranges() else {
+            return Vec::new();
+        };
+        match self.scope {
+            SilenceScope::Off => Vec::new(),
+            SilenceScope::All => ranges.to_vec(),
+            SilenceScope::Edges => ranges
+                .iter()
+                .filter(|(start, end)| {
+                    *start < EDGE || duration.is_some_and(|duration| *end > duration - EDGE)
+                })
+                .copied()
+                .collect(),
+        }
+    }
+
+    pub(crate) fn step_threshold(&mut self, forward: bool) {
+        self.threshold = step(&Self::THRESHOLDS, self.threshold, forward);
+    }
+
+    pub(crate) fn step_duration(&mut self, forward: bool) {
+        self.duration = step(&Self::DURATIONS, self.duration, forward);
+    }
+}
+
+/// Neighbour of the current value in the list, stops at the ends
+fn step<T: Copy + PartialEq>(values: &[T], current: T, forward: bool) -> T {
+    let index = values.iter().position(|v| *v == current).unwrap_or(0);
+    if forward {
+        values[(index + 1).min(values.len() - 1)]
+    } else {
+        values[index.saturating_sub(1)]
+    }
+}
+
+/// Set a preset value if it is one of the allowed
+pub(crate) fn set_allowed<T: FromStr + PartialEq>(
+    values: &[T],
+    value: &str,
+    target: &mut T,
+) -> bool {
+    match value.parse() {
+        Ok(value) if values.contains(&value) => {
+            *target = value;
+            true
+        }
+        _ => false,
+    }
+}
+
+impl Display for SilenceData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_off() {
+            return write!(f, "off");
+        }
+        write!(
+            f,
+            "{}, below {}dB for {}s",
+            self.scope.label(),
+            self.threshold,
+            self.duration
+        )?;
+        if self.video {
+            match self.detected_ranges() {
+                Some(ranges) => write!(f, ", {} found", ranges.len())?,
+                None => write!(f, ", not analyzed")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How the output is split into several files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum SplitMode {
+    #[default]
+    Off,
+    /// One file per input chapter
+    Chapters,
+    /// Segments of the same length
+    Duration,
+    /// Segments up to the size, the length is estimated from the bitrate
+    Size,
+    /// Cut at detected scene changes
+    Scene,
+}
+
+impl SplitMode {
+    pub(crate) const ALL: [SplitMode; 5] = [
+        SplitMode::Off,
+        SplitMode::Chapters,
+        SplitMode::Duration,
+        SplitMode::Size,
+        SplitMode::Scene,
+    ];
+
+    pub(crate) fn id(self) -> &'static str {
+        match self {
+            SplitMode::Off => "off",
+            SplitMode::Chapters => "chapters",
+            SplitMode::Duration => "duration",
+            SplitMode::Size => "size",
+            SplitMode::Scene => "scene",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            SplitMode::Off => "off",
+            SplitMode::Chapters => "by chapters",
+            SplitMode::Duration => "by duration",
+            SplitMode::Size => "by size",
+            SplitMode::Scene => "by scene changes",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.id() == id)
+    }
+}
+
+/// Scene changes found by the `scene` score of the `select` filter for the given threshold
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SceneDetection {
+    pub(crate) threshold: f64,
+    /// Scene start times, seconds
+    pub(crate) times: Vec<f64>,
+}
+
+/// Output split parameters
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SplitData {
+    pub(crate) mode: SplitMode,
+    /// Segment length, seconds
+    pub(crate) duration: u32,
+    /// Maximal segment size, MB
+    pub(crate) size: u32,
+    /// Scene change score threshold, 0..1
+    pub(crate) scene: f64,
+    /// Number of input chapters, splitting by chapters needs some
+    pub(crate) chapters: usize,
+    /// Input has video, scene changes can be detected
+    pub(crate) video: bool,
+    pub(crate) detection: Option<SceneDetection>,
+}
+
+impl Default for SplitData {
+    fn default() -> Self {
+        Self {
+            mode: SplitMode::default(),
+            duration: 60,
+            size: 100,
+            scene: 0.4,
+            chapters: 0,
+            video: false,
+            detection: None,
+        }
+    }
+}
+
+impl SplitData {
+    pub(crate) const DURATIONS: [u32; 8] = [10, 30, 60, 120, 300, 600, 1800, 3600];
+    pub(crate) const SIZES: [u32; 9] = [10, 25, 50, 100, 250, 500, 1000, 2000, 4000];
+    pub(crate) const SCENES: [f64; 5] = [0.2, 0.3, 0.4, 0.5, 0.6];
+
+    pub(crate) fn is_off(&self) -> bool {
+        self.mode == SplitMode::Off
+    }
+
+    /// Modes available for the input
+    pub(crate) fn modes(&self) -> Vec<SplitMode> {
+        SplitMode::ALL
+            .into_iter()
+            .filter(|mode| match mode {
+                SplitMode::Chapters => self.chapters > 0,
+                SplitMode::Scene => self.video,
+                _ => true,
+            })
+            .collect()
+    }
+
+    pub(crate) fn step_mode(&mut self, forward: bool) {
+        let modes = self.modes();
+        let index = modes.iter().position(|m| *m == self.mode).unwrap_or(0);
+        let next = if forward {
+            index + 1
+        } else {
+            index + modes.len() - 1
+        };
+        self.mode = modes[next % modes.len()];
+    }
+
+    /// Change the value of the current mode
+    pub(crate) fn step_value(&mut self, forward: bool) {
+        match self.mode {
+            SplitMode::Duration => self.duration = step(&Self::DURATIONS, self.duration, forward),
+            SplitMode::Size => self.size = step(&Self::SIZES, self.size, forward),
+            SplitMode::Scene => self.scene = step(&Self::SCENES, self.scene, forward),
+            SplitMode::Off | SplitMode::Chapters => {}
+        }
+    }
+
+    /// Value of the current mode, e.g. `5m` or `100MB`
+    pub(crate) fn value_label(&self) -> Option<String> {
+        match self.mode {
+            SplitMode::Duration => Some(Self::format_duration(self.duration)),
+            SplitMode::Size => Some(format!("{}MB", self.size)),
+            SplitMode::Scene => Some(self.scene.to_string()),
+            SplitMode::Off | SplitMode::Chapters => None,
+        }
+    }
+
+    /// Detected scene changes if they match the current threshold
+    pub(crate) fn detected_scenes(&self) -> Option<&[f64]> {
+        self.detection
+            .as_ref()
+            .filter(|d| d.threshold == self.scene)
+            .map(|d| d.times.as_slice())
+    }
+
+    /// Scene changes must be detected before the command can be built
+    pub(crate) fn needs_detection(&self) -> bool {
+        self.video && self.mode == SplitMode::Scene && self.detected_scenes().is_none()
+    }
+
+    fn format_duration(seconds: u32) -> String {
+        if seconds.is_multiple_of(3600) {
+            format!("{}h", seconds / 3600)
+        } else if seconds.is_multiple_of(60) {
+            format!("{}m", seconds / 60)
+        } else {
+            format!("{seconds}s")
+        }
+    }
+}
+
+impl Display for SplitData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.mode {
+            SplitMode::Off => write!(f, "off"),
+            SplitMode::Chapters => write!(f, "by chapters ({})", self.chapters),
+            SplitMode::Duration => write!(f, "every {}", Self::format_duration(self.duration)),
+            SplitMode::Size => write!(f, "up to {}MB", self.size),
+            SplitMode::Scene => {
+                write!(f, "scene changes above {}", self.scene)?;
+                match self.detected_scenes() {
+                    Some(times) => write!(f, ", {} found", times.len()),
+                    None => write!(f, ", not analyzed"),
+                }
+            }
+        }
+    }
+}
+
+/// Which frames are written as still images
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum FramesMode {
+    /// One frame at the trim start
+    #[default]
+    Single,
+    /// One frame per interval
+    Every,
+    /// Every frame of the trim range
+    All,
+    /// First frames of the scenes
+    Scene,
+    /// Grid of frames sampled evenly over the duration in one image
+    Sheet,
+}
+
+impl FramesMode {
+    pub(crate) const ALL: [FramesMode; 5] = [
+        FramesMode::Single,
+        FramesMode::Every,
+        FramesMode::All,
+        FramesMode::Scene,
+        FramesMode::Sheet,
+    ];
+
+    pub(crate) fn id(self) -> &'static str {
+        match self {
+            FramesMode::Single => "single",
+            FramesMode::Every => "every",
+            FramesMode::All => "all",
+            FramesMode::Scene => "scene",
+            FramesMode::Sheet => "sheet",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            FramesMode::Single => "single frame",
+            FramesMode::Every => "every interval",
+            FramesMode::All => "all frames",
+            FramesMode::Scene => "scene changes",
+            FramesMode::Sheet => "contact sheet",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.id() == id)
+    }
+}
+
+/// Image output parameters, the range comes from the trim
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FramesData {
+    pub(crate) mode: FramesMode,
+    /// Time between the frames, seconds
+    pub(crate) interval: u32,
+    /// Scene change score threshold, 0..1
+    pub(crate) scene: f64,
+    /// Contact sheet columns and rows
+    pub(crate) grid: (u32, u32),
+    /// Contact sheet header with the input summary
+    pub(crate) header: bool,
+    /// Time of each contact sheet tile
+    pub(crate) timestamps: bool,
+    /// Input filename, duration, resolution and size for the header
+    pub(crate) summary: String,
+}
+
+impl Default for FramesData {
+    fn default() -> Self {
+        Self {
+            mode: FramesMode::default(),
+            interval: 10,
+            scene: 0.4,
+            grid: (4, 4),
+            header: true,
+            timestamps: true,
+            summary: String::new(),
+        }
+    }
+}
+
+impl FramesData {
+    pub(crate) const INTERVALS: [u32; 9] = [1, 2, 5, 10, 15, 30, 60, 120, 300];
+    pub(crate) const SCENES: [f64; 5] = SplitData::SCENES;
+    pub(crate) const GRIDS: [(u32, u32); 8] = [
+        (2, 2),
+        (3, 2),
+        (3, 3),
+        (4, 3),
+        (4, 4),
+        (5, 4),
+        (5, 5),
+        (6, 6),
+    ];
+
+    /// Columns and rows like `4x3`, only the listed grids are allowed
+    pub(crate) fn parse_grid(value: &str) -> Option<(u32, u32)> {
+        let (columns, rows) = value.split_once('x')?;
+        let grid = (columns.parse().ok()?, rows.parse().ok()?);
+        Self::GRIDS.contains(&grid).then_some(grid)
+    }
+
+    pub(crate) fn grid_label(&self) -> String {
+        format!("{}x{}", self.grid.0, self.grid.1)
+    }
+
+    pub(crate) fn step_mode(&mut self, forward: bool) {
+        let modes = FramesMode::ALL;
+        let index = modes.iter().position(|m| *m == self.mode).unwrap_or(0);
+        let next = if forward {
+            index + 1
+        } else {
+            index + modes.len() - 1
+        };
+        self.mode = modes[next % modes.len()];
+    }
+
+    /// Change the value of the current mode
+    pub(crate) fn step_value(&mut self, forward: bool) {
+        match self.mode {
+            FramesMode::Every => self.interval = step(&Self::INTERVALS, self.interval, forward),
+            FramesMode::Scene => self.scene = step(&Self::SCENES, self.scene, forward),
+            FramesMode::Sheet => self.grid = step(&Self::GRIDS, self.grid, forward),
+            FramesMode::Single | FramesMode::All => {}
+        }
+    }
+
+    /// Value of the current mode, e.g. `10s`, `0.4` or `4x4`
+    pub(crate) fn value_label(&self) -> Option<String> {
+        match self.mode {
+            FramesMode::Every => Some(format!("{}s", self.interval)),
+            FramesMode::Scene => Some(self.scene.to_string()),
+            FramesMode::Sheet => Some(self.grid_label()),
+            FramesMode::Single | FramesMode::All => None,
+        }
+    }
+}
+
+impl Display for FramesData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.mode {
+            FramesMode::Single => write!(f, "single frame"),
+            FramesMode::Every => write!(f, "every {}s", self.interval),
+            FramesMode::All => write!(f, "all frames"),
+            FramesMode::Scene => write!(f, "scene changes above {}", self.scene),
+            FramesMode::Sheet => {
+                write!(f, "{} contact sheet", self.grid_label())?;
+                if self.header {
+                    write!(f, ", header")?;
+                }
+                if self.timestamps {
+                    write!(f, ", timestamps")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Files written by the render, named after the output filename
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) enum OutputFiles {
+    #[default]
+    Single,
+    /// One file per part, the suffixes are appended to the filename
+    Parts(Vec<String>),
+    /// Numbered files of the segment muxer, the count is estimated
+    Segments(usize),
+    /// Adaptive streaming package in a folder named after the output
+    Package(PackageFormat),
+    /// Numbered images, the count is estimated when it is known
+    Frames(Option<usize>),
+}
+
+impl OutputFiles {
+    /// Appended to the segment filename without a number placeholder
+    const NUMBER: &'static str = "_{n:03}";
+    /// Appended to the image filename without a number placeholder
+    const FRAME_NUMBER: &'static str = "_{n:05}";
+
+    /// Segment number placeholder, `{n}` or zero padded `{n:03}`
+    fn number_regex() -> Regex {
+        Regex::new(r"\{n(?::0([1-9]))?\}").unwrap()
+    }
+
+    /// Filename without the number placeholder, e.g. to validate the characters
+    pub(crate) fn strip_number(filename: &str) -> String {
+        Self::number_regex().replace_all(filename, "").into_owned()
+    }
+
+    pub(crate) fn count(&self) -> usize {
+        match self {
+            OutputFiles::Single | OutputFiles::Package(_) => 1,
+            OutputFiles::Parts(suffixes) => suffixes.len(),
+            OutputFiles::Segments(count) => *count,
+            OutputFiles::Frames(count) => count.unwrap_or(1),
+        }
+    }
+
+    /// Segment muxer filename, the number placeholder becomes `%03d`
+    pub(crate) fn segment_pattern(filename: &str) -> String {
+        Self::pattern(filename, Self::NUMBER)
+    }
+
+    /// Image muxer filename, the number placeholder becomes `%05d`
+    pub(crate) fn frame_pattern(filename: &str) -> String {
+        Self::pattern(filename, Self::FRAME_NUMBER)
+    }
+
+    fn pattern(filename: &str, default: &str) -> String {
+        let filename = filename.replace('%', "%%");
+        Self::with_number(&filename, default, |width| match width {
+            Some(width) => format!("%0{width}d"),
+            None => "%d".to_owned(),
+        })
+    }
+
+    /// Names of all files without the extension
+    pub(crate) fn names(&self, filename: &str) -> Vec<String> {
+        match self {
+            OutputFiles::Single => vec![filename.to_owned()],
+            OutputFiles::Package(format) => vec![format!("{filename}/{}", format.index())],
+            OutputFiles::Parts(suffixes) => suffixes
+                .iter()
+                .map(|suffix| format!("{filename}{suffix}"))
+                .collect(),
+            OutputFiles::Segments(count) => (1..=*count)
+                .map(|n| Self::numbered(filename, Self::NUMBER, n))
+                .collect(),
+            // There can be thousands of images, the first one stands for all
+            OutputFiles::Frames(_) => vec![Self::numbered(filename, Self::FRAME_NUMBER, 1)],
+        }
+    }
+
+    fn numbered(filename: &str, default: &str, n: usize) -> String {
+        Self::with_number(filename, default, |width| {
+            format!("{n:0width$}", width = width.unwrap_or(0))
+        })
+    }
+
+    /// Replace the first number placeholder, the default one is appended when there is none
+    fn with_number(
+        filename: &str,
+        default: &str,
+        number: impl Fn(Option<usize>) -> String,
+    ) -> String {
+        let regex = Self::number_regex();
+        let filename = if regex.is_match(filename) {
+            filename.to_owned()
+        } else {
+            format!("{filename}{default}")
+        };
+        regex
+            .replacen(&filename, 1, |caps: &regex::Captures| {
+                number(caps.get(1).and_then(|w| w.as_str().parse().ok()))
+            })
+            .into_owned()
+    }
+}
+
+/// Adaptive streaming package written into a folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PackageFormat {
+    /// `.m3u8` playlists with `.ts` segments
+    Hls,
+    /// `.mpd` manifest with `.m4s` segments
+    Dash,
+}
+
+impl PackageFormat {
+    /// Segment length, seconds
+    pub(crate) const SEGMENT_TIME: u32 = 6;
+
+    /// Package format of the output extension
+    pub(crate) fn from_ext(ext: &str) -> Option<Self> {
+        match ext {
+            "m3u8" => Some(PackageFormat::Hls),
+            "mpd" => Some(PackageFormat::Dash),
+            _ => None,
+        }
+    }
+
+    /// Name of the file players open, without the extension
+    pub(crate) fn index(self) -> &'static str {
+        match self {
+            PackageFormat::Hls => "master",
+            PackageFormat::Dash => "manifest",
+        }
+    }
+
+    /// FFmpeg output name, HLS writes a playlist per variant `%v`
+    pub(crate) fn output(self) -> &'static str {
+        match self {
+            PackageFormat::Hls => "stream_%v",
+            PackageFormat::Dash => "manifest",
+        }
+    }
+
+    /// Option and name of the segment files when they are relative to the working folder
+    pub(crate) fn segment_option(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            PackageFormat::Hls => Some(("-hls_segment_filename", "stream_%v_%03d.ts")),
+            // DASH segment names are relative to the manifest
+            PackageFormat::Dash => None,
+        }
+    }
+}
+
+/// Video variant of an adaptive streaming package, e.g. `720p@2800k`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Rendition {
+    pub(crate) height: u32,
+    pub(crate) bitrate: String,
+}
+
+impl Rendition {
+    /// Common H.264 bitrates by height
+    pub(crate) const LADDER: [(u32, &'static str); 7] = [
+        (240, "400k"),
+        (360, "800k"),
+        (480, "1400k"),
+        (720, "2800k"),
+        (1080, "5M"),
+        (1440, "8M"),
+        (2160, "16M"),
+    ];
+
+    /// Bitrate of the nearest ladder height that is not lower
+    pub(crate) fn default_bitrate(height: u32) -> &'static str {
+        Self::LADDER
+            .iter()
+            .find(|(h, _)| *h >= height)
+            .unwrap_or(&Self::LADDER[Self::LADDER.len() - 1])
+            .1
+    }
+
+    /// Parse `360p@800k,720p` list, `bitrate` validates and normalizes the bitrate
+    pub(crate) fn parse_list(
+        value: &str,
+        bitrate: impl Fn(&str) -> Option<String>,
+    ) -> Option<Vec<Rendition>> {
+        value
+            .split(',')
+            .map(|item| {
+                let (height, rate) = item
+                    .trim()
+                    .split_once('@')
+                    .map_or((item.trim(), None), |(h, b)| (h, Some(b)));
+                let height = height
+                    .strip_suffix('p')
+                    .unwrap_or(height)
+                    .parse::<ScaleValue>()
+                    .ok()
+                    .and_then(|scale| match scale {
+                        ScaleValue {
+                            size: ScaleSize::Height(h),
+                            no_upscale: false,
+                        } => Some(h),
+                        _ => None,
+                    })?;
+                let bitrate = bitrate(rate.unwrap_or(Self::default_bitrate(height)))?;
+                Some(Rendition { height, bitrate })
+            })
+            .collect()
+    }
+}
+
+impl Display for Rendition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}p@{}", self.height, self.bitrate)
+    }
+}
+
+/// What happens to the attached picture of audio outputs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum CoverArt {
+    #[default]
+    Keep,
+    Remove,
+    Replace,
+}
+
+impl CoverArt {
+    pub(crate) const ALL: [CoverArt; 3] = [CoverArt::Keep, CoverArt::Remove, CoverArt::Replace];
+
+    /// Short name used in presets
+    pub(crate) fn id(self) -> &'static str {
+        match self {
+            CoverArt::Keep => "keep",
+            CoverArt::Remove => "remove",
+            CoverArt::Replace => "replace",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.id() == id)
+    }
+
+    pub(crate) fn prev(self) -> Self {
+        let index = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub(crate) fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Editable metadata tags, empty value means no tag
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct MetadataTags {
+    pub(crate) title: String,
+    pub(crate) artist: String,
+    pub(crate) album: String,
+    pub(crate) comment: String,
+    pub(crate) date: String,
+    /// ISO 639-2 code of the audio streams
+    pub(crate) language: String,
+}
+
+impl MetadataTags {
+    pub(crate) const KEYS: [&str; 6] = ["title", "artist", "album", "comment", "date", "language"];
+
+    pub(crate) fn get(&self, key: &str) -> &str {
+        match key {
+            "title" => &self.title,
+            "artist" => &self.artist,
+            "album" => &self.album,
+            "comment" => &self.comment,
+            "date" => &self.date,
+            "language" => &self.language,
+            _ => "",
+        }
+    }
+
+    pub(crate) fn get_mut(&mut self, key: &str) -> Option<&mut String> {
+        match key {
+            "title" => Some(&mut self.title),
+            "artist" => Some(&mut self.artist),
+            "album" => Some(&mut self.album),
+            "comment" => Some(&mut self.comment),
+            "date" => Some(&mut self.date),
+            "language" => Some(&mut self.language),
+            _ => None,
+        }
+    }
+
+    /// Tags with values different from the other ones
+    pub(crate) fn changed<'a>(&'a self, other: &'a MetadataTags) -> Vec<(&'static str, &'a str)> {
+        Self::KEYS
+            .into_iter()
+            .filter(|key| self.get(key) != other.get(key))
+            .map(|key| (key, self.get(key)))
+            .collect()
+    }
+
+    pub(crate) fn validate(language: &str) -> Result<(), &'static str> {
+        if language.is_empty()
+            || (language.len() == 3 && language.chars().all(|c| c.is_ascii_lowercase()))
+        {
+            Ok(())
+        } else {
+            Err("Language must be an ISO 639-2 code, e.g. eng")
+        }
+    }
+}
+
+/// Metadata editing and stripping parameters
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct MetadataData {
+    /// Drop all the source metadata and chapters
+    pub(crate) strip: bool,
+    pub(crate) tags: MetadataTags,
+    /// Source values, only the changed tags are written
+    pub(crate) source: MetadataTags,
+    pub(crate) cover: CoverArt,
+    pub(crate) cover_path: Option<String>,
+    /// Source has an attached picture
+    pub(crate) source_cover: bool,
+}
+
+impl MetadataData {
+    pub(crate) fn with_source(source: MetadataTags, source_cover: bool) -> Self {
+        Self {
+            tags: source.clone(),
+            source,
+            source_cover,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn is_unchanged(&self) -> bool {
+        !self.strip && self.cover == CoverArt::Keep && self.tags == self.source
+    }
+
+    /// Tags to write, the stripped ones are not cleared one by one
+    pub(crate) fn tags_to_write(&self) -> Vec<(&'static str, &str)> {
+        self.tags
+            .changed(&self.source)
+            .into_iter()
+            .filter(|(_, value)| !self.strip || !value.is_empty())
+            .collect()
+    }
+}
+
+impl Display for MetadataData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if self.strip {
+            parts.push("strip".to_owned());
+        }
+        let changed = self.tags_to_write();
+        match changed.as_slice() {
+            [] => {}
+            [(key, "")] => parts.push(format!("no {key}")),
+            [(key, value)] => {
+                let value: String = value.chars().take(16).collect();
+                parts.push(format!("{key} \"{value}\""));
+            }
+            _ => parts.push(format!("{} tags", changed.len())),
+        }
+        match self.cover {
+            CoverArt::Keep => {}
+            CoverArt::Remove => parts.push("no cover".to_owned()),
+            CoverArt::Replace => parts.push("new cover".to_owned()),
+        }
+        if parts.is_empty() {
+            write!(f, "keep")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+/// Check FFmpeg color syntax: a name or #RRGGBB[AA], optionally with @opacity
+pub(crate) fn is_valid_color(color: &str) -> bool {
+    const REGEXP_COLOR: &str =
+        r"^([a-zA-Z]+|(#|0x)[0-9a-fA-F]{6}([0-9a-fA-F]{2})?)(@[01](\.[0-9]+)?)?$";
+    Regex::new(REGEXP_COLOR)
+        .expect("Valid regex")
+        .is_match(color)
+}
+
 /// Bitrate type
 #[derive(Debug, PartialEq)]
 pub(crate) enum BitrateType {
@@ -217,6 +1770,25 @@ pub(crate) enum BitrateType {
 #[derive(Debug)]
 pub(crate) struct Bitrate(pub u32, pub BitrateType);
 
+impl Bitrate {
+    pub(crate) fn from_bps(bps: u64) -> Self {
+        let kbps = u32::try_from(bps / 1000).unwrap_or(u32::MAX);
+        if kbps >= 10_000 {
+            Bitrate(kbps / 1000, BitrateType::M)
+        } else {
+            Bitrate(kbps, BitrateType::K)
+        }
+    }
+
+    pub(crate) fn bps(&self) -> u64 {
+        let multiplier = match self.1 {
+            BitrateType::K => 1000,
+            BitrateType::M => 1_000_000,
+        };
+        u64::from(self.0) * multiplier
+    }
+}
+
 impl Display for BitrateType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", if *self == BitrateType::K { "k" } else { "M" })
@@ -254,6 +1826,132 @@ impl FromStr for Bitrate {
     }
 }
 
+/// Video scale target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScaleSize {
+    /// Height, width keeps the aspect ratio
+    Height(u32),
+    /// Width, height keeps the aspect ratio
+    Width(u32),
+    /// Fit inside the box keeping the aspect ratio
+    Fit(u32, u32),
+    /// Percents of the source size
+    Percent(u32),
+}
+
+/// Video scale value: `720`, `1280:`, `1280x720`, `50%`.
+/// `>` suffix disables upscaling of sizes, e.g. `1280x720>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ScaleValue {
+    pub(crate) size: ScaleSize,
+    pub(crate) no_upscale: bool,
+}
+
+impl ScaleValue {
+    /// Size arguments of the scale filter
+    pub(crate) fn filter_args(&self) -> String {
+        let limit = |v: u32, src: &str| {
+            if self.no_upscale {
+                format!("'min({v},{src})'")
+            } else {
+                v.to_string()
+            }
+        };
+        match self.size {
+            ScaleSize::Height(h) => format!("-2:{}", limit(h, "ih")),
+            ScaleSize::Width(w) => format!("{}:-2", limit(w, "iw")),
+            ScaleSize::Fit(w, h) => format!(
+                "{}:{}:force_original_aspect_ratio=decrease:force_divisible_by=2",
+                limit(w, "iw"),
+                limit(h, "ih")
+            ),
+            ScaleSize::Percent(p) => format!("-2:trunc(ih*{p}/200)*2"),
+        }
+    }
+
+    /// Expected size of the scaled video
+    pub(crate) fn result_size(&self, (sw, sh): (u32, u32)) -> (u32, u32) {
+        // Same rounding as the scale filter uses for the -2 size
+        let rescale = |a: u32, b: u32, c: u32| {
+            let c = u64::from(c.max(1));
+            let v = (u64::from(a) * u64::from(b) + c / 2) / c;
+            u32::try_from(v).unwrap_or(u32::MAX)
+        };
+        let limit = |v: u32, src: u32| if self.no_upscale { v.min(src) } else { v };
+        match self.size {
+            ScaleSize::Height(h) => {
+                let h = limit(h, sh);
+                (rescale(h, sw, sh * 2) * 2, h)
+            }
+            ScaleSize::Width(w) => {
+                let w = limit(w, sw);
+                (w, rescale(w, sh, sw * 2) * 2)
+            }
+            ScaleSize::Fit(w, h) => {
+                let (w, h) = (limit(w, sw), limit(h, sh));
+                let w = rescale(h, sw, sh).min(w);
+                let h = rescale(w, sh, sw).min(h);
+                ((w / 2 * 2).max(2), (h / 2 * 2).max(2))
+            }
+            ScaleSize::Percent(p) => {
+                let h = rescale(sh, p, 200) * 2;
+                (rescale(h, sw, sh * 2) * 2, h)
+            }
+        }
+    }
+}
+
+impl Display for ScaleValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.size {
+            ScaleSize::Height(h) => write!(f, "{h}")?,
+            ScaleSize::Width(w) => write!(f, "{w}:")?,
+            ScaleSize::Fit(w, h) => write!(f, "{w}x{h}")?,
+            ScaleSize::Percent(p) => write!(f, "{p}%")?,
+        }
+        if self.no_upscale {
+            write!(f, ">")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ScaleValue {
+    type Err = &'static str;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const SIZE_ERROR: &str = "Invalid size. Expected an even number in range 64..8192";
+        let parse_size = |v: &str| {
+            v.parse::<u32>()
+                .ok()
+                .filter(|n| (64..=8192).contains(n) && n % 2 == 0)
+                .ok_or(SIZE_ERROR)
+        };
+        let s = s.trim();
+        let (s, no_upscale) = match s.strip_suffix('>') {
+            Some(s) => (s, true),
+            None => (s, false),
+        };
+        let size = if let Some(percent) = s.strip_suffix('%') {
+            if no_upscale {
+                return Err("Upscale limit is not applicable to percents");
+            }
+            percent
+                .parse::<u32>()
+                .ok()
+                .filter(|p| (1..=400).contains(p))
+                .map(ScaleSize::Percent)
+                .ok_or("Invalid percent. Expected a number in range 1..400")?
+        } else if let Some(width) = s.strip_suffix(':') {
+            ScaleSize::Width(parse_size(width)?)
+        } else if let Some((width, height)) = s.split_once(['x', 'X']) {
+            ScaleSize::Fit(parse_size(width)?, parse_size(height)?)
+        } else {
+            ScaleSize::Height(parse_size(s)?)
+        };
+        Ok(ScaleValue { size, no_upscale })
+    }
+}
+
 pub(crate) type ValidationCallback = Arc<dyn Fn(&str) -> Result<String, &str> + Send + Sync>;
 pub(crate) type ValueFormatter = Arc<dyn Fn(&str) -> String + Send + Sync>;
 
@@ -264,6 +1962,10 @@ pub(crate) enum InputType {
     PositiveInteger,
     PositiveDecimal,
     Bitrate,
+    Scale,
+    Interval,
+    IntegerList,
+    RenditionList,
 }
 
 /// Input constraints, max length in characters and input type
@@ -289,6 +1991,16 @@ pub(crate) enum AppEvent {
     SaveCompleted(bool),
     Redraw,
     OpenTrimModal(TrimData),
+    OpenOverlayModal(OverlayData),
+    OpenTextModal(TextData),
+    OpenAspectModal(AspectData),
+    OpenEqModal(EqData),
+    OpenSilenceModal(SilenceData),
+    OpenMetadataModal(MetadataData),
+    OpenSplitModal(SplitData),
+    OpenFramesModal(FramesData),
+    SilenceDetected(Result<SilenceDetection, String>),
+    ScenesDetected(Result<SceneDetection, String>),
     OpenCustomSelectModal(CustomSelectData),
     RenderStarted(ChildStdin),
 }
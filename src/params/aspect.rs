@@ -0,0 +1,110 @@
+use tracing::{debug, warn};
+
+use crate::{
+    info::Info,
+    model::{AspectData, AspectMode},
+    params::{Parameter, ParameterData, PresetParameter, VideoScale},
+    visitors::{CommandBuilder, ComplexFilter, VisitorContext},
+};
+
+pub(crate) struct Aspect;
+
+impl Aspect {
+    pub(crate) const ID: &'static str = "aspect";
+    pub(crate) const NAME: &'static str = "Aspect";
+    const BLUR: &'static str = "boxblur=20:5";
+
+    pub fn new_parameter(info: &Info) -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Aspect(AspectData {
+                source: info.video_size(),
+                ..Default::default()
+            }),
+        )
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if let ParameterData::Aspect(aspect) = data
+            && let Some((w, h)) = aspect.target_size()
+        {
+            debug!(?aspect, "build_command");
+            let fit = |mode: &str| {
+                let args =
+                    format!("{w}:{h}:force_original_aspect_ratio={mode}:force_divisible_by=2");
+                VideoScale::software_scale(cb, &args)
+            };
+            let (fit_inside, fit_cover) = (fit("decrease"), fit("increase"));
+            match aspect.mode {
+                AspectMode::Pad => {
+                    cb.video_filters.push(fit_inside);
+                    cb.video_filters.push(format!(
+                        "pad={w}:{h}:(ow-iw)/2:(oh-ih)/2:color={}",
+                        aspect.color
+                    ));
+                }
+                AspectMode::Blur => {
+                    // Blurred and cropped copy of the video as a background
+                    let (a, b) = (cb.label("asp_a"), cb.label("asp_b"));
+                    let (bg, fg) = (cb.label("asp_bg"), cb.label("asp_fg"));
+                    let graph = [
+                        format!("[{{in}}]split{a}{b}"),
+                        format!("{a}{},crop={w}:{h},{}{bg}", fit_cover, Self::BLUR),
+                        format!("{b}{fit_inside}{fg}"),
+                        format!("{bg}{fg}overlay=(W-w)/2:(H-h)/2,setsar=1[{{out}}]"),
+                    ];
+                    cb.complex_video_filters.push(ComplexFilter {
+                        position: cb.video_filters.len(),
+                        graph: graph.join(";"),
+                    });
+                    return;
+                }
+                AspectMode::Crop => cb.video_filters.push(format!("crop={w}:{h}")),
+            }
+            cb.video_filters.push("setsar=1".to_owned());
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for Aspect {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        if let ParameterData::Aspect(aspect) = data {
+            // ratio,mode,color
+            let parts: Vec<&str> = preset_value.split(',').collect();
+            let [ratio, mode, color] = parts[..] else {
+                warn!("Aspect preset is not valid and will be skipped");
+                return;
+            };
+            let (Some(ratio), Some(mode)) =
+                (AspectData::parse_ratio(ratio), AspectMode::from_id(mode))
+            else {
+                warn!("Unknown aspect ratio or mode {}", preset_value);
+                return;
+            };
+            match AspectData::validate(color) {
+                Ok(()) => {
+                    aspect.ratio = Some(ratio);
+                    aspect.mode = mode;
+                    color.clone_into(&mut aspect.color);
+                }
+                Err(msg) => warn!("Aspect preset is not valid and will be skipped: {}", msg),
+            }
+        }
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        if let ParameterData::Aspect(aspect) = data
+            && aspect.ratio.is_some()
+        {
+            Some(format!(
+                "{},{},{}",
+                AspectData::ratio_label(aspect.ratio),
+                aspect.mode.id(),
+                aspect.color
+            ))
+        } else {
+            None
+        }
+    }
+}
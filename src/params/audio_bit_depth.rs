@@ -0,0 +1,78 @@
+use tracing::debug;
+
+use crate::{
+    params::{
+        Parameter, ParameterData, PresetParameter, SelectOption, macros::select_non_default_option,
+    },
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+/// Sample format of the lossless outputs, the output format and codec should be applied before
+pub(crate) struct AudioBitDepth;
+
+impl AudioBitDepth {
+    pub(crate) const ID: &'static str = "bitdepth";
+    pub(crate) const NAME: &'static str = "Audio Bit Depth";
+    const DEFAULT: &'static str = "auto";
+
+    pub fn new_parameter() -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Select {
+                options: SelectOption::from_pairs(&[
+                    ("auto", "auto"),
+                    ("16 bit", "16"),
+                    ("24 bit", "24"),
+                    ("32 bit float", "32f"),
+                ]),
+                selected_index: 0,
+            },
+        )
+    }
+
+    /// The output formats where the bit depth can be chosen
+    pub(crate) fn is_supported(ext: &str) -> bool {
+        matches!(ext, "wav" | "flac")
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if !cb.discard_audio
+            && let Some(option) = select_non_default_option!(data)
+        {
+            debug!(value = option.value, ext = cb.ext, "build_command");
+            let pcm = match option.value.as_str() {
+                "16" => "pcm_s16le",
+                "24" => "pcm_s24le",
+                _ => "pcm_f32le",
+            };
+            let args: &[&str] = match (cb.ext.as_str(), option.value.as_str()) {
+                // PCM encoder is chosen by the finalizer
+                ("wav", _) => {
+                    cb.audio_codec = Some(pcm.to_owned());
+                    &[]
+                }
+                ("flac", "16") => &["-sample_fmt", "s16"],
+                ("flac", "24") => &["-sample_fmt", "s32", "-bits_per_raw_sample", "24"],
+                ("flac", "32f") => {
+                    cb.warnings.push(
+                        "FLAC does not support floating point samples, 24 bit is used".to_owned(),
+                    );
+                    &["-sample_fmt", "s32", "-bits_per_raw_sample", "24"]
+                }
+                _ => &[],
+            };
+            cb.args.extend(args.iter().map(|&arg| arg.to_owned()));
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for AudioBitDepth {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        Self::set_parameter_value(data, preset_value);
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_option!(data).map(|option| option.value.clone())
+    }
+}
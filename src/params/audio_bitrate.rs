@@ -3,9 +3,10 @@ use std::sync::Arc;
 use tracing::debug;
 
 use crate::{
+    info::Info,
     model::{InputConstraints, InputType},
     params::{
-        Parameter, ParameterData, PresetParameter, SelectOption,
+        AudioCodec, Parameter, ParameterData, PresetParameter, SelectOption,
         macros::select_non_default_custom_value,
     },
     visitors::{CommandBuilder, VisitorContext},
@@ -21,20 +22,30 @@ impl AudioBitrate {
         "4", "16", "32", "0", "64", "128", "192", "256", "320", "512",
     ];
 
-    pub fn new_parameter() -> Parameter {
+    pub fn new_parameter(info: &Info) -> Parameter {
+        let source = info.audio_bitrate();
+        // Hide bitrates above the source one, they only increase the file size
+        let options = SelectOption::from_slice_filtered(&Self::VARIANTS, |v| {
+            v == Self::DEFAULT
+                || source.is_none_or(|bps| v.parse::<u64>().is_ok_and(|v| v * 1000 < bps))
+        });
+        let selected_index = options
+            .iter()
+            .position(|o| o.value == Self::DEFAULT)
+            .unwrap_or_default();
         Parameter::new(
             Self::ID,
             Self::NAME,
             ParameterData::CustomSelect {
-                options: SelectOption::from_slice(&Self::VARIANTS),
-                selected_index: 3,
+                options,
+                selected_index,
                 value: Self::DEFAULT.to_owned(),
                 constraints: InputConstraints {
                     length: 4,
                     input_type: InputType::PositiveInteger,
                 },
                 validator: Arc::new(Self::validate),
-                formatter: Some(Arc::new(Self::format_value)),
+                formatter: Some(Arc::new(move |value| Self::format_value(value, source))),
             },
         )
         .with_order(4000)
@@ -50,19 +61,26 @@ impl AudioBitrate {
         }
     }
 
-    fn format_value(value: &str) -> String {
-        if value == Self::DEFAULT {
-            "auto".to_owned()
-        } else {
-            format!("{}k", &value)
+    fn format_value(value: &str, source: Option<u64>) -> String {
+        match source {
+            Some(bps) if value == Self::DEFAULT => format!("auto (source {}k)", bps / 1000),
+            None if value == Self::DEFAULT => "auto".to_owned(),
+            Some(bps) if value.parse::<u64>().is_ok_and(|v| v * 1000 > bps) => {
+                format!("{value}k (above source)")
+            }
+            _ => format!("{}k", &value),
         }
     }
 
     pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
         if !cb.discard_audio
+            && !cb
+                .audio_codec
+                .as_deref()
+                .is_some_and(AudioCodec::is_lossless)
             && let Some(value) = select_non_default_custom_value!(data)
         {
-            let bitrate = Self::format_value(value);
+            let bitrate = format!("{value}k");
             debug!(bitrate, "build_command");
             cb.pre_output_args.push("-b:a".into());
             cb.pre_output_args.push(bitrate);
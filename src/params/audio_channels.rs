@@ -0,0 +1,106 @@
+use tracing::debug;
+
+use crate::{
+    info::Info,
+    params::{
+        Parameter, ParameterData, PresetParameter, SelectOption, macros::select_non_default_option,
+    },
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+/// Downmix or pick audio channels, the options depend on the source channel count
+pub(crate) struct AudioChannels;
+
+impl AudioChannels {
+    pub(crate) const ID: &'static str = "channels";
+    pub(crate) const NAME: &'static str = "Audio Channels";
+    const DEFAULT: &'static str = "keep";
+    // 5.1 and 5.1(side) share the channel order: FL FR FC LFE BL|SL BR|SR.
+    // Center and surround channels are mixed in at -3dB, LFE is dropped
+    const DOWNMIX_51_STEREO: &'static str =
+        "pan=stereo|c0<c0+0.707*c2+0.707*c4|c1<c1+0.707*c2+0.707*c5";
+    const DOWNMIX_51_MONO: &'static str = "pan=mono|c0<0.5*c0+0.5*c1+0.707*c2+0.354*c4+0.354*c5";
+
+    pub fn new_parameter(info: &Info) -> Parameter {
+        let channels = info.audio_channels();
+        let keep = match channels {
+            Some(1) => "keep (mono)".to_owned(),
+            Some(2) => "keep (stereo)".to_owned(),
+            Some(6) => "keep (5.1)".to_owned(),
+            Some(n) => format!("keep ({n} ch)"),
+            None => "keep".to_owned(),
+        };
+        let mut options = vec![(keep.as_str(), Self::DEFAULT)];
+        options.extend(match channels {
+            Some(1) => vec![("stereo", "stereo")],
+            Some(2) => vec![
+                ("mono", "mono"),
+                ("swap L/R", "swap"),
+                ("left only", "left"),
+                ("right only", "right"),
+            ],
+            Some(_) => vec![
+                ("stereo downmix", "stereo"),
+                ("mono", "mono"),
+                ("left only", "left"),
+                ("right only", "right"),
+            ],
+            None => vec![
+                ("stereo", "stereo"),
+                ("mono", "mono"),
+                ("swap L/R", "swap"),
+                ("left only", "left"),
+                ("right only", "right"),
+            ],
+        });
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Select {
+                options: SelectOption::from_pairs(&options),
+                selected_index: 0,
+            },
+        )
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if !cb.discard_audio
+            && let Some(option) = select_non_default_option!(data)
+        {
+            debug!(
+                value = option.value,
+                channels = cb.ctx.channels,
+                "build_command"
+            );
+            let surround = cb.ctx.channels == Some(6);
+            let filter = match option.value.as_str() {
+                "stereo" if surround => Self::DOWNMIX_51_STEREO,
+                "mono" if surround => Self::DOWNMIX_51_MONO,
+                "mono" if cb.ctx.channels == Some(2) => "pan=mono|c0=0.5*c0+0.5*c1",
+                "swap" => "pan=stereo|c0=c1|c1=c0",
+                "left" => "pan=mono|c0=c0",
+                "right" => "pan=mono|c0=c1",
+                // Upmix or downmix of an unusual layout is left to FFmpeg
+                "stereo" => return Self::set_channel_count(cb, 2),
+                "mono" => return Self::set_channel_count(cb, 1),
+                _ => return,
+            };
+            cb.audio_filters.push(filter.to_owned());
+        }
+    }
+
+    fn set_channel_count(cb: &mut CommandBuilder, count: u32) {
+        cb.args.push("-ac".to_owned());
+        cb.args.push(count.to_string());
+    }
+}
+
+impl<'a> PresetParameter<'a> for AudioChannels {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        Self::set_parameter_value(data, preset_value);
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_option!(data).map(|option| option.value.clone())
+    }
+}
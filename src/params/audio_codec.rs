@@ -0,0 +1,107 @@
+use tracing::debug;
+
+use crate::{
+    params::{
+        Container, Parameter, ParameterData, PresetParameter, SelectOption,
+        macros::select_non_default_option,
+    },
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+/// Audio encoder, `-c:a` itself is added by the finalizer after the bit depth and filters are known.
+/// Should be applied after the output format and before the bitrate parameters
+pub(crate) struct AudioCodec;
+
+impl AudioCodec {
+    pub(crate) const ID: &'static str = "acodec";
+    pub(crate) const NAME: &'static str = "Audio Codec";
+    const DEFAULT: &'static str = "auto";
+
+    pub fn new_parameter() -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Select {
+                options: SelectOption::from_pairs(&[
+                    ("auto", "auto"),
+                    ("AAC", "aac"),
+                    ("Opus", "libopus"),
+                    ("MP3", "libmp3lame"),
+                    ("Vorbis", "libvorbis"),
+                    ("FLAC", "flac"),
+                    ("PCM", "pcm"),
+                    ("copy", "copy"),
+                ]),
+                selected_index: 0,
+            },
+        )
+        .with_order(130)
+    }
+
+    /// Codec FFmpeg picks for the output format
+    pub(crate) fn default_for(ext: &str) -> Option<&'static str> {
+        Container::find(ext).and_then(|c| c.default_audio)
+    }
+
+    /// Codecs the output format can hold, unknown formats accept everything
+    pub(crate) fn is_compatible(codec: &str, ext: &str) -> bool {
+        matches!(codec, "auto" | "copy")
+            || Container::find(ext).is_none_or(|c| c.accepts_audio(codec))
+    }
+
+    pub(crate) fn supports_vbr(codec: &str) -> bool {
+        matches!(codec, "libopus" | "libmp3lame" | "libvorbis")
+    }
+
+    pub(crate) fn is_lossless(codec: &str) -> bool {
+        matches!(codec, "flac" | "pcm" | "copy") || codec.starts_with("pcm_")
+    }
+
+    /// Bitrate for the explicitly chosen lossy codecs when none is set
+    pub(crate) fn default_bitrate(codec: &str) -> Option<&'static str> {
+        match codec {
+            "aac" | "libvorbis" => Some("160k"),
+            "libopus" => Some("128k"),
+            "libmp3lame" => Some("192k"),
+            _ => None,
+        }
+    }
+
+    /// Disable the options the output format does not support
+    pub(crate) fn toggle_codecs(param: &mut Parameter, ext: &str) {
+        if let ParameterData::Select {
+            options,
+            selected_index,
+        } = &mut param.data
+        {
+            for option in options.iter_mut() {
+                option.available = Self::is_compatible(&option.value, ext);
+            }
+            if options.get(*selected_index).is_some_and(|o| !o.available) {
+                *selected_index = 0;
+            }
+        }
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if !cb.discard_audio
+            && let Some(option) = select_non_default_option!(data)
+        {
+            debug!(value = option.value, "build_command");
+            cb.audio_codec = Some(match option.value.as_str() {
+                "pcm" => "pcm_s16le".to_owned(),
+                codec => codec.to_owned(),
+            });
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for AudioCodec {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        Self::set_parameter_value(data, preset_value);
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_option!(data).map(|option| option.value.clone())
+    }
+}
@@ -0,0 +1,54 @@
+use tracing::{debug, warn};
+
+use crate::{
+    model::{EqBands, EqData, EqPreset},
+    params::{Parameter, ParameterData, PresetParameter},
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+pub(crate) struct AudioEq;
+
+impl AudioEq {
+    pub(crate) const ID: &'static str = "eq";
+    pub(crate) const NAME: &'static str = "Audio EQ";
+
+    pub fn new_parameter() -> Parameter {
+        Parameter::new(Self::ID, Self::NAME, ParameterData::Eq(EqData::default()))
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if !cb.discard_audio
+            && let ParameterData::Eq(eq) = data
+            && !eq.is_off()
+        {
+            debug!(?eq, "build_command");
+            cb.audio_filters.extend(eq.bands.filters());
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for AudioEq {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        if let ParameterData::Eq(eq) = data {
+            // Preset id or custom bands "bass,mid,treble,highpass,lowpass"
+            if let Some(preset) = EqPreset::from_id(preset_value)
+                && preset != EqPreset::Custom
+            {
+                *eq = EqData::with_preset(preset);
+            } else if let Some(bands) = EqBands::parse(preset_value) {
+                *eq = EqData::with_bands(bands);
+            } else {
+                warn!("Audio EQ preset is not valid and will be skipped");
+            }
+        }
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        match data {
+            ParameterData::Eq(eq) if eq.is_off() => None,
+            ParameterData::Eq(eq) if eq.preset == EqPreset::Custom => Some(eq.bands.to_string()),
+            ParameterData::Eq(eq) => Some(eq.preset.id().to_owned()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use tracing::debug;
+
+use crate::{
+    model::{InputConstraints, InputType},
+    params::{
+        Parameter, ParameterData, PresetParameter, SelectOption,
+        macros::select_non_default_custom_value,
+    },
+    visitors::{CommandBuilder, ExtraInput, VisitorContext},
+};
+
+/// Shift the audio relative to the video in milliseconds, positive values delay the audio.
+/// Should be applied after the trim and the audio codec, before the silence removal
+pub(crate) struct AudioOffset;
+
+impl AudioOffset {
+    pub(crate) const ID: &'static str = "aoffset";
+    pub(crate) const NAME: &'static str = "Audio Offset";
+    const DEFAULT: &'static str = "0";
+    const VARIANTS: [&str; 11] = [
+        "-1000", "-500", "-200", "-100", "-50", "0", "50", "100", "200", "500", "1000",
+    ];
+
+    pub fn new_parameter() -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::CustomSelect {
+                options: SelectOption::from_slice(&Self::VARIANTS),
+                selected_index: 5,
+                value: Self::DEFAULT.to_owned(),
+                constraints: InputConstraints {
+                    length: 6,
+                    input_type: InputType::Integer,
+                },
+                validator: Arc::new(Self::validate),
+                formatter: Some(Arc::new(Self::format_value)),
+            },
+        )
+        .with_order(1650)
+    }
+
+    fn validate(value: &str) -> Result<String, &str> {
+        if let Ok(num) = value.parse::<i32>()
+            && (-60000..=60000).contains(&num)
+        {
+            Ok(num.to_string())
+        } else {
+            Err("Invalid value. Expected milliseconds in range -60000..60000")
+        }
+    }
+
+    fn format_value(value: &str) -> String {
+        if value == Self::DEFAULT {
+            "in sync".to_owned()
+        } else if value.starts_with('-') {
+            format!("{value}ms (audio earlier)")
+        } else {
+            format!("+{value}ms (audio later)")
+        }
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if cb.discard_audio {
+            return;
+        }
+        let Some(ms) = select_non_default_custom_value!(data).and_then(|v| v.parse::<i32>().ok())
+        else {
+            return;
+        };
+        debug!(ms, codec = cb.audio_codec, "build_command");
+        if cb.audio_codec.as_deref() == Some("copy") {
+            // Filters would re-encode the audio, so the audio is taken from the same file
+            // opened again with shifted timestamps. Non-precise trim seeks the first input
+            // and its arguments are the only pre-input ones at this point, the second input
+            // is seeked the same way. Precise trim seeks the output and affects both inputs
+            let mut args = cb.pre_input_args.clone();
+            args.push("-itsoffset".to_owned());
+            args.push(format!("{:.3}", f64::from(ms) / 1000.0));
+            cb.audio_input = cb.push_input(ExtraInput {
+                args,
+                source: true,
+                ..Default::default()
+            });
+        } else if ms > 0 {
+            // Before the speed and the silence filters, so the offset is in the source time
+            cb.audio_filters.insert(0, format!("adelay={ms}:all=1"));
+        } else {
+            cb.audio_filters.insert(
+                0,
+                format!(
+                    "atrim=start={:.3},asetpts=PTS-STARTPTS",
+                    f64::from(-ms) / 1000.0
+                ),
+            );
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for AudioOffset {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        if Self::validate(preset_value).is_ok() {
+            Self::set_parameter_value(data, preset_value);
+        }
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_custom_value!(data).cloned()
+    }
+}
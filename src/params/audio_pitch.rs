@@ -3,9 +3,10 @@ use std::sync::Arc;
 use tracing::debug;
 
 use crate::{
+    features::Features,
     model::{InputConstraints, InputType},
     params::{
-        Parameter, ParameterData, PresetParameter, SelectOption,
+        Parameter, ParameterData, PresetParameter, SelectOption, SpeedFactor,
         macros::select_non_default_custom_value,
     },
     visitors::{CommandBuilder, VisitorContext},
@@ -19,7 +20,8 @@ impl AudioPitch {
     const DEFAULT: &'static str = "1";
     const VARIANTS: [&str; 8] = ["0.6", "0.8", "0.9", "1", "1.15", "1.25", "1.5", "2"];
 
-    pub fn new_parameter() -> Parameter {
+    pub fn new_parameter(features: Features) -> Parameter {
+        let rubberband = features.rubberband;
         Parameter::new(
             Self::ID,
             Self::NAME,
@@ -32,7 +34,7 @@ impl AudioPitch {
                     input_type: InputType::PositiveDecimal,
                 },
                 validator: Arc::new(Self::validate),
-                formatter: None,
+                formatter: Some(Arc::new(move |value| Self::format_value(value, rubberband))),
             },
         )
     }
@@ -47,13 +49,41 @@ impl AudioPitch {
         }
     }
 
+    /// Show the pitch shifting method in use
+    fn format_value(value: &str, rubberband: bool) -> String {
+        if value == Self::DEFAULT {
+            value.to_owned()
+        } else if rubberband {
+            format!("{value} (rubberband)")
+        } else {
+            format!("{value} (resample)")
+        }
+    }
+
     pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
         if !cb.discard_audio
             && let Some(value) = select_non_default_custom_value!(data)
         {
             debug!(value, "build_command");
-            cb.audio_filters
-                .push(format!("rubberband=pitchq=quality:pitch={}", &value));
+            if cb.ctx.features.rubberband {
+                cb.audio_filters
+                    .push(format!("rubberband=pitchq=quality:pitch={}", &value));
+            } else if let (Some(sample_rate), Ok(pitch)) =
+                (cb.ctx.sample_rate, value.parse::<f64>())
+            {
+                // Resampling changes both pitch and tempo, so restore the tempo back
+                cb.audio_filters
+                    .push(format!("asetrate={sample_rate}*{value}"));
+                cb.audio_filters.push(format!("aresample={sample_rate}"));
+                cb.audio_filters
+                    .extend(SpeedFactor::atempo_chain(1.0 / pitch));
+            } else {
+                cb.warnings.push(
+                    "FFmpeg has no rubberband filter and the audio sample rate is unknown, \
+                     the pitch is unchanged"
+                        .to_owned(),
+                );
+            }
         }
     }
 }
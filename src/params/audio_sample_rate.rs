@@ -0,0 +1,77 @@
+use tracing::debug;
+
+use crate::{
+    info::Info,
+    params::{
+        Parameter, ParameterData, PresetParameter, SelectOption, macros::select_non_default_option,
+    },
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+/// Output sample rate, should be applied after the speed and pitch filters
+/// which depend on the source sample rate
+pub(crate) struct AudioSampleRate;
+
+impl AudioSampleRate {
+    pub(crate) const ID: &'static str = "srate";
+    pub(crate) const NAME: &'static str = "Audio Sample Rate";
+    const DEFAULT: &'static str = "0";
+    const VARIANTS: [u32; 4] = [22050, 44100, 48000, 96000];
+
+    pub fn new_parameter(info: &Info) -> Parameter {
+        let source = info.sample_rate();
+        let original =
+            source.map_or_else(|| "original".to_owned(), |sr| format!("original ({sr})"));
+        let mut options = vec![SelectOption::from((original.as_str(), Self::DEFAULT))];
+        for rate in Self::VARIANTS
+            .into_iter()
+            .filter(|rate| Some(*rate) != source)
+        {
+            let value = rate.to_string();
+            let name = if source.is_some_and(|sr| rate > sr) {
+                format!("{rate} (upsample)")
+            } else {
+                value.clone()
+            };
+            options.push(SelectOption::from((name.as_str(), value.as_str())));
+        }
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Select {
+                options,
+                selected_index: 0,
+            },
+        )
+        .with_order(1500)
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if !cb.discard_audio
+            && let Some(option) = select_non_default_option!(data)
+        {
+            debug!(
+                value = option.value,
+                soxr = cb.ctx.features.soxr,
+                "build_command"
+            );
+            if cb.ctx.features.soxr {
+                cb.audio_filters
+                    .push(format!("aresample={}:resampler=soxr", option.value));
+            } else {
+                cb.args.push("-ar".to_owned());
+                cb.args.push(option.value.clone());
+            }
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for AudioSampleRate {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        Self::set_parameter_value(data, preset_value);
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_option!(data).map(|option| option.value.clone())
+    }
+}
@@ -0,0 +1,78 @@
+use tracing::{debug, warn};
+
+use crate::{
+    params::{
+        AudioCodec, Parameter, ParameterData, PresetParameter, SelectOption,
+        macros::select_non_default_option,
+    },
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+/// Variable bitrate quality, an alternative to the constant audio bitrate.
+/// Should be applied after the audio codec
+pub(crate) struct AudioVbr;
+
+impl AudioVbr {
+    pub(crate) const ID: &'static str = "avbr";
+    pub(crate) const NAME: &'static str = "Audio VBR Quality";
+    const DEFAULT: &'static str = "off";
+
+    pub fn new_parameter() -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Select {
+                options: SelectOption::from_slice(&["off", "high", "medium", "low"]),
+                selected_index: 0,
+            },
+        )
+        .with_order(140)
+    }
+
+    pub(crate) fn is_enabled(data: &ParameterData) -> bool {
+        select_non_default_option!(data).is_some()
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if cb.discard_audio {
+            return;
+        }
+        let Some(option) = select_non_default_option!(data) else {
+            return;
+        };
+        let codec = cb
+            .audio_codec
+            .as_deref()
+            .or_else(|| AudioCodec::default_for(&cb.ext))
+            .unwrap_or_default();
+        debug!(value = option.value, codec, "build_command");
+        let level = ["high", "medium", "low"]
+            .iter()
+            .position(|v| *v == option.value)
+            .unwrap_or(1);
+        let args = match codec {
+            // LAME quality, 0 is the best
+            "libmp3lame" => vec!["-q:a", ["0", "2", "5"][level]],
+            // Vorbis quality, 10 is the best
+            "libvorbis" => vec!["-q:a", ["8", "6", "4"][level]],
+            // Opus is always VBR, the bitrate is the target
+            "libopus" => vec!["-vbr", "on", "-b:a", ["160k", "128k", "96k"][level]],
+            _ => {
+                warn!(codec, "VBR is not supported");
+                return;
+            }
+        };
+        cb.pre_output_args
+            .extend(args.into_iter().map(ToOwned::to_owned));
+    }
+}
+
+impl<'a> PresetParameter<'a> for AudioVbr {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        Self::set_parameter_value(data, preset_value);
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_option!(data).map(|option| option.value.clone())
+    }
+}
@@ -0,0 +1,245 @@
+/// Output container and the codecs it accepts.
+/// Codec names are the audio codec parameter values, video codecs are the generic FFmpeg names
+#[derive(Debug)]
+pub(crate) struct Container {
+    pub(crate) ext: &'static str,
+    pub(crate) audio_only: bool,
+    /// Still images written by the image muxer, no audio
+    pub(crate) image: bool,
+    pub(crate) video_codecs: &'static [&'static str],
+    pub(crate) audio_codecs: &'static [&'static str],
+    /// Audio codec FFmpeg picks for the container
+    pub(crate) default_audio: Option<&'static str>,
+    /// Move the index to the beginning for progressive playback (`-movflags faststart`)
+    pub(crate) faststart: bool,
+    /// Audio format can hold an attached picture
+    pub(crate) cover_art: bool,
+}
+
+/// Output formats in the UI order, video ones first
+pub(crate) const CONTAINERS: [Container; 17] = [
+    Container {
+        ext: "mp4",
+        audio_only: false,
+        image: false,
+        video_codecs: &["h264", "hevc", "av1", "vp9"],
+        audio_codecs: &["aac", "libopus", "libmp3lame", "flac"],
+        default_audio: Some("aac"),
+        faststart: true,
+        cover_art: true,
+    },
+    Container {
+        ext: "mkv",
+        audio_only: false,
+        image: false,
+        video_codecs: &["h264", "hevc", "av1", "vp9", "vp8"],
+        audio_codecs: &["aac", "libopus", "libmp3lame", "libvorbis", "flac", "pcm"],
+        default_audio: Some("libvorbis"),
+        faststart: false,
+        cover_art: true,
+    },
+    Container {
+        ext: "webm",
+        audio_only: false,
+        image: false,
+        video_codecs: &["vp9", "vp8", "av1"],
+        audio_codecs: &["libopus", "libvorbis"],
+        default_audio: Some("libopus"),
+        faststart: false,
+        cover_art: false,
+    },
+    Container {
+        ext: "mov",
+        audio_only: false,
+        image: false,
+        video_codecs: &["h264", "hevc", "prores"],
+        audio_codecs: &["aac", "libmp3lame", "pcm"],
+        default_audio: Some("aac"),
+        faststart: true,
+        cover_art: true,
+    },
+    Container {
+        ext: "ts",
+        audio_only: false,
+        image: false,
+        video_codecs: &["h264", "hevc"],
+        audio_codecs: &["aac", "libmp3lame", "libopus"],
+        default_audio: Some("mp2"),
+        faststart: false,
+        cover_art: false,
+    },
+    // Adaptive streaming packages, see `PackageFormat`
+    Container {
+        ext: "m3u8",
+        audio_only: false,
+        image: false,
+        video_codecs: &["h264", "hevc"],
+        audio_codecs: &["aac", "libmp3lame"],
+        default_audio: Some("aac"),
+        faststart: false,
+        cover_art: false,
+    },
+    Container {
+        ext: "mpd",
+        audio_only: false,
+        image: false,
+        video_codecs: &["h264", "hevc", "vp9", "av1"],
+        audio_codecs: &["aac", "libopus"],
+        default_audio: Some("aac"),
+        faststart: false,
+        cover_art: false,
+    },
+    // Still images, see the Frames parameter
+    Container {
+        ext: "png",
+        audio_only: false,
+        image: true,
+        video_codecs: &["png"],
+        audio_codecs: &[],
+        default_audio: None,
+        faststart: false,
+        cover_art: false,
+    },
+    Container {
+        ext: "jpg",
+        audio_only: false,
+        image: true,
+        video_codecs: &["mjpeg"],
+        audio_codecs: &[],
+        default_audio: None,
+        faststart: false,
+        cover_art: false,
+    },
+    Container {
+        ext: "webp",
+        audio_only: false,
+        image: true,
+        video_codecs: &["libwebp"],
+        audio_codecs: &[],
+        default_audio: None,
+        faststart: false,
+        cover_art: false,
+    },
+    Container {
+        ext: "mp3",
+        audio_only: true,
+        image: false,
+        video_codecs: &[],
+        audio_codecs: &["libmp3lame"],
+        default_audio: Some("libmp3lame"),
+        faststart: false,
+        cover_art: true,
+    },
+    Container {
+        ext: "m4a",
+        audio_only: true,
+        image: false,
+        video_codecs: &[],
+        audio_codecs: &["aac", "flac"],
+        default_audio: Some("aac"),
+        faststart: true,
+        cover_art: true,
+    },
+    Container {
+        ext: "aac",
+        audio_only: true,
+        image: false,
+        video_codecs: &[],
+        audio_codecs: &["aac"],
+        default_audio: Some("aac"),
+        faststart: false,
+        cover_art: false,
+    },
+    Container {
+        ext: "opus",
+        audio_only: true,
+        image: false,
+        video_codecs: &[],
+        audio_codecs: &["libopus"],
+        default_audio: Some("libopus"),
+        faststart: false,
+        cover_art: false,
+    },
+    Container {
+        ext: "ogg",
+        audio_only: true,
+        image: false,
+        video_codecs: &[],
+        audio_codecs: &["libvorbis", "libopus", "flac"],
+        default_audio: Some("libvorbis"),
+        faststart: false,
+        cover_art: false,
+    },
+    Container {
+        ext: "flac",
+        audio_only: true,
+        image: false,
+        video_codecs: &[],
+        audio_codecs: &["flac"],
+        default_audio: Some("flac"),
+        faststart: false,
+        cover_art: true,
+    },
+    Container {
+        ext: "wav",
+        audio_only: true,
+        image: false,
+        video_codecs: &[],
+        audio_codecs: &["pcm"],
+        default_audio: Some("pcm"),
+        faststart: false,
+        cover_art: false,
+    },
+];
+
+impl Container {
+    /// Known container by the file extension
+    pub(crate) fn find(ext: &str) -> Option<&'static Container> {
+        CONTAINERS.iter().find(|c| c.ext == ext)
+    }
+
+    pub(crate) fn accepts_audio(&self, codec: &str) -> bool {
+        self.audio_codecs.contains(&codec)
+    }
+
+    pub(crate) fn accepts_video(&self, codec: &str) -> bool {
+        self.video_codecs.contains(&codec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let webm = Container::find("webm").expect("known container");
+        assert!(!webm.audio_only);
+        assert!(webm.accepts_audio("libopus"));
+        assert!(!webm.accepts_audio("aac"));
+        assert!(!webm.accepts_video("h264"));
+        assert!(Container::find("m4a").is_some_and(|c| c.audio_only && c.faststart));
+        assert!(Container::find("avi").is_none());
+        assert!(Container::find("jpg").is_some_and(|c| c.image && c.default_audio.is_none()));
+    }
+
+    #[test]
+    fn test_table_consistency() {
+        for container in &CONTAINERS {
+            assert_eq!(container.audio_only, container.video_codecs.is_empty());
+            assert_eq!(
+                container.image,
+                container.audio_codecs.is_empty(),
+                "{}",
+                container.ext
+            );
+            assert_eq!(
+                CONTAINERS.iter().filter(|c| c.ext == container.ext).count(),
+                1
+            );
+        }
+        // Video formats go first in the UI
+        let first_audio = CONTAINERS.iter().position(|c| c.audio_only).unwrap();
+        assert!(CONTAINERS[first_audio..].iter().all(|c| c.audio_only));
+    }
+}
@@ -1,6 +1,10 @@
 use tracing::debug;
 
-use crate::visitors::CommandBuilder;
+use crate::{
+    model::PackageFormat,
+    params::{AudioCodec, Container, OutputFormat},
+    visitors::{CommandBuilder, InputLoop},
+};
 
 /// Always enabled and always last virtual parameter
 /// Does not rely on other parameters that might be disabled by user choice
@@ -9,9 +13,186 @@ pub(crate) struct Finalizer;
 impl Finalizer {
     pub fn build_command(cb: &mut CommandBuilder) {
         debug!("build_command finalizer");
-        if cb.ext == "mp4" || cb.ext == "mov" {
-            cb.pre_output_args.push("-movflags".to_owned());
-            cb.pre_output_args.push("faststart".to_owned());
+        if let Some(input_loop) = cb.input_loop {
+            Self::input_loop(cb, input_loop);
         }
+        if Container::find(&cb.ext).is_some_and(|c| c.faststart) {
+            // The segment muxer passes the flags to the muxer of each file
+            if cb.segments.is_some() {
+                cb.pre_output_args
+                    .push("-segment_format_options".to_owned());
+                cb.pre_output_args.push("movflags=+faststart".to_owned());
+            } else {
+                cb.pre_output_args.push("-movflags".to_owned());
+                cb.pre_output_args.push("faststart".to_owned());
+            }
+        }
+        if !cb.discard_audio
+            && let Some(codec) = cb.audio_codec.take()
+        {
+            Self::audio_codec(cb, codec);
+        }
+        if let Some(format) = PackageFormat::from_ext(&cb.ext) {
+            Self::package(cb, format);
+        }
+    }
+
+    /// Muxer options of the adaptive streaming package, key frames are aligned with the segments
+    fn package(cb: &mut CommandBuilder, format: PackageFormat) {
+        let has_video = cb.ctx.frame_rate.is_some();
+        let has_audio = !cb.discard_audio && cb.ctx.sample_rate.is_some();
+        let videos = cb.renditions.len().max(usize::from(has_video));
+        let time = PackageFormat::SEGMENT_TIME.to_string();
+        if has_video {
+            cb.pre_output_args.push("-force_key_frames".to_owned());
+            cb.pre_output_args
+                .push(format!("expr:gte(t,n_forced*{time})"));
+        }
+        match format {
+            PackageFormat::Hls => {
+                // Each variant has its own audio stream
+                let streams: Vec<String> = if videos == 0 {
+                    vec!["a:0".to_owned()]
+                } else {
+                    (0..videos)
+                        .map(|i| {
+                            if has_audio {
+                                format!("v:{i},a:{i}")
+                            } else {
+                                format!("v:{i}")
+                            }
+                        })
+                        .collect()
+                };
+                cb.pre_output_args.extend(
+                    [
+                        "-f",
+                        "hls",
+                        "-hls_time",
+                        &time,
+                        "-hls_playlist_type",
+                        "vod",
+                        "-master_pl_name",
+                        &format!("{}.m3u8", format.index()),
+                        "-var_stream_map",
+                        &streams.join(" "),
+                    ]
+                    .map(ToOwned::to_owned),
+                );
+            }
+            PackageFormat::Dash => {
+                let mut sets = Vec::new();
+                if has_video {
+                    sets.push(format!("id={},streams=v", sets.len()));
+                }
+                if has_audio {
+                    sets.push(format!("id={},streams=a", sets.len()));
+                }
+                cb.pre_output_args.extend(
+                    [
+                        "-f",
+                        "dash",
+                        "-seg_duration",
+                        &time,
+                        "-use_template",
+                        "1",
+                        "-use_timeline",
+                        "1",
+                        "-adaptation_sets",
+                        &sets.join(" "),
+                    ]
+                    .map(ToOwned::to_owned),
+                );
+            }
+        }
+    }
+
+    /// Loop filters when the input is filtered anyway, otherwise the demuxer repeats the input
+    fn input_loop(cb: &mut CommandBuilder, input_loop: InputLoop) {
+        let repeats = input_loop.repeats.to_string();
+        let has_filters = !cb.video_filters.is_empty()
+            || !cb.complex_video_filters.is_empty()
+            || (!cb.discard_audio && !cb.audio_filters.is_empty());
+        let has_video = cb.ctx.frame_rate.is_some() && !OutputFormat::is_audio(&cb.ext);
+        let frames = input_loop
+            .duration
+            .zip(cb.ctx.frame_rate)
+            .map(|(d, fps)| (d * fps).ceil() as u64);
+        let samples = input_loop
+            .duration
+            .zip(cb.ctx.sample_rate)
+            .map(|(d, sr)| (d * f64::from(sr)).ceil() as u64);
+        let sizes_known = (!has_video || frames.is_some())
+            && (cb.discard_audio || cb.ctx.sample_rate.is_none() || samples.is_some());
+        if has_filters && sizes_known {
+            if has_video && let Some(frames) = frames {
+                if frames > 1000 {
+                    cb.warnings.push(format!(
+                        "Loop filter keeps {frames} decoded frames in memory"
+                    ));
+                }
+                cb.video_filters.splice(
+                    0..0,
+                    [
+                        format!("loop=loop={repeats}:size={frames}:start=0"),
+                        "setpts=N/FRAME_RATE/TB".to_owned(),
+                    ],
+                );
+                for filter in &mut cb.complex_video_filters {
+                    filter.position += 2;
+                }
+            }
+            if !cb.discard_audio
+                && let Some(samples) = samples
+            {
+                cb.audio_filters.splice(
+                    0..0,
+                    [
+                        format!("aloop=loop={repeats}:size={samples}:start=0"),
+                        "asetpts=N/SR/TB".to_owned(),
+                    ],
+                );
+            }
+        } else {
+            cb.pre_input_args.push("-stream_loop".to_owned());
+            cb.pre_input_args.push(repeats.clone());
+            // The source opened again, e.g. by the audio offset, plays the same way
+            for input in cb.extra_inputs.iter_mut().filter(|input| input.source) {
+                input
+                    .args
+                    .splice(0..0, ["-stream_loop".to_owned(), repeats.clone()]);
+            }
+        }
+        let has_cut = cb
+            .args
+            .iter()
+            .chain(&cb.pre_input_args)
+            .any(|arg| arg == "-t" || arg == "-to");
+        if let Some(target) = input_loop.target
+            && !has_cut
+        {
+            cb.args.push("-t".to_owned());
+            cb.args.push(format!("{target}"));
+        }
+    }
+
+    fn audio_codec(cb: &mut CommandBuilder, codec: String) {
+        if codec == "copy" && !cb.audio_filters.is_empty() {
+            cb.warnings.push(
+                "Audio filters are used, the audio is re-encoded instead of copied".to_owned(),
+            );
+            return;
+        }
+        let has_bitrate = cb
+            .pre_output_args
+            .iter()
+            .any(|arg| arg == "-b:a" || arg == "-q:a");
+        cb.pre_output_args.push("-c:a".to_owned());
+        cb.pre_output_args.push(codec.clone());
+        if !has_bitrate && let Some(bitrate) = AudioCodec::default_bitrate(&codec) {
+            cb.pre_output_args.push("-b:a".to_owned());
+            cb.pre_output_args.push(bitrate.to_owned());
+        }
+        cb.audio_codec = Some(codec);
     }
 }
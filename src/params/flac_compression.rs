@@ -0,0 +1,55 @@
+use tracing::debug;
+
+use crate::{
+    params::{
+        Parameter, ParameterData, PresetParameter, SelectOption, macros::select_non_default_option,
+    },
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+/// FLAC compression level, higher is smaller and slower, the sound is the same
+pub(crate) struct FlacCompression;
+
+impl FlacCompression {
+    pub(crate) const ID: &'static str = "flaclevel";
+    pub(crate) const NAME: &'static str = "FLAC Compression";
+    const DEFAULT: &'static str = "5";
+
+    pub fn new_parameter() -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Select {
+                options: SelectOption::from_pairs(&[
+                    ("0 (fastest)", "0"),
+                    ("2", "2"),
+                    ("5 (default)", "5"),
+                    ("8", "8"),
+                    ("12 (smallest)", "12"),
+                ]),
+                selected_index: 2,
+            },
+        )
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if !cb.discard_audio
+            && cb.ext == "flac"
+            && let Some(option) = select_non_default_option!(data)
+        {
+            debug!(value = option.value, "build_command");
+            cb.args.push("-compression_level".to_owned());
+            cb.args.push(option.value.clone());
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for FlacCompression {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        Self::set_parameter_value(data, preset_value);
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_option!(data).map(|option| option.value.clone())
+    }
+}
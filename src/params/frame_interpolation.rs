@@ -0,0 +1,87 @@
+use tracing::debug;
+
+use crate::{
+    params::{
+        Parameter, ParameterData, PresetParameter, SelectOption, macros::select_non_default_option,
+    },
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+/// Frame rate conversion mode for the output frame rate and slow motion.
+/// Should be applied after the speed, frame rate and scale parameters
+pub(crate) struct FrameInterpolation;
+
+impl FrameInterpolation {
+    pub(crate) const ID: &'static str = "interp";
+    pub(crate) const NAME: &'static str = "Frame Interpolation";
+    const DEFAULT: &'static str = "dup";
+    // Rough motion interpolation speed of an average CPU, frames per second
+    const MCI_FPS: f64 = 5.0;
+
+    pub fn new_parameter() -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Select {
+                options: SelectOption::from_pairs(&[
+                    ("drop/dup", "dup"),
+                    ("blend", "blend"),
+                    ("motion", "mci"),
+                ]),
+                selected_index: 0,
+            },
+        )
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if let Some(option) = select_non_default_option!(data) {
+            // Target frame rate is either the selected one or the source one for slow motion
+            let slow_motion = cb.speed_factor.is_some_and(|speed| speed < 1.0);
+            let Some(fps) = cb
+                .output_frame_rate
+                .or(cb.ctx.frame_rate.filter(|_| slow_motion))
+            else {
+                return;
+            };
+            let fps = format!("{fps:.3}")
+                .trim_end_matches('0')
+                .trim_end_matches('.')
+                .to_owned();
+            debug!(value = option.value, fps, "build_command");
+            match option.value.as_str() {
+                "blend" => cb.video_filters.push(format!("framerate=fps={fps}")),
+                "mci" => {
+                    cb.video_filters.push(format!(
+                        "minterpolate=fps={fps}:mi_mode=mci:mc_mode=aobmc:me_mode=bidir:vsbmc=1"
+                    ));
+                    Self::warn_render_time(cb, &fps);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn warn_render_time(cb: &mut CommandBuilder, fps: &str) {
+        let (Some(duration), Ok(fps)) = (cb.ctx.input_duration, fps.parse::<f64>()) else {
+            cb.warnings
+                .push("Motion interpolation is slow, rendering may take a long time".to_owned());
+            return;
+        };
+        let out_duration = duration / cb.speed_factor.unwrap_or(1.0);
+        let minutes = (out_duration * fps / Self::MCI_FPS / 60.0).ceil();
+        cb.warnings.push(format!(
+            "Motion interpolation is slow, rendering may take about {minutes} min \
+             for {out_duration:.0}s of output"
+        ));
+    }
+}
+
+impl<'a> PresetParameter<'a> for FrameInterpolation {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        Self::set_parameter_value(data, preset_value);
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_option!(data).map(|option| option.value.clone())
+    }
+}
@@ -0,0 +1,193 @@
+use tracing::{debug, warn};
+
+use crate::{
+    info::Info,
+    model::{FramesData, FramesMode, set_allowed},
+    params::{OutputFormat, Parameter, ParameterData, PresetParameter, Text},
+    visitors::{CommandBuilder, VisitorContext, escape_filter_value},
+};
+
+/// Write still images instead of a video, the range comes from the trim and the size from the scale.
+/// Should be applied after the other video filters, the frames are picked from the filtered video
+pub(crate) struct Frames;
+
+impl Frames {
+    pub(crate) const ID: &'static str = "frames";
+    pub(crate) const NAME: &'static str = "Frames";
+    // Contact sheet layout, pixels
+    const TILE_WIDTH: u32 = 320;
+    const TILE_GAP: u32 = 4;
+    const HEADER_HEIGHT: u32 = 36;
+
+    pub fn new_parameter(info: &Info) -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Frames(FramesData {
+                summary: info.summary(),
+                ..Default::default()
+            }),
+        )
+        .with_order(4150)
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        let ParameterData::Frames(frames) = data else {
+            return;
+        };
+        if !OutputFormat::is_image(&cb.ext) {
+            return;
+        }
+        debug!(?frames, "build_command");
+        if !cb.discard_audio {
+            cb.discard_audio = true;
+            cb.args.push("-an".into());
+        }
+        // Output duration of the trimmed range after the speed change
+        let (start, end) = cb.source_range();
+        let duration = end
+            .or(cb.ctx.input_duration)
+            .map(|end| (end - start).max(0.0) / cb.speed_factor.unwrap_or(1.0));
+        match frames.mode {
+            FramesMode::Single => {
+                cb.pre_output_args
+                    .extend(["-frames:v", "1", "-update", "1"].map(ToOwned::to_owned));
+            }
+            FramesMode::Every => {
+                let interval = f64::from(frames.interval);
+                cb.video_filters.push(format!("fps=1/{}", frames.interval));
+                cb.frames = Some(duration.map(|d| (d / interval).ceil().max(1.0) as usize));
+            }
+            FramesMode::All => {
+                // Source timing, frames are not duplicated or dropped
+                cb.pre_output_args
+                    .extend(["-fps_mode", "passthrough"].map(ToOwned::to_owned));
+                let rate = cb.output_frame_rate.or(cb.ctx.frame_rate);
+                cb.frames = Some(
+                    duration
+                        .zip(rate)
+                        .map(|(d, rate)| (d * rate).ceil().max(1.0) as usize),
+                );
+            }
+            FramesMode::Scene => {
+                cb.video_filters
+                    .push(format!("select='gt(scene,{})'", frames.scene));
+                cb.pre_output_args
+                    .extend(["-fps_mode", "vfr"].map(ToOwned::to_owned));
+                cb.frames = Some(None);
+            }
+            FramesMode::Sheet => Self::sheet(cb, frames, duration),
+        }
+    }
+
+    /// One image with a grid of frames from the middle of equal parts of the duration
+    fn sheet(cb: &mut CommandBuilder, frames: &FramesData, duration: Option<f64>) {
+        let Some(duration) = duration.filter(|d| *d > 0.0) else {
+            warn!("Contact sheet needs the input duration");
+            cb.warnings
+                .push("Duration is unknown, the contact sheet is not made".to_owned());
+            return;
+        };
+        let (columns, rows) = frames.grid;
+        let interval = duration / f64::from(columns * rows);
+        // Filters see the source timestamps when the output is seeked by a precise trim
+        let offset = cb
+            .trim
+            .as_ref()
+            .filter(|trim| trim.output_args.is_some())
+            .map_or(0.0, |trim| trim.start / cb.speed_factor.unwrap_or(1.0));
+        cb.video_filters.push(format!(
+            "select='gte(t,{:.3}+selected_n*{interval:.3})'",
+            offset + interval / 2.0
+        ));
+        cb.video_filters
+            .push(format!("scale={}:-2", Self::TILE_WIDTH));
+        if frames.timestamps {
+            // Source time if the input was seeked
+            let time = match cb.input_seek {
+                Some(offset) => format!("%{{pts:hms:{offset}}}"),
+                None => "%{pts:hms}".to_owned(),
+            };
+            cb.video_filters.push(format!(
+                "drawtext=text={}:fontsize=16:fontcolor=white:box=1:boxcolor=black@0.5:boxborderw=4:x=w-tw-8:y=h-th-8",
+                escape_filter_value(&time)
+            ));
+        }
+        let gap = Self::TILE_GAP;
+        cb.video_filters
+            .push(format!("tile={columns}x{rows}:padding={gap}:margin={gap}"));
+        if frames.header && !frames.summary.is_empty() {
+            let height = Self::HEADER_HEIGHT;
+            cb.video_filters
+                .push(format!("pad=iw:ih+{height}:0:{height}:color=black"));
+            cb.video_filters.push(format!(
+                "drawtext=text={}:fontsize=20:fontcolor=white:x={gap}:y=({height}-th)/2",
+                escape_filter_value(&Text::escape_text(&frames.summary))
+            ));
+        }
+        cb.pre_output_args
+            .extend(["-frames:v", "1", "-update", "1"].map(ToOwned::to_owned));
+    }
+}
+
+impl<'a> PresetParameter<'a> for Frames {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        if let ParameterData::Frames(frames) = data {
+            // mode[,value], the contact sheet adds the header and timestamps flags
+            let mut parts = preset_value.split(',');
+            let Some(mode) = parts.next().and_then(FramesMode::from_id) else {
+                warn!("Unknown frames preset {}", preset_value);
+                return;
+            };
+            let value = parts.next();
+            let flags: Vec<&str> = parts.collect();
+            let valid = match (mode, value) {
+                (FramesMode::Sheet, Some(value)) => {
+                    match (FramesData::parse_grid(value), &flags[..]) {
+                        (Some(grid), [header, timestamps]) => {
+                            frames.grid = grid;
+                            frames.header = *header == "1";
+                            frames.timestamps = *timestamps == "1";
+                            true
+                        }
+                        _ => false,
+                    }
+                }
+                _ if !flags.is_empty() => false,
+                (FramesMode::Single | FramesMode::All, None) => true,
+                (FramesMode::Every, Some(value)) => {
+                    set_allowed(&FramesData::INTERVALS, value, &mut frames.interval)
+                }
+                (FramesMode::Scene, Some(value)) => {
+                    set_allowed(&FramesData::SCENES, value, &mut frames.scene)
+                }
+                _ => false,
+            };
+            if valid {
+                frames.mode = mode;
+            } else {
+                warn!("Frames preset is not valid and will be skipped");
+            }
+        }
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        match data {
+            ParameterData::Frames(frames) if frames.mode != FramesMode::Single => {
+                let mode = frames.mode.id();
+                Some(match frames.mode {
+                    FramesMode::Every => format!("{mode},{}", frames.interval),
+                    FramesMode::Scene => format!("{mode},{}", frames.scene),
+                    FramesMode::Sheet => format!(
+                        "{mode},{},{},{}",
+                        frames.grid_label(),
+                        u8::from(frames.header),
+                        u8::from(frames.timestamps)
+                    ),
+                    FramesMode::Single | FramesMode::All => mode.to_owned(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
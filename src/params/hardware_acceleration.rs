@@ -2,11 +2,13 @@ use tracing::debug;
 
 use crate::{
     params::{
-        Parameter, ParameterData, PresetParameter, SelectOption, macros::select_non_default_option,
+        Container, Parameter, ParameterData, PresetParameter, SelectOption,
+        macros::select_non_default_option,
     },
     visitors::{CommandBuilder, HWAccel, VisitorContext},
 };
 
+/// Hardware H.264 encoders and decoders
 pub(crate) struct HardwareAcceleration;
 
 impl HardwareAcceleration {
@@ -38,6 +40,11 @@ impl HardwareAcceleration {
         .with_order(2000)
     }
 
+    /// All the hardware encoders are H.264, unknown formats accept everything
+    pub(crate) fn is_supported(ext: &str) -> bool {
+        Container::find(ext).is_none_or(|c| c.accepts_video("h264"))
+    }
+
     pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
         if let Some(option) = select_non_default_option!(data) {
             debug!(value = option.value, "build_command");
@@ -64,7 +71,7 @@ impl HardwareAcceleration {
                     cb.pre_input_args.push("-filter_hw_device".into());
                     cb.pre_input_args.push("hw".into());
                     // For recompress only enable full qsv processing
-                    if cb.video_filters.is_empty() {
+                    if cb.video_filters.is_empty() && cb.complex_video_filters.is_empty() {
                         cb.pre_input_args.push("-hwaccel".into());
                         cb.pre_input_args.push("qsv".into());
                         cb.pre_input_args.push("-c:v".into());
@@ -0,0 +1,69 @@
+use tracing::debug;
+
+use crate::{
+    params::{
+        Parameter, ParameterData, PresetParameter, SelectOption, macros::select_non_default_option,
+    },
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+/// Quality of JPEG and WebP images in percents, PNG is lossless
+pub(crate) struct ImageQuality;
+
+impl ImageQuality {
+    pub(crate) const ID: &'static str = "iquality";
+    pub(crate) const NAME: &'static str = "Image Quality";
+    const DEFAULT: &'static str = "auto";
+
+    pub fn new_parameter() -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Select {
+                options: SelectOption::from_pairs(&[
+                    ("auto", "auto"),
+                    ("100% (best)", "100"),
+                    ("90%", "90"),
+                    ("75%", "75"),
+                    ("50%", "50"),
+                    ("25% (smallest)", "25"),
+                ]),
+                selected_index: 0,
+            },
+        )
+    }
+
+    pub(crate) fn is_supported(ext: &str) -> bool {
+        matches!(ext, "jpg" | "webp")
+    }
+
+    /// MJPEG quality scale goes from 2 (best) to 31
+    fn jpeg_scale(quality: u32) -> u32 {
+        2 + (100 - quality.min(100)) * 29 / 100
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if let Some(option) = select_non_default_option!(data)
+            && let Ok(quality) = option.value.parse::<u32>()
+        {
+            debug!(quality, ext = cb.ext, "build_command");
+            let (option, value) = match cb.ext.as_str() {
+                "jpg" => ("-q:v", Self::jpeg_scale(quality)),
+                "webp" => ("-quality", quality),
+                _ => return,
+            };
+            cb.args.push(option.to_owned());
+            cb.args.push(value.to_string());
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for ImageQuality {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        Self::set_parameter_value(data, preset_value);
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_option!(data).map(|option| option.value.clone())
+    }
+}
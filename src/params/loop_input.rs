@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use tracing::{debug, warn};
+
+use crate::{
+    info::Info,
+    model::{InputConstraints, InputType},
+    params::{
+        Parameter, ParameterData, PresetParameter, SelectOption,
+        macros::select_non_default_custom_value,
+    },
+    visitors::{CommandBuilder, InputLoop, VisitorContext},
+};
+
+/// Play the input N times (`3`) or repeat it until the target duration (`60s`).
+/// Should be applied first, so the other parameters see the looped duration
+pub(crate) struct Loop;
+
+impl Loop {
+    pub(crate) const ID: &'static str = "loop";
+    pub(crate) const NAME: &'static str = "Loop";
+    const DEFAULT: &'static str = "1";
+    const VARIANTS: [&str; 9] = ["1", "2", "3", "4", "5", "10", "30s", "60s", "300s"];
+
+    pub fn new_parameter(info: &Info) -> Parameter {
+        let duration = info.get_duration();
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::CustomSelect {
+                options: SelectOption::from_slice(&Self::VARIANTS),
+                selected_index: 0,
+                value: Self::DEFAULT.to_owned(),
+                constraints: InputConstraints {
+                    length: 6,
+                    input_type: InputType::Interval,
+                },
+                validator: Arc::new(Self::validate),
+                formatter: Some(Arc::new(move |value| Self::format_value(value, duration))),
+            },
+        )
+        .with_order(50)
+    }
+
+    fn validate(value: &str) -> Result<String, &str> {
+        if let Some(seconds) = value.strip_suffix('s') {
+            if let Ok(num) = seconds.parse::<f64>()
+                && (1.0..=86400.0).contains(&num)
+            {
+                Ok(format!("{num}s"))
+            } else {
+                Err("Invalid duration. Expected seconds in range 1s..86400s")
+            }
+        } else if let Ok(num) = value.parse::<u32>()
+            && (1..=1000).contains(&num)
+        {
+            Ok(num.to_string())
+        } else {
+            Err("Invalid value. Expected play count 1..1000, or target duration like 60s")
+        }
+    }
+
+    /// Total play count and the target duration for the `60s` values
+    fn plays(value: &str, duration: Option<f64>) -> Option<(u32, Option<f64>)> {
+        match value.strip_suffix('s') {
+            Some(seconds) => {
+                let target = seconds.parse::<f64>().ok()?;
+                let duration = duration.filter(|d| *d > 0.0)?;
+                Some(((target / duration).ceil().max(1.0) as u32, Some(target)))
+            }
+            None => Some((value.parse().ok()?, None)),
+        }
+    }
+
+    fn format_value(value: &str, duration: Option<f64>) -> String {
+        if value == Self::DEFAULT {
+            return "off".to_owned();
+        }
+        match (value.strip_suffix('s'), Self::plays(value, duration)) {
+            (Some(seconds), Some((plays, _))) => format!("until {seconds}s ({plays} times)"),
+            (Some(seconds), None) => format!("until {seconds}s"),
+            (None, _) => format!("{value} times"),
+        }
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        let Some(value) = select_non_default_custom_value!(data) else {
+            return;
+        };
+        debug!(value, duration = cb.ctx.input_duration, "build_command");
+        let Some((plays, target)) = Self::plays(value, cb.ctx.input_duration) else {
+            warn!(value, "Loop target duration needs the input duration");
+            cb.warnings
+                .push("Input duration is unknown, the loop is ignored".to_owned());
+            return;
+        };
+        if plays < 2 {
+            return;
+        }
+        cb.input_loop = Some(InputLoop {
+            repeats: plays - 1,
+            duration: cb.ctx.input_duration,
+            target,
+        });
+        cb.ctx.input_duration = target.or(cb.ctx.input_duration.map(|d| d * f64::from(plays)));
+    }
+}
+
+impl<'a> PresetParameter<'a> for Loop {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        if Self::validate(preset_value).is_ok() {
+            Self::set_parameter_value(data, preset_value);
+        }
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_custom_value!(data).cloned()
+    }
+}
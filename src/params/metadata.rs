@@ -0,0 +1,157 @@
+use tracing::{debug, warn};
+
+use crate::{
+    info::Info,
+    model::{CoverArt, MetadataData, MetadataTags},
+    params::{Container, OutputFormat, Parameter, ParameterData, PresetParameter},
+    visitors::{
+        CommandBuilder, ExtraInput, VisitorContext, escape_preset_value, unescape_preset_value,
+    },
+};
+
+/// Edit or strip the metadata, keep or replace the cover art of audio outputs
+pub(crate) struct Metadata;
+
+impl Metadata {
+    pub(crate) const ID: &'static str = "metadata";
+    pub(crate) const NAME: &'static str = "Metadata";
+
+    pub fn new_parameter(info: &Info) -> Parameter {
+        let mut source = MetadataTags::default();
+        for key in MetadataTags::KEYS {
+            let value = match key {
+                "language" => info.format_tag(key).or_else(|| info.audio_tag(key)),
+                _ => info.format_tag(key),
+            };
+            if let Some(value) = value
+                && let Some(tag) = source.get_mut(key)
+            {
+                value.clone_into(tag);
+            }
+        }
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Metadata(MetadataData::with_source(source, info.has_cover_art())),
+        )
+        .with_order(2100)
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if let ParameterData::Metadata(meta) = data
+            && !meta.is_unchanged()
+        {
+            debug!(?meta, "build_command");
+            if meta.strip {
+                cb.args.extend(
+                    ["-map_metadata", "-1", "-map_chapters", "-1"]
+                        .iter()
+                        .map(|&arg| arg.to_owned()),
+                );
+            }
+            for (key, value) in meta.tags_to_write() {
+                // Language is a stream tag
+                let option = if key == "language" {
+                    "-metadata:s:a"
+                } else {
+                    "-metadata"
+                };
+                cb.metadata
+                    .push((option.to_owned(), format!("{key}={value}")));
+            }
+        }
+        if let ParameterData::Metadata(meta) = data
+            && OutputFormat::is_audio(&cb.ext)
+        {
+            Self::cover_art(cb, meta);
+        }
+    }
+
+    /// Copy the picture stream as is, video encoders would turn it into a video track
+    fn cover_art(cb: &mut CommandBuilder, meta: &MetadataData) {
+        let supported = Container::find(&cb.ext).is_some_and(|c| c.cover_art);
+        let cover_input = match (meta.cover, &meta.cover_path) {
+            (CoverArt::Keep, _) if meta.source_cover && supported => Some(0),
+            (CoverArt::Replace, Some(path)) if supported => Some(cb.push_input(ExtraInput {
+                path: path.clone(),
+                ..Default::default()
+            })),
+            (CoverArt::Replace, _) => {
+                warn!(ext = cb.ext, "Cover art is not supported");
+                cb.warnings
+                    .push(format!("{} output can't hold cover art", cb.ext));
+                None
+            }
+            _ => None,
+        };
+        if let Some(input) = cover_input {
+            cb.cover_input = Some(input);
+            cb.pre_output_args.extend(
+                ["-c:v", "copy", "-disposition:v:0", "attached_pic"]
+                    .iter()
+                    .map(|&arg| arg.to_owned()),
+            );
+        } else if meta.source_cover {
+            cb.args.push("-vn".to_owned());
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for Metadata {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        if let ParameterData::Metadata(meta) = data {
+            // flags|cover|cover path|title|artist|album|comment|date|language
+            let parts: Vec<String> = preset_value
+                .splitn(9, '|')
+                .map(unescape_preset_value)
+                .collect();
+            if parts.len() != 9 {
+                warn!("Metadata preset is not valid and will be skipped");
+                return;
+            }
+            let Some(cover) = CoverArt::from_id(&parts[1]) else {
+                warn!("Unknown cover art mode {}", parts[1]);
+                return;
+            };
+            if let Err(msg) = MetadataTags::validate(&parts[8]) {
+                warn!("Metadata preset is not valid and will be skipped: {}", msg);
+                return;
+            }
+            meta.strip = parts[0].contains('s');
+            meta.cover = cover;
+            meta.cover_path = Some(parts[2].clone()).filter(|v| !v.is_empty());
+            // Empty values keep the source tags
+            meta.tags = meta.source.clone();
+            for (key, value) in MetadataTags::KEYS.into_iter().zip(&parts[3..]) {
+                if !value.is_empty()
+                    && let Some(tag) = meta.tags.get_mut(key)
+                {
+                    value.clone_into(tag);
+                }
+            }
+        }
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        if let ParameterData::Metadata(meta) = data
+            && !meta.is_unchanged()
+        {
+            let mut parts = vec![
+                if meta.strip { "s" } else { "" }.to_owned(),
+                meta.cover.id().to_owned(),
+                escape_preset_value(meta.cover_path.as_deref().unwrap_or_default()),
+            ];
+            for key in MetadataTags::KEYS {
+                let value = meta.tags.get(key);
+                parts.push(if value == meta.source.get(key) {
+                    String::new()
+                } else {
+                    escape_preset_value(value)
+                });
+            }
+            Some(parts.join("|"))
+        } else {
+            None
+        }
+    }
+}
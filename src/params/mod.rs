@@ -1,78 +1,183 @@
 /// Parameters are sorted by logical order, which is different from UI order
 ///
 /// Order:
+///    50 loop
 ///   100 noaudio
 ///   120 output
+///   130 acodec
+///   140 avbr
+///   990 scaler, keeppitch
 ///  1000 default params (in order of addition)
+///  1500 srate
 ///  1600 trim
+///  1650 aoffset
+///  1700 silence
+///  1800 overlay
+///  1900 text
 ///  2000 hwaccel
+///  2100 metadata
 ///  4000 abitrate, vbitrate
+///  4100 split
+///  4150 frames
+///  4200 renditions
 mod macros;
 mod parameter;
 
+mod aspect;
+mod audio_bit_depth;
 mod audio_bitrate;
+mod audio_channels;
+mod audio_codec;
 mod audio_crystalizer;
+mod audio_eq;
+mod audio_offset;
 mod audio_pitch;
+mod audio_sample_rate;
+mod audio_vbr;
 mod audio_volume;
+mod container;
 mod disable_audio;
 mod finalizer;
+mod flac_compression;
+mod frame_interpolation;
+mod frames;
 mod hardware_acceleration;
+mod image_quality;
+mod loop_input;
+mod metadata;
 mod output_format;
+mod overlay;
+mod preserve_pitch;
+mod remove_silence;
+mod renditions;
+mod scale_algorithm;
 mod speed_factor;
+mod split;
+mod text;
+mod timelapse;
 mod trim;
 mod video_bitrate;
 mod video_frame_rate;
 mod video_scale;
+mod voice_cleanup;
 
+pub(crate) use aspect::*;
+pub(crate) use audio_bit_depth::*;
 pub(crate) use audio_bitrate::*;
+pub(crate) use audio_channels::*;
+pub(crate) use audio_codec::*;
 pub(crate) use audio_crystalizer::*;
+pub(crate) use audio_eq::*;
+pub(crate) use audio_offset::*;
 pub(crate) use audio_pitch::*;
+pub(crate) use audio_sample_rate::*;
+pub(crate) use audio_vbr::*;
 pub(crate) use audio_volume::*;
+pub(crate) use container::{CONTAINERS, Container};
 pub(crate) use disable_audio::*;
 pub(crate) use finalizer::*;
+pub(crate) use flac_compression::*;
+pub(crate) use frame_interpolation::*;
+pub(crate) use frames::*;
 pub(crate) use hardware_acceleration::*;
+pub(crate) use image_quality::*;
+pub(crate) use loop_input::*;
+pub(crate) use metadata::*;
 pub(crate) use output_format::*;
+pub(crate) use overlay::*;
 pub(crate) use parameter::{Parameter, ParameterData, PresetParameter, SelectOption};
+pub(crate) use preserve_pitch::*;
+pub(crate) use remove_silence::*;
+pub(crate) use renditions::*;
+pub(crate) use scale_algorithm::*;
 pub(crate) use speed_factor::*;
+pub(crate) use split::*;
+pub(crate) use text::*;
+pub(crate) use timelapse::*;
 pub(crate) use trim::*;
 pub(crate) use video_bitrate::*;
 pub(crate) use video_frame_rate::*;
 pub(crate) use video_scale::*;
+pub(crate) use voice_cleanup::*;
 
 use crate::{
+    features::Features,
     info::Info,
+    model::PackageFormat,
     params::macros::select_option,
     visitors::{ParameterVisitor, PresetApplier, PresetSaver, VisitorContext},
 };
 use tracing::{Level, debug, enabled};
 
 /// Create parameters based on given info and apply the preset
-pub(crate) fn create_params(info: &Info, preset: Option<&str>, source_ext: &str) -> Vec<Parameter> {
+pub(crate) fn create_params(
+    info: &Info,
+    features: Features,
+    preset: Option<&str>,
+    source_ext: &str,
+) -> Vec<Parameter> {
     let mut params: Vec<Parameter> = Vec::new();
     if info.has_non_empty_duration() {
         params.push(Trim::new_parameter());
+        params.push(Loop::new_parameter(info));
     }
     if info.has_audio() && info.has_video() {
         params.push(DisableAudio::new_parameter());
     }
     if info.has_audio() {
-        params.push(AudioBitrate::new_parameter());
+        params.push(AudioCodec::new_parameter());
+        params.push(AudioBitrate::new_parameter(info));
+        params.push(AudioVbr::new_parameter());
+        params.push(AudioChannels::new_parameter(info));
+        if info.has_video() {
+            params.push(AudioOffset::new_parameter());
+        }
+        params.push(AudioSampleRate::new_parameter(info));
+        params.push(AudioBitDepth::new_parameter());
+        params.push(FlacCompression::new_parameter());
+        params.push(VoiceCleanup::new_parameter());
         params.push(AudioCrystalizer::new_parameter());
+        params.push(AudioEq::new_parameter());
         params.push(AudioVolume::new_parameter());
-        params.push(AudioPitch::new_parameter());
+        params.push(AudioPitch::new_parameter(features));
+        if info.has_non_empty_duration() {
+            params.push(RemoveSilence::new_parameter(info));
+        }
     }
     if info.has_non_empty_duration() {
         params.push(SpeedFactor::new_parameter());
+        if info.has_audio() {
+            params.push(PreservePitch::new_parameter());
+        }
+        if info.has_video() {
+            params.push(Timelapse::new_parameter(info));
+        }
     }
     if info.has_video() {
-        params.push(VideoBitrate::new_parameter());
-        params.push(VideoFrameRate::new_parameter());
-        params.push(VideoScale::new_parameter());
+        params.push(VideoBitrate::new_parameter(info));
+        params.push(VideoFrameRate::new_parameter(info));
+        params.push(Aspect::new_parameter(info));
+        params.push(VideoScale::new_parameter(info));
+        params.push(ScaleAlgorithm::new_parameter());
+        if info.has_non_empty_duration() {
+            params.push(FrameInterpolation::new_parameter());
+        }
+        params.push(Overlay::new_parameter());
+        params.push(Text::new_parameter());
         params.push(HardwareAcceleration::new_parameter());
+        params.push(Renditions::new_parameter(info));
+    }
+    if info.has_non_empty_duration() {
+        params.push(Split::new_parameter(info));
+    }
+    if info.has_video() {
+        params.push(Frames::new_parameter(info));
+        params.push(ImageQuality::new_parameter());
     }
+    params.push(Metadata::new_parameter(info));
     params.push(OutputFormat::new_parameter(info, source_ext));
     if let Some(preset_value) = preset {
-        let ctx = VisitorContext::new(info);
+        let ctx = VisitorContext::new(info, features);
         apply_preset(ctx, &mut params, preset_value);
     }
     recheck_params(&mut params);
@@ -92,11 +197,9 @@ pub(crate) fn save_preset(ctx: VisitorContext, params: &mut [Parameter]) -> Stri
 
 /// Recheck and disable conflicting parameters after each option change
 pub(crate) fn recheck_params(params: &mut [Parameter]) {
-    let result_is_audio = if let Some(result_format) = get_output_format(params) {
-        OutputFormat::is_audio(&result_format.value)
-    } else {
-        false
-    };
+    let result_ext = get_output_format(params).map_or_else(String::new, |o| o.value.clone());
+    let result_is_audio = OutputFormat::is_audio(&result_ext);
+    let result_is_image = OutputFormat::is_image(&result_ext);
 
     let audio_is_disabled = if let Some(disable_audio) = params
         .iter()
@@ -109,26 +212,120 @@ pub(crate) fn recheck_params(params: &mut [Parameter]) {
     } else {
         false
     };
+    // Effective audio codec and VBR mode decide which bitrate parameter is used
+    let audio_codec = params
+        .iter()
+        .filter(|param| param.id == AudioCodec::ID)
+        .find_map(|param| select_option!(&param.data))
+        .map(|option| option.value.as_str())
+        .filter(|codec| AudioCodec::is_compatible(codec, &result_ext) && *codec != "auto")
+        .or_else(|| AudioCodec::default_for(&result_ext))
+        .map(ToOwned::to_owned);
+    let vbr_is_enabled = params
+        .iter()
+        .any(|param| param.id == AudioVbr::ID && AudioVbr::is_enabled(&param.data));
+    let result_is_package = PackageFormat::from_ext(&result_ext).is_some();
+    let renditions_are_enabled = result_is_package
+        && params
+            .iter()
+            .any(|param| param.id == Renditions::ID && Renditions::is_enabled(&param.data));
+    let split_by_chapters = params
+        .iter()
+        .any(|param| param.id == Split::ID && Split::by_chapters(&param.data));
+    // Images and timelapse always drop audio
+    let audio_is_disabled = audio_is_disabled
+        || result_is_image
+        || (!result_is_audio
+            && params
+                .iter()
+                .any(|param| param.id == Timelapse::ID && Timelapse::is_enabled(&param.data)));
 
     for param in params {
         if matches!(
             param.id,
             DisableAudio::ID
+                | Aspect::ID
                 | VideoScale::ID
+                | ScaleAlgorithm::ID
+                | FrameInterpolation::ID
+                | Timelapse::ID
                 | VideoBitrate::ID
                 | VideoFrameRate::ID
-                | HardwareAcceleration::ID
+                | Overlay::ID
+                | Text::ID
         ) {
             param.enabled = !result_is_audio;
         }
 
         if matches!(
             param.id,
-            AudioBitrate::ID | AudioCrystalizer::ID | AudioPitch::ID | AudioVolume::ID
+            AudioCodec::ID
+                | AudioChannels::ID
+                | AudioOffset::ID
+                | AudioSampleRate::ID
+                | AudioCrystalizer::ID
+                | AudioEq::ID
+                | AudioPitch::ID
+                | AudioVolume::ID
+                | PreservePitch::ID
+                | VoiceCleanup::ID
+                | RemoveSilence::ID
         ) {
             param.enabled = !audio_is_disabled;
         }
 
+        // Only meaningful for some output formats and codecs
+        if param.id == AudioCodec::ID {
+            AudioCodec::toggle_codecs(param, &result_ext);
+        }
+        if param.id == AudioBitrate::ID {
+            param.enabled = !audio_is_disabled
+                && !vbr_is_enabled
+                && !audio_codec.as_deref().is_some_and(AudioCodec::is_lossless);
+        }
+        if param.id == AudioVbr::ID {
+            param.enabled =
+                !audio_is_disabled && audio_codec.as_deref().is_some_and(AudioCodec::supports_vbr);
+        }
+        if param.id == Trim::ID || param.id == Loop::ID {
+            param.enabled = !split_by_chapters;
+        }
+        // Packages are segmented by the muxer, renditions scale and encode each stream
+        if param.id == Split::ID {
+            param.enabled = !result_is_package && !result_is_image;
+        }
+        // Still images have no audio, bitrate or tags
+        if param.id == DisableAudio::ID {
+            param.enabled = !result_is_audio && !result_is_image;
+        }
+        if param.id == Metadata::ID {
+            param.enabled = !result_is_image;
+        }
+        if param.id == Frames::ID {
+            param.enabled = result_is_image;
+        }
+        if param.id == ImageQuality::ID {
+            param.enabled = ImageQuality::is_supported(&result_ext);
+        }
+        if param.id == Renditions::ID {
+            param.enabled = result_is_package;
+        }
+        if param.id == VideoScale::ID || param.id == VideoBitrate::ID {
+            param.enabled = !result_is_audio && !renditions_are_enabled;
+        }
+        if param.id == VideoBitrate::ID && result_is_image {
+            param.enabled = false;
+        }
+        if param.id == HardwareAcceleration::ID {
+            param.enabled = !result_is_audio && HardwareAcceleration::is_supported(&result_ext);
+        }
+        if param.id == AudioBitDepth::ID {
+            param.enabled = !audio_is_disabled && AudioBitDepth::is_supported(&result_ext);
+        }
+        if param.id == FlacCompression::ID {
+            param.enabled = !audio_is_disabled && result_ext == "flac";
+        }
+
         if param.id == OutputFormat::ID {
             OutputFormat::toggle_audio_formats(param, !audio_is_disabled);
         }
@@ -158,17 +355,40 @@ pub(crate) fn apply_visitor(visitor: &mut dyn ParameterVisitor, params: &mut [Pa
     }
     for param in sorted_params {
         match param.id {
+            Loop::ID => visitor.visit_loop(&mut param.data),
             Trim::ID => visitor.visit_trim(&mut param.data),
             DisableAudio::ID => visitor.visit_disable_audio(&mut param.data),
             AudioVolume::ID => visitor.visit_audio_volume(&mut param.data),
+            AudioCodec::ID => visitor.visit_audio_codec(&mut param.data),
             AudioBitrate::ID => visitor.visit_audio_bitrate(&mut param.data),
+            AudioVbr::ID => visitor.visit_audio_vbr(&mut param.data),
+            AudioChannels::ID => visitor.visit_audio_channels(&mut param.data),
+            AudioOffset::ID => visitor.visit_audio_offset(&mut param.data),
+            AudioSampleRate::ID => visitor.visit_audio_sample_rate(&mut param.data),
+            AudioBitDepth::ID => visitor.visit_audio_bit_depth(&mut param.data),
+            FlacCompression::ID => visitor.visit_flac_compression(&mut param.data),
+            VoiceCleanup::ID => visitor.visit_voice_cleanup(&mut param.data),
             AudioCrystalizer::ID => visitor.visit_audio_crystalizer(&mut param.data),
+            AudioEq::ID => visitor.visit_audio_eq(&mut param.data),
             AudioPitch::ID => visitor.visit_audio_pitch(&mut param.data),
+            RemoveSilence::ID => visitor.visit_remove_silence(&mut param.data),
             SpeedFactor::ID => visitor.visit_speed_factor(&mut param.data),
+            PreservePitch::ID => visitor.visit_preserve_pitch(&mut param.data),
+            Timelapse::ID => visitor.visit_timelapse(&mut param.data),
             VideoBitrate::ID => visitor.visit_video_bitrate(&mut param.data),
             VideoFrameRate::ID => visitor.visit_video_frame_rate(&mut param.data),
+            Aspect::ID => visitor.visit_aspect(&mut param.data),
             VideoScale::ID => visitor.visit_video_scale(&mut param.data),
+            ScaleAlgorithm::ID => visitor.visit_scale_algorithm(&mut param.data),
+            FrameInterpolation::ID => visitor.visit_frame_interpolation(&mut param.data),
+            Overlay::ID => visitor.visit_overlay(&mut param.data),
+            Text::ID => visitor.visit_text(&mut param.data),
             HardwareAcceleration::ID => visitor.visit_hardware_acceleration(&mut param.data),
+            Split::ID => visitor.visit_split(&mut param.data),
+            Frames::ID => visitor.visit_frames(&mut param.data),
+            ImageQuality::ID => visitor.visit_image_quality(&mut param.data),
+            Renditions::ID => visitor.visit_renditions(&mut param.data),
+            Metadata::ID => visitor.visit_metadata(&mut param.data),
             OutputFormat::ID => visitor.visit_output_format(&mut param.data),
             _ => {}
         }
@@ -2,18 +2,17 @@ use tracing::debug;
 
 use crate::{
     info::Info,
-    params::{Parameter, ParameterData, PresetParameter, SelectOption},
+    params::{CONTAINERS, Container, Parameter, ParameterData, PresetParameter, SelectOption},
     visitors::{CommandBuilder, VisitorContext},
 };
 
+/// Output container, the list and the codec restrictions come from the container table
 pub(crate) struct OutputFormat;
 
 impl OutputFormat {
     pub(crate) const ID: &'static str = "output";
     pub(crate) const NAME: &'static str = "Output";
 
-    const AUDIO_TYPES: [&'static str; 4] = ["mp3", "wav", "flac", "ogg"];
-
     pub fn new_parameter(info: &Info, source_ext: &str) -> Parameter {
         Parameter::new(
             Self::ID,
@@ -24,22 +23,30 @@ impl OutputFormat {
     }
 
     pub(crate) fn is_audio(ext: &str) -> bool {
-        Self::AUDIO_TYPES.contains(&ext)
+        Container::find(ext).is_some_and(|c| c.audio_only)
+    }
+
+    pub(crate) fn is_image(ext: &str) -> bool {
+        Container::find(ext).is_some_and(|c| c.image)
     }
 
     fn get_parameter_data(info: &Info, ext: &str) -> ParameterData {
-        let mut selected_index = 0;
-        let mut options = Vec::new();
-        if info.has_video() {
-            options.push("mp4");
-        }
-        if info.has_audio() {
-            options.extend(Self::AUDIO_TYPES);
-        }
-        if !options.contains(&ext) {
-            selected_index = options.len();
+        let mut options: Vec<&str> = CONTAINERS
+            .iter()
+            .filter(|c| {
+                if c.audio_only {
+                    info.has_audio()
+                } else {
+                    info.has_video()
+                }
+            })
+            .map(|c| c.ext)
+            .collect();
+        // Keep the source format by default
+        let selected_index = options.iter().position(|o| *o == ext).unwrap_or_else(|| {
             options.push(ext);
-        }
+            options.len() - 1
+        });
         ParameterData::Select {
             options: options.into_iter().map(SelectOption::from).collect(),
             selected_index,
@@ -0,0 +1,116 @@
+use tracing::{debug, warn};
+
+use crate::{
+    model::{OverlayData, OverlayPosition},
+    params::{Parameter, ParameterData, PresetParameter},
+    visitors::{
+        CommandBuilder, ComplexFilter, ExtraInput, VisitorContext, escape_preset_value,
+        unescape_preset_value,
+    },
+};
+
+pub(crate) struct Overlay;
+
+impl Overlay {
+    pub(crate) const ID: &'static str = "overlay";
+    pub(crate) const NAME: &'static str = "Overlay";
+
+    pub fn new_parameter() -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Overlay(OverlayData::default()),
+        )
+        .with_order(1800)
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if let ParameterData::Overlay(overlay) = data
+            && let Some(path) = &overlay.path
+        {
+            debug!(?overlay, "build_command");
+            let input = cb.push_input(ExtraInput {
+                path: path.clone(),
+                ..Default::default()
+            });
+            let mut graph = Vec::new();
+            let mut label = format!("{input}:v");
+            let mut base = "{in}".to_owned();
+            // Scale the overlay relative to the main video height
+            if overlay.scale != 0 {
+                graph.push(format!(
+                    "[{label}][{base}]scale2ref=w=oh*mdar:h=ih*{}[ovs{input}][ovb{input}]",
+                    Self::fraction(overlay.scale)
+                ));
+                label = format!("ovs{input}");
+                base = format!("ovb{input}");
+            }
+            if overlay.opacity != 100 {
+                graph.push(format!(
+                    "[{label}]format=rgba,colorchannelmixer=aa={}[ova{input}]",
+                    Self::fraction(overlay.opacity)
+                ));
+                label = format!("ova{input}");
+            }
+            graph.push(format!(
+                "[{base}][{label}]overlay={}[{{out}}]",
+                overlay.position.overlay_expr(overlay.margin)
+            ));
+            cb.complex_video_filters.push(ComplexFilter {
+                position: cb.video_filters.len(),
+                graph: graph.join(";"),
+            });
+        }
+    }
+
+    fn fraction(percent: u32) -> String {
+        (f64::from(percent) / 100.0).to_string()
+    }
+}
+
+impl<'a> PresetParameter<'a> for Overlay {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        if let ParameterData::Overlay(overlay) = data {
+            // position,margin,scale,opacity,path with the separators escaped
+            let parts: Vec<&str> = preset_value.splitn(5, ',').collect();
+            if parts.len() != 5 {
+                warn!("Overlay preset is not valid and will be skipped");
+                return;
+            }
+            let Some(position) = OverlayPosition::from_id(parts[0]) else {
+                warn!("Unknown overlay position {}", parts[0]);
+                return;
+            };
+            let path = unescape_preset_value(parts[4]);
+            match OverlayData::validate(&path, parts[1], parts[2], parts[3]) {
+                Ok((margin, scale, opacity)) => {
+                    *overlay = OverlayData {
+                        path: Some(path).filter(|p| !p.is_empty()),
+                        position,
+                        margin,
+                        scale,
+                        opacity,
+                    };
+                }
+                Err(msg) => warn!("Overlay preset is not valid and will be skipped: {}", msg),
+            }
+        }
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        if let ParameterData::Overlay(overlay) = data
+            && let Some(path) = &overlay.path
+        {
+            Some(format!(
+                "{},{},{},{},{}",
+                overlay.position.id(),
+                overlay.margin,
+                overlay.scale,
+                overlay.opacity,
+                escape_preset_value(path)
+            ))
+        } else {
+            None
+        }
+    }
+}
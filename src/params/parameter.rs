@@ -2,7 +2,9 @@ use std::sync::{Arc, mpsc::Sender};
 
 use crate::{
     model::{
-        AppEvent, CustomSelectData, InputConstraints, TrimData, ValidationCallback, ValueFormatter,
+        AppEvent, AspectData, CustomSelectData, EqData, FramesData, InputConstraints, MetadataData,
+        OverlayData, SilenceData, SplitData, TextData, TrimData, ValidationCallback,
+        ValueFormatter,
     },
     visitors::VisitorContext,
 };
@@ -39,6 +41,15 @@ impl SelectOption {
         values.iter().map(|&v| v.into()).collect()
     }
 
+    /// Options from the slice, except those rejected by the filter
+    pub(crate) fn from_slice_filtered(values: &[&str], filter: impl Fn(&str) -> bool) -> Vec<Self> {
+        values
+            .iter()
+            .filter(|v| filter(v))
+            .map(|&v| v.into())
+            .collect()
+    }
+
     pub(crate) fn from_pairs(names: &[(&str, &str)]) -> Vec<Self> {
         names.iter().map(|&v| v.into()).collect()
     }
@@ -61,6 +72,14 @@ pub(crate) enum ParameterData {
         value: bool,
     },
     Trim(TrimData),
+    Overlay(OverlayData),
+    Text(TextData),
+    Aspect(AspectData),
+    Eq(EqData),
+    Silence(SilenceData),
+    Split(SplitData),
+    Frames(FramesData),
+    Metadata(MetadataData),
 }
 
 pub(crate) struct Parameter {
@@ -131,7 +150,17 @@ impl Parameter {
                     value.clone_from(&options[*selected_index].value);
                 }
             }
-            ParameterData::Trim(_) => self.open_modal(event_sender),
+            ParameterData::Trim(_)
+            | ParameterData::Overlay(_)
+            | ParameterData::Text(_)
+            | ParameterData::Aspect(_)
+            | ParameterData::Eq(_)
+            | ParameterData::Silence(_)
+            | ParameterData::Split(_)
+            | ParameterData::Frames(_)
+            | ParameterData::Metadata(_) => {
+                self.open_modal(event_sender);
+            }
         }
     }
 
@@ -179,7 +208,17 @@ impl Parameter {
                     value.clone_from(&options[*selected_index].value);
                 }
             }
-            ParameterData::Trim(_) => self.open_modal(event_sender),
+            ParameterData::Trim(_)
+            | ParameterData::Overlay(_)
+            | ParameterData::Text(_)
+            | ParameterData::Aspect(_)
+            | ParameterData::Eq(_)
+            | ParameterData::Silence(_)
+            | ParameterData::Split(_)
+            | ParameterData::Frames(_)
+            | ParameterData::Metadata(_) => {
+                self.open_modal(event_sender);
+            }
         }
     }
 
@@ -203,6 +242,14 @@ impl Parameter {
                 }
             }
             ParameterData::Trim(data) => data.to_string(),
+            ParameterData::Overlay(data) => data.to_string(),
+            ParameterData::Text(data) => data.to_string(),
+            ParameterData::Aspect(data) => data.to_string(),
+            ParameterData::Eq(data) => data.to_string(),
+            ParameterData::Silence(data) => data.to_string(),
+            ParameterData::Split(data) => data.to_string(),
+            ParameterData::Frames(data) => data.to_string(),
+            ParameterData::Metadata(data) => data.to_string(),
         }
     }
 
@@ -231,6 +278,30 @@ impl Parameter {
             ParameterData::Trim(data) => {
                 let _ = event_sender.send(AppEvent::OpenTrimModal(data.clone()));
             }
+            ParameterData::Overlay(data) => {
+                let _ = event_sender.send(AppEvent::OpenOverlayModal(data.clone()));
+            }
+            ParameterData::Text(data) => {
+                let _ = event_sender.send(AppEvent::OpenTextModal(data.clone()));
+            }
+            ParameterData::Aspect(data) => {
+                let _ = event_sender.send(AppEvent::OpenAspectModal(data.clone()));
+            }
+            ParameterData::Eq(data) => {
+                let _ = event_sender.send(AppEvent::OpenEqModal(*data));
+            }
+            ParameterData::Silence(data) => {
+                let _ = event_sender.send(AppEvent::OpenSilenceModal(data.clone()));
+            }
+            ParameterData::Split(data) => {
+                let _ = event_sender.send(AppEvent::OpenSplitModal(data.clone()));
+            }
+            ParameterData::Frames(data) => {
+                let _ = event_sender.send(AppEvent::OpenFramesModal(data.clone()));
+            }
+            ParameterData::Metadata(data) => {
+                let _ = event_sender.send(AppEvent::OpenMetadataModal(data.clone()));
+            }
             _ => {}
         }
     }
@@ -252,7 +323,16 @@ impl ParameterData {
     pub(crate) fn is_editable(&self) -> bool {
         matches!(
             self,
-            ParameterData::CustomSelect { .. } | ParameterData::Trim { .. }
+            ParameterData::CustomSelect { .. }
+                | ParameterData::Trim { .. }
+                | ParameterData::Overlay { .. }
+                | ParameterData::Text { .. }
+                | ParameterData::Aspect { .. }
+                | ParameterData::Eq { .. }
+                | ParameterData::Silence { .. }
+                | ParameterData::Split { .. }
+                | ParameterData::Frames { .. }
+                | ParameterData::Metadata { .. }
         )
     }
 }
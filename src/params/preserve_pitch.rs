@@ -0,0 +1,43 @@
+use tracing::debug;
+
+use crate::{
+    params::{Parameter, ParameterData, PresetParameter},
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+/// Keep the audio pitch when the speed changes, should be applied before the speed parameter
+pub(crate) struct PreservePitch;
+
+impl PreservePitch {
+    pub(crate) const ID: &'static str = "keeppitch";
+    pub(crate) const NAME: &'static str = "Preserve Pitch";
+
+    pub fn new_parameter() -> Parameter {
+        Parameter::new(Self::ID, Self::NAME, ParameterData::Toggle { value: true }).with_order(990)
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if let ParameterData::Toggle { value } = data
+            && !*value
+        {
+            debug!("build_command change pitch with speed");
+            cb.change_pitch = true;
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for PreservePitch {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        Self::set_parameter_value(data, preset_value);
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        if let ParameterData::Toggle { value } = data
+            && !*value
+        {
+            Some("0".to_owned())
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use tracing::{debug, warn};
+
+use crate::{
+    info::Info,
+    model::{SilenceData, SilenceScope},
+    params::{OutputFormat, Parameter, ParameterData, PresetParameter},
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+/// Audio-only outputs use `silenceremove`. Video is cut by the ranges found by `silencedetect`,
+/// should be applied after trim to know the input seek, filters are inserted first.
+/// A precise trim is done by the same selection, the output seek would miss the removed parts
+pub(crate) struct RemoveSilence;
+
+impl RemoveSilence {
+    pub(crate) const ID: &'static str = "silence";
+    pub(crate) const NAME: &'static str = "Remove Silence";
+
+    pub fn new_parameter(info: &Info) -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Silence(SilenceData {
+                video: info.has_video(),
+                ..Default::default()
+            }),
+        )
+        .with_order(1700)
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if cb.discard_audio {
+            return;
+        }
+        let ParameterData::Silence(silence) = data else {
+            return;
+        };
+        if silence.is_off() {
+            return;
+        }
+        debug!(?silence, "build_command");
+        if !silence.video || OutputFormat::is_audio(&cb.ext) {
+            cb.audio_filters.insert(0, Self::silenceremove(silence));
+            cb.timeline_changed = true;
+            return;
+        }
+        if silence.detected_ranges().is_none() {
+            warn!("Silence is not detected yet");
+            cb.warnings
+                .push("Silence is not analyzed yet, it will be kept".to_owned());
+            return;
+        }
+        // Timestamps start from zero after the input seek
+        let seek = cb.input_seek.unwrap_or(0.0);
+        let output_trim = cb
+            .trim
+            .as_ref()
+            .filter(|trim| trim.output_args.is_some())
+            .map(|trim| (trim.start, trim.end));
+        let (trim_start, trim_end) = output_trim.unwrap_or((0.0, None));
+        let ranges: Vec<String> = silence
+            .cut_ranges(cb.ctx.input_duration)
+            .into_iter()
+            .filter(|(start, end)| {
+                *end > seek.max(trim_start) && trim_end.is_none_or(|e| *start < e)
+            })
+            .map(|(start, end)| {
+                format!(
+                    "between(t,{},{})",
+                    Self::format_time((start - seek).max(0.0)),
+                    Self::format_time(end - seek)
+                )
+            })
+            .collect();
+        if ranges.is_empty() {
+            return;
+        }
+        cb.timeline_changed = true;
+        let mut keep = format!("not({})", ranges.join("+"));
+        if output_trim.is_some() {
+            let range = match trim_end {
+                Some(end) => format!(
+                    "between(t,{},{})",
+                    Self::format_time(trim_start),
+                    Self::format_time(end)
+                ),
+                None => format!("gte(t,{})", Self::format_time(trim_start)),
+            };
+            keep = format!("{range}*{keep}");
+            if let Some(args) = cb.trim.as_mut().and_then(|trim| trim.output_args.take()) {
+                cb.args.drain(args);
+            }
+        }
+        cb.video_filters.splice(
+            0..0,
+            [
+                format!("select='{keep}'"),
+                "setpts=N/FRAME_RATE/TB".to_owned(),
+            ],
+        );
+        for filter in &mut cb.complex_video_filters {
+            filter.position += 2;
+        }
+        cb.audio_filters.splice(
+            0..0,
+            [format!("aselect='{keep}'"), "asetpts=N/SR/TB".to_owned()],
+        );
+    }
+
+    fn silenceremove(silence: &SilenceData) -> String {
+        let (threshold, duration) = (silence.threshold, silence.duration);
+        let start = format!(
+            "silenceremove=start_periods=1:start_duration={duration}:start_threshold={threshold}dB"
+        );
+        match silence.scope {
+            // Trailing silence is removed from the reversed audio
+            SilenceScope::Edges => format!("{start},areverse,{start},areverse"),
+            _ => format!(
+                "{start}:stop_periods=-1:stop_duration={duration}:stop_threshold={threshold}dB"
+            ),
+        }
+    }
+
+    fn format_time(seconds: f64) -> String {
+        format!("{seconds:.3}")
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_owned()
+    }
+}
+
+impl<'a> PresetParameter<'a> for RemoveSilence {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        if let ParameterData::Silence(silence) = data {
+            // scope,threshold,duration
+            let parts: Vec<&str> = preset_value.split(',').collect();
+            let [scope, threshold, duration] = parts[..] else {
+                warn!("Remove silence preset is not valid and will be skipped");
+                return;
+            };
+            match (
+                SilenceScope::from_id(scope),
+                threshold.parse::<i32>(),
+                duration.parse::<f64>(),
+            ) {
+                (Some(scope), Ok(threshold), Ok(duration))
+                    if SilenceData::THRESHOLDS.contains(&threshold)
+                        && SilenceData::DURATIONS.contains(&duration) =>
+                {
+                    silence.scope = scope;
+                    silence.threshold = threshold;
+                    silence.duration = duration;
+                }
+                _ => warn!("Unknown remove silence preset {}", preset_value),
+            }
+        }
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        match data {
+            ParameterData::Silence(silence) if !silence.is_off() => Some(format!(
+                "{},{},{}",
+                silence.scope.id(),
+                silence.threshold,
+                silence.duration
+            )),
+            _ => None,
+        }
+    }
+}
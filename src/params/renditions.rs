@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use tracing::{debug, warn};
+
+use crate::{
+    info::Info,
+    model::{InputConstraints, InputType, PackageFormat, Rendition, ScaleSize, ScaleValue},
+    params::{
+        Parameter, ParameterData, PresetParameter, SelectOption, VideoBitrate, VideoScale,
+        macros::select_non_default_custom_value,
+    },
+    visitors::{CommandBuilder, RenditionOutput, VisitorContext},
+};
+
+/// Several video streams of an HLS or DASH package, e.g. `360p@800k,720p`.
+/// Each one is scaled and encoded like the Video Scale and Video Bitrate parameters do,
+/// should be applied after the other video filters
+pub(crate) struct Renditions;
+
+impl Renditions {
+    pub(crate) const ID: &'static str = "renditions";
+    pub(crate) const NAME: &'static str = "Renditions";
+    const DEFAULT: &'static str = "0";
+    const VARIANTS: [&str; 6] = [
+        "0",
+        "360p,720p",
+        "360p,480p,720p",
+        "360p,720p,1080p",
+        "240p,480p,720p,1080p",
+        "720p,1080p,2160p",
+    ];
+    const MAX_COUNT: usize = 6;
+
+    pub fn new_parameter(info: &Info) -> Parameter {
+        let source = info.video_size().map(|(_, h)| h);
+        // Hide sets that would upscale the source
+        let options = SelectOption::from_slice_filtered(&Self::VARIANTS, |v| {
+            v == Self::DEFAULT
+                || source.is_none_or(|h| Self::parse(v).is_some_and(|r| Self::max_height(&r) <= h))
+        });
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::CustomSelect {
+                options,
+                selected_index: 0,
+                value: Self::DEFAULT.to_owned(),
+                constraints: InputConstraints {
+                    length: 60,
+                    input_type: InputType::RenditionList,
+                },
+                validator: Arc::new(Self::validate),
+                formatter: Some(Arc::new(move |value| Self::format_value(value, source))),
+            },
+        )
+        .with_order(4200)
+    }
+
+    /// Renditions replace the video scale and bitrate of HLS and DASH outputs
+    pub(crate) fn is_enabled(data: &ParameterData) -> bool {
+        select_non_default_custom_value!(data).is_some()
+    }
+
+    fn parse(value: &str) -> Option<Vec<Rendition>> {
+        // Auto bitrate is not allowed, the renditions need distinct ones
+        Rendition::parse_list(value, |bitrate| {
+            VideoBitrate::validate(bitrate).ok().filter(|b| b != "0")
+        })
+        .filter(|list| (1..=Self::MAX_COUNT).contains(&list.len()))
+    }
+
+    fn max_height(renditions: &[Rendition]) -> u32 {
+        renditions.iter().map(|r| r.height).max().unwrap_or(0)
+    }
+
+    fn validate(value: &str) -> Result<String, &str> {
+        if value == Self::DEFAULT {
+            return Ok(value.to_owned());
+        }
+        match Self::parse(value) {
+            Some(renditions) => Ok(renditions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")),
+            None => Err(
+                "Invalid value. Expected up to 6 even heights with optional bitrates \
+                 like 360p@800k,720p@2800k,1080p, or 0 - single stream",
+            ),
+        }
+    }
+
+    fn format_value(value: &str, source: Option<u32>) -> String {
+        let Some(renditions) = Self::parse(value) else {
+            return "single".to_owned();
+        };
+        let mut result = renditions
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        if source.is_some_and(|h| Self::max_height(&renditions) > h) {
+            result.push_str(" (upscale)");
+        }
+        result
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if PackageFormat::from_ext(&cb.ext).is_none() {
+            return;
+        }
+        let Some(value) = select_non_default_custom_value!(data) else {
+            return;
+        };
+        let Some(renditions) = Self::parse(value) else {
+            warn!("Renditions {} are not valid and will be skipped", value);
+            return;
+        };
+        debug!(?renditions, "build_command");
+        // The scale runs after the split, the other filters run before it
+        let first = cb.video_filters.is_empty() && cb.complex_video_filters.is_empty();
+        for (i, rendition) in renditions.iter().enumerate() {
+            let scale = ScaleValue {
+                size: ScaleSize::Height(rendition.height),
+                no_upscale: false,
+            };
+            cb.renditions.push(RenditionOutput {
+                filter: VideoScale::scale_filter(cb, &scale, first),
+                args: VideoBitrate::args(&rendition.bitrate, Some(i)).to_vec(),
+            });
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for Renditions {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        if let Ok(value) = Self::validate(preset_value) {
+            Self::set_parameter_value(data, &value);
+        }
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_custom_value!(data).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(
+            Renditions::validate("360,720@3M,1080p").as_deref(),
+            Ok("360p@800k,720p@3M,1080p@5M")
+        );
+        assert_eq!(Renditions::validate("0").as_deref(), Ok("0"));
+        for value in [
+            "720P",
+            "361p",
+            "720p@",
+            "720p@1k",
+            "p",
+            "",
+            "240,360,480,720,1080,1440,2160",
+        ] {
+            assert!(Renditions::validate(value).is_err(), "{value}");
+        }
+    }
+}
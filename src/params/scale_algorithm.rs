@@ -0,0 +1,48 @@
+use tracing::debug;
+
+use crate::{
+    params::{
+        Parameter, ParameterData, PresetParameter, SelectOption, macros::select_non_default_option,
+    },
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+/// Scaler algorithm for the software scale filters, should be applied before them
+pub(crate) struct ScaleAlgorithm;
+
+impl ScaleAlgorithm {
+    pub(crate) const ID: &'static str = "scaler";
+    pub(crate) const NAME: &'static str = "Scale Algorithm";
+    const DEFAULT: &'static str = "auto";
+
+    pub fn new_parameter() -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Select {
+                options: SelectOption::from_slice(&[
+                    "auto", "bilinear", "bicubic", "lanczos", "spline", "neighbor",
+                ]),
+                selected_index: 0,
+            },
+        )
+        .with_order(990)
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if let Some(option) = select_non_default_option!(data) {
+            debug!(value = option.value, "build_command");
+            cb.scale_flags = Some(option.value.clone());
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for ScaleAlgorithm {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        Self::set_parameter_value(data, preset_value);
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_option!(data).map(|option| option.value.clone())
+    }
+}
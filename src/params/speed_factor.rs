@@ -17,8 +17,9 @@ impl SpeedFactor {
     pub(crate) const ID: &'static str = "speed";
     pub(crate) const NAME: &'static str = "Speed";
     const DEFAULT: &'static str = "1";
-    const VARIANTS: [&str; 13] = [
-        "0.5", "0.75", "0.8", "0.9", "1", "1.25", "1.4", "1.5", "1.6", "1.8", "2", "2.5", "3",
+    const VARIANTS: [&str; 14] = [
+        "0.25", "0.5", "0.75", "0.8", "0.9", "1", "1.25", "1.4", "1.5", "1.6", "1.8", "2", "2.5",
+        "3",
     ];
 
     pub fn new_parameter() -> Parameter {
@@ -27,7 +28,7 @@ impl SpeedFactor {
             Self::NAME,
             ParameterData::CustomSelect {
                 options: SelectOption::from_slice(&Self::VARIANTS),
-                selected_index: 4,
+                selected_index: 5,
                 value: Self::DEFAULT.to_owned(),
                 constraints: InputConstraints {
                     length: 4,
@@ -41,14 +42,34 @@ impl SpeedFactor {
 
     fn validate(value: &str) -> Result<String, &str> {
         if let Ok(num) = value.parse::<f64>()
-            && (0.5..=100.0).contains(&num)
+            && (0.1..=100.0).contains(&num)
         {
             Ok(num.to_string())
         } else {
-            Err("Invalid value. Expected a number in range 0.5..100")
+            Err("Invalid value. Expected a number in range 0.1..100")
         }
     }
 
+    /// Chain of atempo filters, each one is kept within 0.5..2 for quality and compatibility
+    pub(crate) fn atempo_chain(factor: f64) -> Vec<String> {
+        let mut chain = Vec::new();
+        let mut rest = factor;
+        while rest > 2.0 {
+            chain.push("atempo=2".to_owned());
+            rest /= 2.0;
+        }
+        while rest < 0.5 {
+            chain.push("atempo=0.5".to_owned());
+            rest /= 0.5;
+        }
+        if chain.is_empty() || (rest - 1.0).abs() > 1e-6 {
+            let rest = format!("{rest:.6}");
+            let rest = rest.trim_end_matches('0').trim_end_matches('.');
+            chain.push(format!("atempo={rest}"));
+        }
+        chain
+    }
+
     pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
         if let Some(value) = select_non_default_custom_value!(data) {
             cb.speed_factor = value.parse().ok();
@@ -62,8 +83,25 @@ impl SpeedFactor {
                     "build_command"
                 );
             }
-            if !cb.discard_audio {
-                cb.audio_filters.push(format!("atempo={}", &value));
+            if !cb.discard_audio
+                && let Some(speed) = cb.speed_factor
+            {
+                match cb.ctx.sample_rate {
+                    // Resample like a tape, the pitch changes along with the speed
+                    Some(sample_rate) if cb.change_pitch => {
+                        cb.audio_filters
+                            .push(format!("asetrate={sample_rate}*{value}"));
+                        cb.audio_filters.push(format!("aresample={sample_rate}"));
+                    }
+                    _ => {
+                        if cb.change_pitch {
+                            cb.warnings.push(
+                                "Unknown audio sample rate, the pitch is preserved".to_owned(),
+                            );
+                        }
+                        cb.audio_filters.extend(Self::atempo_chain(speed));
+                    }
+                }
             }
             cb.video_filters.push(format!("setpts=PTS/{}", &value));
         }
@@ -0,0 +1,242 @@
+use tracing::{debug, warn};
+
+use crate::{
+    info::Info,
+    model::{Bitrate, SplitData, SplitMode, set_allowed},
+    params::{OutputFormat, Parameter, ParameterData, PresetParameter},
+    visitors::{CommandBuilder, OutputPart, VisitorContext},
+};
+
+/// Write several output files instead of one.
+/// Should be applied after the speed factor and bitrates, the part times are in the output timeline
+pub(crate) struct Split;
+
+impl Split {
+    pub(crate) const ID: &'static str = "split";
+    pub(crate) const NAME: &'static str = "Split";
+
+    pub fn new_parameter(info: &Info) -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Split(SplitData {
+                chapters: info.chapters.len(),
+                video: info.has_video(),
+                ..Default::default()
+            }),
+        )
+        .with_order(4100)
+    }
+
+    /// The whole input is split by chapters, so trimming and looping don't apply
+    pub(crate) fn by_chapters(data: &ParameterData) -> bool {
+        matches!(data, ParameterData::Split(split) if split.mode == SplitMode::Chapters)
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        let ParameterData::Split(split) = data else {
+            return;
+        };
+        if split.is_off() {
+            return;
+        }
+        debug!(?split, "build_command");
+        let speed = cb.speed_factor.unwrap_or(1.0);
+        match split.mode {
+            SplitMode::Off => {}
+            SplitMode::Chapters => Self::chapters(cb, speed),
+            SplitMode::Duration => Self::every(cb, f64::from(split.duration)),
+            SplitMode::Size => {
+                let Some(bitrate) = Self::estimate_bitrate(cb) else {
+                    cb.warnings
+                        .push("Bitrate is unknown, the output is not split".to_owned());
+                    return;
+                };
+                // Leave some room for the container overhead
+                let bits = f64::from(split.size) * 8_000_000.0 * 0.95;
+                Self::every(cb, (bits / bitrate as f64).floor().max(1.0));
+            }
+            SplitMode::Scene => {
+                let Some(scenes) = split.detected_scenes() else {
+                    warn!("Scene changes are not detected yet");
+                    cb.warnings.push(
+                        "Scene changes are not analyzed yet, the output is not split".to_owned(),
+                    );
+                    return;
+                };
+                if cb.timeline_changed {
+                    warn!("Scene split is skipped, the timeline is changed");
+                    cb.warnings.push(
+                        "Scene changes don't match the output with timelapse or silence removal, \
+                         the output is not split"
+                            .to_owned(),
+                    );
+                    return;
+                }
+                // Timestamps start from zero at the trim start
+                let (start, end) = cb.source_range();
+                let times: Vec<String> = scenes
+                    .iter()
+                    .filter(|time| **time > start && end.is_none_or(|end| **time < end))
+                    .map(|time| format!("{:.3}", (time - start) / speed))
+                    .collect();
+                if times.is_empty() {
+                    cb.warnings
+                        .push("No scene changes found, the output is not split".to_owned());
+                    return;
+                }
+                let times_list = times.join(",");
+                Self::segment(
+                    cb,
+                    "-segment_times",
+                    &times_list,
+                    &times_list,
+                    times.len() + 1,
+                );
+            }
+        }
+    }
+
+    fn chapters(cb: &mut CommandBuilder, speed: f64) {
+        debug!(chapters = cb.ctx.chapters.len(), "split by chapters");
+        let chapters = cb.ctx.chapters.clone();
+        for (i, chapter) in chapters.iter().enumerate() {
+            cb.output_parts.push(OutputPart {
+                args: vec![
+                    "-ss".to_owned(),
+                    format!("{:.3}", chapter.start() / speed),
+                    "-to".to_owned(),
+                    format!("{:.3}", chapter.end() / speed),
+                ],
+                suffix: format!("_{:02}_{}", i + 1, Self::file_safe(&chapter.title(i))),
+            });
+        }
+    }
+
+    /// Segments of the same length in seconds
+    fn every(cb: &mut CommandBuilder, seconds: f64) {
+        let speed = cb.speed_factor.unwrap_or(1.0);
+        let count = cb.ctx.input_duration.map_or(1, |duration| {
+            (duration / speed / seconds).ceil().max(1.0) as usize
+        });
+        let time = seconds.to_string();
+        let key_frames = format!("expr:gte(t,n_forced*{time})");
+        Self::segment(cb, "-segment_time", &time, &key_frames, count);
+    }
+
+    /// Segment muxer writes numbered files, key frames are forced at the cut points
+    fn segment(cb: &mut CommandBuilder, option: &str, times: &str, key_frames: &str, count: usize) {
+        if !OutputFormat::is_audio(&cb.ext) {
+            cb.pre_output_args.push("-force_key_frames".to_owned());
+            cb.pre_output_args.push(key_frames.to_owned());
+        }
+        cb.pre_output_args.extend(
+            [
+                "-f",
+                "segment",
+                option,
+                times,
+                "-segment_start_number",
+                "1",
+                "-reset_timestamps",
+                "1",
+            ]
+            .map(ToOwned::to_owned),
+        );
+        cb.segments = Some(count);
+    }
+
+    /// Output bits per second from the bitrate options, the source bitrates otherwise
+    fn estimate_bitrate(cb: &mut CommandBuilder) -> Option<u64> {
+        let option = |name: &str| {
+            cb.pre_output_args
+                .iter()
+                .position(|arg| arg == name)
+                .and_then(|i| cb.pre_output_args.get(i + 1))
+                .and_then(|value| value.parse::<Bitrate>().ok())
+                .map(|bitrate| bitrate.bps())
+        };
+        let video = if OutputFormat::is_audio(&cb.ext) {
+            Some(0)
+        } else {
+            option("-b:v").or_else(|| {
+                cb.warnings.push(
+                    "Video bitrate is not set, the part size is estimated from the source"
+                        .to_owned(),
+                );
+                cb.ctx.video_bitrate
+            })
+        };
+        let audio = if cb.discard_audio {
+            Some(0)
+        } else {
+            option("-b:a").or(cb.ctx.audio_bitrate)
+        };
+        let bitrate = video.unwrap_or(0) + audio.unwrap_or(0);
+        (video.is_some() && bitrate > 0).then_some(bitrate)
+    }
+
+    /// Replace the characters not allowed in filenames on any platform
+    fn file_safe(title: &str) -> String {
+        title
+            .chars()
+            .map(|c| {
+                if c.is_control()
+                    || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+                {
+                    '_'
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+}
+
+impl<'a> PresetParameter<'a> for Split {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        if let ParameterData::Split(split) = data {
+            // mode[,value]
+            let (mode, value) = preset_value
+                .split_once(',')
+                .map_or((preset_value, None), |(mode, value)| (mode, Some(value)));
+            let Some(mode) = SplitMode::from_id(mode).filter(|m| split.modes().contains(m)) else {
+                warn!("Unknown split preset {}", preset_value);
+                return;
+            };
+            let valid = match (mode, value) {
+                (SplitMode::Off | SplitMode::Chapters, None) => true,
+                (SplitMode::Duration, Some(value)) => {
+                    set_allowed(&SplitData::DURATIONS, value, &mut split.duration)
+                }
+                (SplitMode::Size, Some(value)) => {
+                    set_allowed(&SplitData::SIZES, value, &mut split.size)
+                }
+                (SplitMode::Scene, Some(value)) => {
+                    set_allowed(&SplitData::SCENES, value, &mut split.scene)
+                }
+                _ => false,
+            };
+            if valid {
+                split.mode = mode;
+            } else {
+                warn!("Split preset is not valid and will be skipped");
+            }
+        }
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        match data {
+            ParameterData::Split(split) if !split.is_off() => {
+                let mode = split.mode.id();
+                Some(match split.mode {
+                    SplitMode::Duration => format!("{mode},{}", split.duration),
+                    SplitMode::Size => format!("{mode},{}", split.size),
+                    SplitMode::Scene => format!("{mode},{}", split.scene),
+                    SplitMode::Off | SplitMode::Chapters => mode.to_owned(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use tracing::{debug, warn};
+
+use crate::{
+    model::{OverlayPosition, TextData},
+    params::{Parameter, ParameterData, PresetParameter, Trim},
+    visitors::{
+        CommandBuilder, VisitorContext, escape_filter_value, escape_preset_value,
+        unescape_preset_value,
+    },
+};
+
+pub(crate) struct Text;
+
+impl Text {
+    pub(crate) const ID: &'static str = "text";
+    pub(crate) const NAME: &'static str = "Text";
+    const MARGIN: u32 = 16;
+    const TIMECODE: &'static str = "%{pts:hms}";
+
+    pub fn new_parameter() -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::Text(TextData::default()),
+        )
+        .with_order(1900)
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if let ParameterData::Text(text) = data
+            && !text.is_empty()
+        {
+            debug!(?text, "build_command");
+            let mut content = Self::escape_text(&text.text);
+            if text.timecode {
+                if !content.is_empty() {
+                    content.push(' ');
+                }
+                // Show the source time if the input was seeked
+                match cb.input_seek {
+                    Some(offset) => content.push_str(&format!("%{{pts:hms:{offset}}}")),
+                    None => content.push_str(Self::TIMECODE),
+                }
+            }
+            let mut options = vec![format!("text={}", escape_filter_value(&content))];
+            if let Some(font) = &text.font {
+                options.push(format!("fontfile={}", escape_filter_value(font)));
+            }
+            options.push(format!("fontsize={}", text.size));
+            options.push(format!("fontcolor={}", text.color));
+            if text.boxed {
+                options.push("box=1".to_owned());
+                options.push("boxcolor=black@0.5".to_owned());
+                options.push("boxborderw=8".to_owned());
+            }
+            options.push(text.position.drawtext_expr(Self::MARGIN));
+            let start = text.start.as_deref().map(Trim::to_seconds);
+            let end = text.end.as_deref().map(Trim::to_seconds);
+            let enable = match (start, end) {
+                (Some(start), Some(end)) => Some(format!("between(t,{start},{end})")),
+                (Some(start), None) => Some(format!("gte(t,{start})")),
+                (None, Some(end)) => Some(format!("lte(t,{end})")),
+                (None, None) => None,
+            };
+            if let Some(enable) = enable {
+                options.push(format!("enable={}", escape_filter_value(&enable)));
+            }
+            cb.video_filters
+                .push(format!("drawtext={}", options.join(":")));
+        }
+    }
+
+    /// Escape drawtext text expansion characters
+    pub(crate) fn escape_text(text: &str) -> String {
+        text.replace('\\', "\\\\").replace('%', "\\%")
+    }
+}
+
+impl<'a> PresetParameter<'a> for Text {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        if let ParameterData::Text(text) = data {
+            // position,size,color,flags,start,end,font,text with the separators escaped
+            let parts: Vec<&str> = preset_value.splitn(8, ',').collect();
+            if parts.len() != 8 {
+                warn!("Text preset is not valid and will be skipped");
+                return;
+            }
+            let Some(position) = OverlayPosition::from_id(parts[0]) else {
+                warn!("Unknown text position {}", parts[0]);
+                return;
+            };
+            let font = unescape_preset_value(parts[6]);
+            match TextData::validate(&font, parts[1], parts[2], parts[4], parts[5]) {
+                Ok(size) => {
+                    let non_empty = |v: &str| Some(v.to_owned()).filter(|v| !v.is_empty());
+                    *text = TextData {
+                        text: unescape_preset_value(parts[7]),
+                        timecode: parts[3].contains('t'),
+                        font: non_empty(&font),
+                        size,
+                        color: parts[2].to_owned(),
+                        boxed: parts[3].contains('b'),
+                        position,
+                        start: non_empty(parts[4]),
+                        end: non_empty(parts[5]),
+                    };
+                }
+                Err(msg) => warn!("Text preset is not valid and will be skipped: {}", msg),
+            }
+        }
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        if let ParameterData::Text(text) = data
+            && !text.is_empty()
+        {
+            Some(format!(
+                "{},{},{},{}{},{},{},{},{}",
+                text.position.id(),
+                text.size,
+                text.color,
+                if text.boxed { "b" } else { "" },
+                if text.timecode { "t" } else { "" },
+                text.start.as_deref().unwrap_or_default(),
+                text.end.as_deref().unwrap_or_default(),
+                escape_preset_value(text.font.as_deref().unwrap_or_default()),
+                escape_preset_value(&text.text)
+            ))
+        } else {
+            None
+        }
+    }
+}
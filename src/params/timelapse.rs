@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use tracing::debug;
+
+use crate::{
+    info::Info,
+    model::{InputConstraints, InputType},
+    params::{
+        Parameter, ParameterData, PresetParameter, SelectOption,
+        macros::select_non_default_custom_value,
+    },
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+/// Keep every Nth frame (`10`) or one frame every T seconds (`2s`), audio is dropped
+pub(crate) struct Timelapse;
+
+impl Timelapse {
+    pub(crate) const ID: &'static str = "timelapse";
+    pub(crate) const NAME: &'static str = "Timelapse";
+    const DEFAULT: &'static str = "0";
+    const VARIANTS: [&str; 9] = ["0", "5", "10", "30", "60", "1s", "2s", "5s", "10s"];
+
+    pub fn new_parameter(info: &Info) -> Parameter {
+        let duration = info.get_duration();
+        let fps = info.frame_rate();
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::CustomSelect {
+                options: SelectOption::from_slice(&Self::VARIANTS),
+                selected_index: 0,
+                value: Self::DEFAULT.to_owned(),
+                constraints: InputConstraints {
+                    length: 6,
+                    input_type: InputType::Interval,
+                },
+                validator: Arc::new(Self::validate),
+                formatter: Some(Arc::new(move |value| {
+                    Self::format_value(value, duration, fps)
+                })),
+            },
+        )
+    }
+
+    pub(crate) fn is_enabled(data: &ParameterData) -> bool {
+        matches!(data, ParameterData::CustomSelect { value, .. } if value != Self::DEFAULT)
+    }
+
+    fn validate(value: &str) -> Result<String, &str> {
+        if value == Self::DEFAULT {
+            return Ok(value.to_owned());
+        }
+        if let Some(seconds) = value.strip_suffix('s') {
+            if let Ok(num) = seconds.parse::<f64>()
+                && (0.1..=3600.0).contains(&num)
+            {
+                Ok(format!("{num}s"))
+            } else {
+                Err("Invalid interval. Expected seconds in range 0.1s..3600s")
+            }
+        } else if let Ok(num) = value.parse::<u32>()
+            && (2..=10000).contains(&num)
+        {
+            Ok(num.to_string())
+        } else {
+            Err("Invalid value. Expected every Nth frame 2..10000, interval like 2s, or 0 - off")
+        }
+    }
+
+    /// How many times the video becomes shorter
+    fn factor(value: &str, fps: Option<f64>) -> Option<f64> {
+        match value.strip_suffix('s') {
+            Some(seconds) => Some(seconds.parse::<f64>().ok()? * fps?),
+            None => value.parse::<f64>().ok(),
+        }
+    }
+
+    /// Describe the value with the output duration estimate, e.g. "1 of 10 frames → 1m 30s"
+    fn format_value(value: &str, duration: Option<f64>, fps: Option<f64>) -> String {
+        if value == Self::DEFAULT {
+            return "off".to_owned();
+        }
+        let mut result = match value.strip_suffix('s') {
+            Some(seconds) => format!("every {seconds}s"),
+            None => format!("1 of {value} frames"),
+        };
+        if let (Some(duration), Some(factor)) = (duration, Self::factor(value, fps)) {
+            result.push_str(&format!(
+                " \u{2192} {}",
+                Self::format_duration(duration / factor)
+            ));
+        }
+        result
+    }
+
+    fn format_duration(seconds: f64) -> String {
+        let total = seconds.round() as u64;
+        match total {
+            0 => format!("{seconds:.1}s"),
+            1..60 => format!("{total}s"),
+            60..3600 => format!("{}m {}s", total / 60, total % 60),
+            _ => format!("{}h {}m", total / 3600, total % 3600 / 60),
+        }
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if let Some(value) = select_non_default_custom_value!(data) {
+            debug!(value, "build_command");
+            if !cb.discard_audio {
+                cb.discard_audio = true;
+                cb.args.push("-an".into());
+            }
+            let select = match value.strip_suffix('s') {
+                Some(seconds) => {
+                    // Timestamps are already changed by the speed factor
+                    let speed = cb.speed_factor.unwrap_or(1.0);
+                    let interval = seconds.parse::<f64>().unwrap_or(1.0) / speed;
+                    format!("select='isnan(prev_selected_t)+gte(t-prev_selected_t,{interval})'")
+                }
+                None => format!("select='not(mod(n,{value}))'"),
+            };
+            cb.video_filters.push(select);
+            // Play the selected frames one after another at the source frame rate
+            cb.video_filters.push("setpts=N/FRAME_RATE/TB".to_owned());
+            cb.timeline_changed = true;
+            cb.speed_factor = Self::factor(value, cb.ctx.frame_rate);
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for Timelapse {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        if Self::validate(preset_value).is_ok() {
+            Self::set_parameter_value(data, preset_value);
+        }
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_custom_value!(data).cloned()
+    }
+}
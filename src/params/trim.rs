@@ -3,7 +3,7 @@ use tracing::{debug, warn};
 use crate::{
     model::TrimData,
     params::{Parameter, ParameterData, PresetParameter},
-    visitors::{CommandBuilder, VisitorContext},
+    visitors::{CommandBuilder, TrimRange, VisitorContext},
 };
 
 pub(crate) struct Trim;
@@ -34,7 +34,7 @@ impl Trim {
                 return;
             }
 
-            let trim_data = if has_percents {
+            let mut trim_data = if has_percents {
                 let duration = cb.ctx.input_duration.unwrap();
                 debug!(?trim_raw, ?duration, "build_command before normalization");
                 match trim_raw.normalize(duration) {
@@ -48,8 +48,13 @@ impl Trim {
                 trim_raw.clone()
             };
 
+            // Looped or shared input can only be cut after decoding
+            trim_data.precise |= cb.input_loop.is_some() || cb.seek_output;
             debug!(?trim_data, "build_command");
             let mut args = Vec::new();
+            // Kept source range, the start is unknown for -sseof
+            let mut start = Some(0.0);
+            let mut end = None;
 
             let time_multiplier = cb
                 .speed_factor
@@ -75,6 +80,11 @@ impl Trim {
                     ss.to_owned()
                 };
 
+                start = (args.first().is_some_and(|arg| arg == "-ss"))
+                    .then(|| Self::to_seconds(&ss_normalized));
+                if !trim_data.precise {
+                    cb.input_seek = start;
+                }
                 if let Some(tmult) = time_multiplier {
                     args.push(Self::adjust_time(&ss_normalized, tmult));
                 } else {
@@ -95,6 +105,13 @@ impl Trim {
                     args.push("-t".into());
                     to.to_owned()
                 };
+                end = if !trim_data.use_to {
+                    start.map(|start| start + Self::to_seconds(&to_normalized))
+                } else if to_normalized.starts_with('-') {
+                    None
+                } else {
+                    Some(Self::to_seconds(&to_normalized))
+                };
 
                 if let Some(tmult) = time_multiplier {
                     args.push(Self::adjust_time(&to_normalized, tmult));
@@ -103,6 +120,14 @@ impl Trim {
                 }
             }
             debug!(?args, "trim args");
+            let output_args = trim_data
+                .precise
+                .then(|| cb.args.len()..cb.args.len() + args.len());
+            cb.trim = start.map(|start| TrimRange {
+                start,
+                end,
+                output_args,
+            });
             if trim_data.precise {
                 cb.args.append(&mut args);
             } else {
@@ -111,7 +136,7 @@ impl Trim {
         }
     }
 
-    fn to_seconds(time_str: &str) -> f64 {
+    pub(crate) fn to_seconds(time_str: &str) -> f64 {
         // Parse HH:MM:SS.mmm / MM:SS.mmm / SS.mmm
         let parts: Vec<&str> = time_str.split(':').collect();
         match parts.len() {
@@ -207,6 +232,7 @@ mod tests {
     fn apply_preset(preset: &str) -> TrimData {
         let ctx = VisitorContext {
             input_duration: Some(100.0),
+            ..Default::default()
         };
         let mut data = ParameterData::Trim(TrimData::default());
         Trim::apply_preset(&ctx, &mut data, preset);
@@ -219,6 +245,7 @@ mod tests {
     fn save_preset(trim_data: TrimData) -> Option<String> {
         let ctx = VisitorContext {
             input_duration: Some(100.0),
+            ..Default::default()
         };
         let data = ParameterData::Trim(trim_data);
         Trim::save_preset(&ctx, &data)
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use tracing::debug;
 
 use crate::{
+    info::Info,
     model::{Bitrate, BitrateType, InputConstraints, InputType},
     params::{
         Parameter, ParameterData, PresetParameter, SelectOption,
@@ -21,26 +22,36 @@ impl VideoBitrate {
         "16k", "32k", "0", "64k", "128k", "256k", "512k", "1M", "2M", "4M", "8M", "16M",
     ];
 
-    pub fn new_parameter() -> Parameter {
+    pub fn new_parameter(info: &Info) -> Parameter {
+        let source = info.video_bitrate();
+        // Hide bitrates above the source one, they only increase the file size
+        let options = SelectOption::from_slice_filtered(&Self::VARIANTS, |v| {
+            v == Self::DEFAULT
+                || source.is_none_or(|bps| v.parse::<Bitrate>().is_ok_and(|v| v.bps() < bps))
+        });
+        let selected_index = options
+            .iter()
+            .position(|o| o.value == Self::DEFAULT)
+            .unwrap_or_default();
         Parameter::new(
             Self::ID,
             Self::NAME,
             ParameterData::CustomSelect {
-                options: SelectOption::from_slice(&Self::VARIANTS),
-                selected_index: 2,
+                options,
+                selected_index,
                 value: Self::DEFAULT.to_owned(),
                 constraints: InputConstraints {
                     length: 5,
                     input_type: InputType::Bitrate,
                 },
                 validator: Arc::new(Self::validate),
-                formatter: Some(Arc::new(Self::format_value)),
+                formatter: Some(Arc::new(move |value| Self::format_value(value, source))),
             },
         )
         .with_order(4000)
     }
 
-    fn validate(value: &str) -> Result<String, &str> {
+    pub(crate) fn validate(value: &str) -> Result<String, &str> {
         if let Ok(bitrate) = value.parse::<Bitrate>()
             && let Bitrate(value, unit) = &bitrate
             && (*value == 0
@@ -53,21 +64,34 @@ impl VideoBitrate {
         }
     }
 
-    fn format_value(value: &str) -> String {
-        if value == Self::DEFAULT {
-            "auto".to_owned()
-        } else {
-            value.to_owned()
+    fn format_value(value: &str, source: Option<u64>) -> String {
+        match source {
+            Some(bps) if value == Self::DEFAULT => {
+                format!("auto (source {})", Bitrate::from_bps(bps))
+            }
+            None if value == Self::DEFAULT => "auto".to_owned(),
+            Some(bps) if value.parse::<Bitrate>().is_ok_and(|v| v.bps() > bps) => {
+                format!("{value} (above source)")
+            }
+            _ => value.to_owned(),
         }
     }
 
     pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
         if let Some(value) = select_non_default_custom_value!(data) {
             debug!(value, "build_command");
-            cb.pre_output_args.push("-b:v".into());
-            cb.pre_output_args.push(value.into());
+            cb.pre_output_args.extend(Self::args(value, None));
         }
     }
+
+    /// Bitrate option of all video streams or the given output stream
+    pub(crate) fn args(value: &str, stream: Option<usize>) -> [String; 2] {
+        let option = match stream {
+            Some(index) => format!("-b:v:{index}"),
+            None => "-b:v".to_owned(),
+        };
+        [option, value.to_owned()]
+    }
 }
 
 impl<'a> PresetParameter<'a> for VideoBitrate {
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use tracing::debug;
 
 use crate::{
+    info::Info,
     model::{InputConstraints, InputType},
     params::{
         Parameter, ParameterData, PresetParameter, SelectOption,
@@ -18,20 +19,29 @@ impl VideoFrameRate {
     const DEFAULT: &'static str = "0";
     const VARIANTS: [&str; 10] = ["5", "10", "15", "20", "24", "25", "0", "30", "50", "60"];
 
-    pub fn new_parameter() -> Parameter {
+    pub fn new_parameter(info: &Info) -> Parameter {
+        let source = info.frame_rate();
+        // Hide frame rates above the source one, they only duplicate frames
+        let options = SelectOption::from_slice_filtered(&Self::VARIANTS, |v| {
+            v == Self::DEFAULT || source.is_none_or(|fps| v.parse::<f64>().is_ok_and(|v| v < fps))
+        });
+        let selected_index = options
+            .iter()
+            .position(|o| o.value == Self::DEFAULT)
+            .unwrap_or_default();
         Parameter::new(
             Self::ID,
             Self::NAME,
             ParameterData::CustomSelect {
-                options: SelectOption::from_slice(&Self::VARIANTS),
-                selected_index: 6,
+                options,
+                selected_index,
                 value: Self::DEFAULT.to_owned(),
                 constraints: InputConstraints {
                     length: 3,
                     input_type: InputType::PositiveInteger,
                 },
                 validator: Arc::new(Self::validate),
-                formatter: Some(Arc::new(Self::format_value)),
+                formatter: Some(Arc::new(move |value| Self::format_value(value, source))),
             },
         )
     }
@@ -46,9 +56,21 @@ impl VideoFrameRate {
         }
     }
 
-    fn format_value(value: &str) -> String {
+    fn format_value(value: &str, source: Option<f64>) -> String {
+        let Some(fps) = source else {
+            return if value == Self::DEFAULT {
+                "original".to_owned()
+            } else {
+                value.to_owned()
+            };
+        };
         if value == Self::DEFAULT {
-            "original".to_owned()
+            // 23.976, 25
+            let fps = format!("{fps:.3}");
+            let fps = fps.trim_end_matches('0').trim_end_matches('.');
+            format!("original ({fps})")
+        } else if value.parse::<f64>().is_ok_and(|v| v > fps + 0.01) {
+            format!("{value} (above source)")
         } else {
             value.to_owned()
         }
@@ -57,6 +79,7 @@ impl VideoFrameRate {
     pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
         if let Some(value) = select_non_default_custom_value!(data) {
             debug!(value, "build_command");
+            cb.output_frame_rate = value.parse().ok();
             cb.args.push("-r".into());
             cb.args.push(value.into());
         }
@@ -3,7 +3,8 @@ use std::sync::Arc;
 use tracing::debug;
 
 use crate::{
-    model::{InputConstraints, InputType},
+    info::Info,
+    model::{InputConstraints, InputType, ScaleSize, ScaleValue},
     params::{
         Parameter, ParameterData, PresetParameter, SelectOption,
         macros::select_non_default_custom_value,
@@ -19,84 +20,107 @@ impl VideoScale {
     const DEFAULT: &'static str = "0";
     const VARIANTS: [&str; 7] = ["144", "240", "360", "0", "480", "720", "1080"];
 
-    pub fn new_parameter() -> Parameter {
+    pub fn new_parameter(info: &Info) -> Parameter {
+        let source = info.video_size();
+        // Hide heights that would upscale the source
+        let options = SelectOption::from_slice_filtered(&Self::VARIANTS, |v| {
+            v == Self::DEFAULT || source.is_none_or(|(_, h)| v.parse::<u32>().is_ok_and(|v| v < h))
+        });
+        let selected_index = options
+            .iter()
+            .position(|o| o.value == Self::DEFAULT)
+            .unwrap_or_default();
         Parameter::new(
             Self::ID,
             Self::NAME,
             ParameterData::CustomSelect {
-                options: SelectOption::from_slice(&Self::VARIANTS),
-                selected_index: 3,
+                options,
+                selected_index,
                 value: Self::DEFAULT.to_owned(),
                 constraints: InputConstraints {
-                    length: 4,
-                    input_type: InputType::PositiveInteger,
+                    length: 10,
+                    input_type: InputType::Scale,
                 },
                 validator: Arc::new(Self::validate),
-                formatter: Some(Arc::new(Self::format_value)),
+                formatter: Some(Arc::new(move |value| Self::format_value(value, source))),
             },
         )
     }
 
     fn validate(value: &str) -> Result<String, &str> {
-        Self::validate_range(value)
-            .and_then(Self::validate_divisible_by_2)
-            .map(|num| num.to_string())
+        if value == Self::DEFAULT {
+            return Ok(value.to_owned());
+        }
+        value
+            .parse::<ScaleValue>()
+            .map(|scale| scale.to_string())
+            .map_err(|_| {
+                "Invalid value. Expected an even height 720, width 1280:, box 1280x720, \
+                 percents 50%, optional > to prevent upscale, or 0 - original"
+            })
     }
 
-    fn validate_range(value: &str) -> Result<i32, &str> {
-        if let Ok(num) = value.parse::<i32>()
-            && (num == 0 || (64..=8192).contains(&num))
-        {
-            Ok(num)
-        } else {
-            Err("Invalid value. Expected a number in range 64..8192, or 0 - original")
+    /// Describe the value with the resulting size, e.g. "fit 1280x720 → 1280x540"
+    fn format_value(value: &str, source: Option<(u32, u32)>) -> String {
+        let Ok(scale) = value.parse::<ScaleValue>() else {
+            return match source {
+                Some((w, h)) => format!("original ({w}x{h})"),
+                None => "original".to_owned(),
+            };
+        };
+        let mut result = match scale.size {
+            ScaleSize::Height(h) => format!("{h}p"),
+            ScaleSize::Width(w) => format!("width {w}"),
+            ScaleSize::Fit(w, h) => format!("fit {w}x{h}"),
+            ScaleSize::Percent(p) => format!("{p}%"),
+        };
+        if scale.no_upscale {
+            result.push_str(" no upscale");
         }
+        if let Some(source) = source {
+            let (w, h) = scale.result_size(source);
+            result.push_str(&format!(" \u{2192} {w}x{h}"));
+            if h > source.1 {
+                result.push_str(" (upscale)");
+            }
+        }
+        result
     }
 
-    fn validate_divisible_by_2<'a>(num: i32) -> Result<i32, &'a str> {
-        if num % 2 == 0 {
-            Ok(num)
-        } else {
-            Err("Invalid value. Expected a number divisible by 2")
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if let Some(value) = select_non_default_custom_value!(data)
+            && let Ok(scale) = value.parse::<ScaleValue>()
+        {
+            debug!(value, "build_command");
+            let filter = Self::scale_filter(cb, &scale, cb.video_filters.is_empty());
+            cb.video_filters.push(filter);
         }
     }
 
-    fn format_value(value: &str) -> String {
-        if value == Self::DEFAULT {
-            "original".to_owned()
-        } else {
-            format!("{}p", &value)
+    /// Scale filter for the hardware acceleration, `first` is set when no other video filter runs before it
+    pub(crate) fn scale_filter(cb: &CommandBuilder, scale: &ScaleValue, first: bool) -> String {
+        let args = scale.filter_args();
+        // Use nvenc cuda scale only if there is no other video filter
+        #[cfg(any(target_os = "windows", target_os = "linux"))]
+        if cb.hwaccel == HWAccel::Nvenc && first {
+            return format!("scale_cuda={args}");
         }
-    }
+        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+        let _ = first;
 
-    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
-        if let Some(value) = select_non_default_custom_value!(data) {
-            debug!(value, "build_command");
-            // Use nvenc cuda scale only if there is no other video filter
-            #[cfg(target_os = "windows")]
-            {
-                if (cb.hwaccel == HWAccel::Nvenc) && (cb.video_filters.is_empty()) {
-                    cb.video_filters.push(format!("scale_cuda=-2:{}", &value));
-                } else {
-                    cb.video_filters.push(format!("scale=-2:{}", &value));
-                }
-            }
+        #[cfg(target_os = "linux")]
+        if cb.hwaccel == HWAccel::Vaapi {
+            return format!("scale_vaapi={args}");
+        }
 
-            #[cfg(target_os = "linux")]
-            {
-                if (cb.hwaccel == HWAccel::Nvenc) && (cb.video_filters.is_empty()) {
-                    cb.video_filters.push(format!("scale_cuda=-2:{}", &value));
-                } else if cb.hwaccel == HWAccel::Vaapi {
-                    cb.video_filters.push(format!("scale_vaapi=-2:{}", &value));
-                } else {
-                    cb.video_filters.push(format!("scale=-2:{}", &value));
-                }
-            }
+        Self::software_scale(cb, &args)
+    }
 
-            #[cfg(not(any(target_os = "windows", target_os = "linux")))]
-            {
-                cb.video_filters.push(format!("scale=-2:{}", &value));
-            }
+    /// Software scale filter with the selected algorithm
+    pub(crate) fn software_scale(cb: &CommandBuilder, args: &str) -> String {
+        match &cb.scale_flags {
+            Some(flags) => format!("scale={args}:flags={flags}"),
+            None => format!("scale={args}"),
         }
     }
 }
@@ -112,3 +136,51 @@ impl<'a> PresetParameter<'a> for VideoScale {
         select_non_default_custom_value!(data).cloned()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(VideoScale::validate("720").as_deref(), Ok("720"));
+        assert_eq!(VideoScale::validate("1280:>").as_deref(), Ok("1280:>"));
+        assert_eq!(VideoScale::validate("1280X720").as_deref(), Ok("1280x720"));
+        assert_eq!(VideoScale::validate("50%").as_deref(), Ok("50%"));
+        for value in ["721", "32", "1280x", "x720", "0%", "50%>", "720>>", ""] {
+            assert!(VideoScale::validate(value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_format_value() {
+        let ultrawide = Some((2560, 1080));
+        assert_eq!(
+            VideoScale::format_value("0", ultrawide),
+            "original (2560x1080)"
+        );
+        assert_eq!(VideoScale::format_value("0", None), "original");
+        assert_eq!(
+            VideoScale::format_value("1280x720", ultrawide),
+            "fit 1280x720 → 1280x540"
+        );
+        assert_eq!(
+            VideoScale::format_value("720", ultrawide),
+            "720p → 1706x720"
+        );
+        assert_eq!(
+            VideoScale::format_value("3840:>", ultrawide),
+            "width 3840 no upscale → 2560x1080"
+        );
+        assert_eq!(VideoScale::format_value("50%", ultrawide), "50% → 1280x540");
+        assert_eq!(
+            VideoScale::format_value("1440", ultrawide),
+            "1440p → 3414x1440 (upscale)"
+        );
+        assert_eq!(
+            VideoScale::format_value("1080x1920>", Some((1920, 1080))),
+            "fit 1080x1920 no upscale → 1080x608"
+        );
+        assert_eq!(VideoScale::format_value("720", None), "720p");
+    }
+}
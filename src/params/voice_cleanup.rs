@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use tracing::debug;
+
+use crate::{
+    model::{InputConstraints, InputType},
+    params::{
+        Parameter, ParameterData, PresetParameter, SelectOption,
+        macros::select_non_default_custom_value,
+    },
+    visitors::{CommandBuilder, VisitorContext},
+};
+
+/// Denoise, highpass, compressor and limiter chain for voice recordings.
+/// Custom value sets the individual thresholds: "noise reduction,threshold,ratio,limit"
+pub(crate) struct VoiceCleanup;
+
+#[derive(Debug)]
+struct CleanupLevels {
+    /// Noise reduction, dB
+    noise_reduction: i32,
+    /// Compressor threshold, dB
+    threshold: i32,
+    /// Compressor ratio
+    ratio: i32,
+    /// Limiter ceiling, dB
+    limit: i32,
+}
+
+impl VoiceCleanup {
+    pub(crate) const ID: &'static str = "cleanup";
+    pub(crate) const NAME: &'static str = "Voice Cleanup";
+    const DEFAULT: &'static str = "off";
+    const HIGHPASS: u32 = 80;
+
+    pub fn new_parameter() -> Parameter {
+        Parameter::new(
+            Self::ID,
+            Self::NAME,
+            ParameterData::CustomSelect {
+                options: SelectOption::from_slice(&["off", "light", "strong"]),
+                selected_index: 0,
+                value: Self::DEFAULT.to_owned(),
+                constraints: InputConstraints {
+                    length: 14,
+                    input_type: InputType::IntegerList,
+                },
+                validator: Arc::new(Self::validate),
+                formatter: Some(Arc::new(Self::format_value)),
+            },
+        )
+    }
+
+    fn levels(value: &str) -> Option<CleanupLevels> {
+        match value {
+            "light" => Some(CleanupLevels {
+                noise_reduction: 10,
+                threshold: -18,
+                ratio: 2,
+                limit: -1,
+            }),
+            "strong" => Some(CleanupLevels {
+                noise_reduction: 20,
+                threshold: -24,
+                ratio: 4,
+                limit: -1,
+            }),
+            _ => {
+                let parts = value
+                    .split(',')
+                    .map(|part| part.trim().parse::<i32>().ok())
+                    .collect::<Option<Vec<_>>>()?;
+                let [noise_reduction, threshold, ratio, limit] = parts[..] else {
+                    return None;
+                };
+                Some(CleanupLevels {
+                    noise_reduction,
+                    threshold,
+                    ratio,
+                    limit,
+                })
+            }
+        }
+    }
+
+    fn validate(value: &str) -> Result<String, &str> {
+        if matches!(value, "off" | "light" | "strong") {
+            return Ok(value.to_owned());
+        }
+        match Self::levels(value) {
+            Some(levels)
+                if (0..=97).contains(&levels.noise_reduction)
+                    && (-60..=0).contains(&levels.threshold)
+                    && (1..=20).contains(&levels.ratio)
+                    && (-24..=0).contains(&levels.limit) =>
+            {
+                Ok(format!(
+                    "{},{},{},{}",
+                    levels.noise_reduction, levels.threshold, levels.ratio, levels.limit
+                ))
+            }
+            _ => Err(
+                "Expected noise reduction 0..97, threshold -60..0, ratio 1..20, \
+                 limit -24..0 in dB, e.g. 12,-20,3,-1",
+            ),
+        }
+    }
+
+    fn format_value(value: &str) -> String {
+        match Self::levels(value) {
+            Some(levels) if !matches!(value, "light" | "strong") => format!(
+                "denoise {}dB, compress {}dB {}:1, limit {}dB",
+                levels.noise_reduction, levels.threshold, levels.ratio, levels.limit
+            ),
+            _ => value.to_owned(),
+        }
+    }
+
+    pub fn build_command(cb: &mut CommandBuilder, data: &ParameterData) {
+        if !cb.discard_audio
+            && let Some(value) = select_non_default_custom_value!(data)
+            && let Some(levels) = Self::levels(value)
+        {
+            debug!(value, "build_command");
+            if levels.noise_reduction > 0 {
+                cb.audio_filters
+                    .push(format!("afftdn=nr={}:nf=-50", levels.noise_reduction));
+            }
+            cb.audio_filters
+                .push(format!("highpass=f={}", Self::HIGHPASS));
+            cb.audio_filters.push(format!(
+                "acompressor=threshold={}dB:ratio={}:attack=20:release=250",
+                levels.threshold, levels.ratio
+            ));
+            cb.audio_filters
+                .push(format!("alimiter=limit={}dB:level=0", levels.limit));
+        }
+    }
+}
+
+impl<'a> PresetParameter<'a> for VoiceCleanup {
+    fn apply_preset(_ctx: &VisitorContext, data: &mut ParameterData, preset_value: &str) {
+        if let Ok(value) = Self::validate(preset_value) {
+            Self::set_parameter_value(data, &value);
+        }
+    }
+
+    fn save_preset(_ctx: &VisitorContext, data: &'a ParameterData) -> Option<String> {
+        select_non_default_custom_value!(data).cloned()
+    }
+}
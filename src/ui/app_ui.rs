@@ -62,7 +62,7 @@ impl Widget for &mut App<'_> {
                 .border_set(symbols::border::ROUNDED)
                 .border_style(style)
                 .title_top(
-                    Line::from("Params")
+                    Line::from(self.params_title())
                         .fg(self.theme.pane_title_color())
                         .left_aligned(),
                 );
@@ -1,6 +1,8 @@
-use ratatui::layout::Rect;
-use ratatui::style::Stylize as _;
-use ratatui::text::Line;
+use ratatui::layout::{Position, Rect};
+use ratatui::prelude::Frame;
+use ratatui::style::{Style, Stylize as _};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget as _};
 use tui_input::Input;
 
 mod app_ui;
@@ -45,3 +47,54 @@ fn checkbox_line<'a>(checked: bool, label: &str, active: bool, theme: &'a Theme)
     }
     line
 }
+
+/// Prepare a selector Line, the value is switched with arrow keys
+fn selector_line<'a>(label: &str, value: &str, active: bool, theme: &'a Theme) -> Line<'a> {
+    let mut line = Line::from(vec![
+        format!("{label}: ").fg(theme.checkbox_label_color()),
+        "\u{2039} ".fg(theme.checkbox_color()),
+        value.to_owned().fg(theme.text_param_color()),
+        " \u{203a}".fg(theme.checkbox_color()),
+    ]);
+    if active {
+        line = line.patch_style(theme.checkbox_focused_style());
+    }
+    line
+}
+
+/// Render a titled input field, the active one also gets the cursor
+fn render_input(
+    frame: &mut Frame,
+    area: Rect,
+    input: &Input,
+    title: &str,
+    active: bool,
+    theme: &Theme,
+) {
+    let (value, x) = input_value_and_pos(input, area.width);
+    let (border_color, text_color) = if active {
+        (theme.border_input_color(), theme.text_input_color())
+    } else {
+        (
+            theme.border_input_inactive_color(),
+            theme.text_muted_color(),
+        )
+    };
+    Paragraph::new(value)
+        .block(
+            Block::bordered()
+                .border_style(border_color)
+                .style(text_color)
+                .title(Span::styled(
+                    title.to_owned(),
+                    Style::new().fg(border_color),
+                )),
+        )
+        .render(area, frame.buffer_mut());
+    if active {
+        frame.set_cursor_position(Position {
+            x: area.x + x,
+            y: area.y + 1,
+        });
+    }
+}
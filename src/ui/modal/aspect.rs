@@ -0,0 +1,205 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::layout::{HorizontalAlignment, Margin};
+use ratatui::text::Span;
+use ratatui::{layout::Layout, prelude::Frame};
+use ratatui::{
+    layout::{Constraint, Flex, Rect},
+    style::Stylize as _,
+    symbols,
+    text::Line,
+    widgets::{Block, Paragraph, Widget as _},
+};
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler as _;
+
+use crate::model::{AspectData, AspectMode};
+use crate::ui::modal::{KeyboardHandler, ModalResult, UiModal};
+use crate::ui::widget::BgClear;
+use crate::ui::{Theme, is_portrait, render_input, selector_line};
+
+const INPUTS: usize = 3;
+const RATIO: usize = 0;
+const MODE: usize = 1;
+const COLOR: usize = 2;
+
+#[derive(Debug)]
+pub(crate) struct AspectModal {
+    active_input: usize,
+    ratio: Option<(u32, u32)>,
+    mode: AspectMode,
+    color: Input,
+    source: Option<(u32, u32)>,
+    error: Option<String>,
+}
+
+impl UiModal for AspectModal {
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let area = frame.area();
+        let portrait = is_portrait(area);
+        let [modal_area] = Layout::vertical([Constraint::Length(11)])
+            .horizontal_margin(if portrait { 1 } else { area.width / 4 })
+            .flex(Flex::Center)
+            .areas(area);
+        let [selectors_area, color_area, size_area, hints_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::SpaceBetween)
+        .areas(modal_area.inner(Margin::new(2, 1)));
+        let [ratio_area, mode_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(selectors_area);
+
+        frame.render_widget(BgClear::new(theme.background_color()), modal_area);
+        Block::bordered()
+            .title("Aspect".fg(theme.modal_title_color()))
+            .border_set(symbols::border::THICK)
+            .border_style(theme.border_modal_style())
+            .render(modal_area, frame.buffer_mut());
+
+        let active = self.active_input;
+        let ratio = AspectData::ratio_label(self.ratio);
+        let lines = [
+            (
+                ratio_area,
+                selector_line("Ratio", &ratio, active == RATIO, theme),
+            ),
+            (
+                mode_area,
+                selector_line("Mode", self.mode.label(), active == MODE, theme),
+            ),
+        ];
+        for (line_area, line) in lines {
+            Paragraph::new(line)
+                .alignment(HorizontalAlignment::Center)
+                .render(line_area, frame.buffer_mut());
+        }
+        render_input(
+            frame,
+            color_area,
+            &self.color,
+            "Padding color",
+            active == COLOR,
+            theme,
+        );
+        Paragraph::new(self.size_line(theme))
+            .alignment(HorizontalAlignment::Center)
+            .render(size_area, frame.buffer_mut());
+        self.render_status(hints_area, frame, theme);
+    }
+}
+
+impl KeyboardHandler for AspectModal {
+    fn handle_key(&mut self, key: KeyEvent) -> ModalResult {
+        self.error.take();
+        match (self.active_input, key.code) {
+            (_, KeyCode::Esc) => return ModalResult::Close,
+            (_, KeyCode::BackTab) => self.active_input = (self.active_input + INPUTS - 1) % INPUTS,
+            (_, KeyCode::Tab) => self.active_input = (self.active_input + 1) % INPUTS,
+            (_, KeyCode::Enter) => {
+                return match AspectData::validate(self.color.value()) {
+                    Ok(()) => ModalResult::Aspect,
+                    Err(msg) => {
+                        self.error = Some(msg.to_owned());
+                        ModalResult::None
+                    }
+                };
+            }
+            (RATIO, KeyCode::Left | KeyCode::Char('h')) => {
+                self.ratio = AspectData::prev_ratio(self.ratio);
+            }
+            (RATIO, KeyCode::Right | KeyCode::Char('l' | ' ')) => {
+                self.ratio = AspectData::next_ratio(self.ratio);
+            }
+            (MODE, KeyCode::Left | KeyCode::Char('h')) => self.mode = self.mode.prev(),
+            (MODE, KeyCode::Right | KeyCode::Char('l' | ' ')) => self.mode = self.mode.next(),
+            (COLOR, KeyCode::Char(x))
+                if (x.is_ascii_alphanumeric() || matches!(x, '#' | '@' | '.'))
+                    && self.color.value().len() < 16 =>
+            {
+                self.color.handle_event(&Event::Key(key));
+            }
+            (
+                COLOR,
+                KeyCode::Backspace
+                | KeyCode::Delete
+                | KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::Home
+                | KeyCode::End,
+            ) => {
+                self.color.handle_event(&Event::Key(key));
+            }
+            _ => {}
+        }
+        ModalResult::None
+    }
+}
+
+impl AspectModal {
+    pub fn new(data: AspectData) -> Self {
+        Self {
+            active_input: 0,
+            ratio: data.ratio,
+            mode: data.mode,
+            color: Input::new(data.color),
+            source: data.source,
+            error: None,
+        }
+    }
+
+    /// Source and resulting resolution
+    fn size_line<'a>(&self, theme: &'a Theme) -> Line<'a> {
+        let Some((w, h)) = self.source else {
+            return Line::from("Source resolution is unknown".fg(theme.text_muted_color()));
+        };
+        let (tw, th) = AspectData::from(self).target_size().unwrap_or((w, h));
+        Line::from(vec![
+            format!("{w}x{h}").fg(theme.text_muted_color()),
+            " \u{2192} ".fg(theme.text_muted_color()),
+            format!("{tw}x{th}").fg(theme.text_param_color()),
+        ])
+    }
+
+    fn render_status(&self, area: Rect, frame: &mut Frame, theme: &Theme) {
+        let line = if let Some(error) = &self.error {
+            Line::from(Span::styled(error, theme.error_style().bold())).centered()
+        } else {
+            let key_style = theme.key_style();
+            let text_style = theme.text_color();
+            let mut parts = vec![
+                Span::styled("Enter", key_style),
+                Span::styled(": confirm  ", text_style),
+                Span::styled("Esc", key_style),
+                Span::styled(": close  ", text_style),
+                Span::styled("Tab", key_style),
+                Span::styled(": switch focus", text_style),
+            ];
+            if self.active_input != COLOR {
+                parts.append(&mut vec![
+                    Span::styled("  ←/→", key_style),
+                    Span::styled(": change", text_style),
+                ]);
+            }
+            Line::from(parts)
+        };
+        frame.render_widget(Paragraph::new(line), area);
+    }
+}
+
+impl From<&AspectModal> for AspectData {
+    fn from(modal: &AspectModal) -> AspectData {
+        let color = modal.color.value();
+        AspectData {
+            ratio: modal.ratio,
+            mode: modal.mode,
+            color: if AspectData::validate(color).is_ok() {
+                color.to_owned()
+            } else {
+                AspectData::default().color
+            },
+            source: modal.source,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::Frame;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Padding, Paragraph, Widget as _};
+use ratatui::{layout::Constraint, style::Style};
+
+use crate::ui::Theme;
+use crate::ui::modal::{KeyboardHandler, ModalResult, UiModal};
+use crate::ui::widget::BgClear;
+
+/// Question before a destructive action, `y` or Enter returns the result, any other key cancels
+#[derive(Debug)]
+pub(crate) struct ConfirmModal {
+    message: String,
+    result: ModalResult,
+}
+
+impl UiModal for ConfirmModal {
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let width = self.message.len().max(24) as u16;
+        let modal_area = frame
+            .area()
+            .centered(Constraint::Length(width + 10), Constraint::Length(6));
+
+        frame.render_widget(BgClear::new(theme.background_color()), modal_area);
+        let key_style = theme.key_style();
+        let text_style = Style::new().fg(theme.warning_text_color());
+        Paragraph::new(vec![
+            Line::styled(self.message.as_str(), text_style.bold()),
+            Line::default(),
+            Line::from(vec![
+                Span::styled("y", key_style),
+                Span::styled(": confirm  ", text_style),
+                Span::styled("Esc", key_style),
+                Span::styled(": cancel", text_style),
+            ]),
+        ])
+        .block(
+            Block::default()
+                .title_top(Line::from(" Confirm ").centered())
+                .borders(Borders::TOP)
+                .border_type(BorderType::Double)
+                .border_style(theme.warning_style())
+                .padding(Padding::vertical(1)),
+        )
+        .centered()
+        .render(modal_area, frame.buffer_mut());
+    }
+}
+
+impl KeyboardHandler for ConfirmModal {
+    fn handle_key(&mut self, key: KeyEvent) -> ModalResult {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => self.result.clone(),
+            _ => ModalResult::Close,
+        }
+    }
+}
+
+impl ConfirmModal {
+    pub(crate) fn new(message: String, result: ModalResult) -> Self {
+        Self { message, result }
+    }
+}
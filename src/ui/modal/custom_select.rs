@@ -83,7 +83,14 @@ impl KeyboardHandler for CustomSelectModal {
                     (InputType::Integer, '0'..='9' | '-')
                     | (InputType::PositiveInteger, '0'..='9')
                     | (InputType::PositiveDecimal, '0'..='9' | '.')
-                    | (InputType::Bitrate, '0'..='9' | 'k' | 'K' | 'm' | 'M') => {
+                    | (InputType::Bitrate, '0'..='9' | 'k' | 'K' | 'm' | 'M')
+                    | (InputType::Scale, '0'..='9' | 'x' | ':' | '%' | '>')
+                    | (InputType::Interval, '0'..='9' | '.' | 's')
+                    | (InputType::IntegerList, '0'..='9' | '-' | ',')
+                    | (
+                        InputType::RenditionList,
+                        '0'..='9' | 'p' | '@' | 'k' | 'K' | 'm' | 'M' | ',',
+                    ) => {
                         self.input.handle_event(&Event::Key(key));
                     }
                     _ => {}
@@ -0,0 +1,217 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{HorizontalAlignment, Margin};
+use ratatui::text::Span;
+use ratatui::widgets::Wrap;
+use ratatui::{layout::Layout, prelude::Frame};
+use ratatui::{
+    layout::{Constraint, Flex, Rect},
+    style::Stylize as _,
+    symbols,
+    text::Line,
+    widgets::{Block, Paragraph, Widget as _},
+};
+
+use crate::model::{EqBands, EqData, EqPreset};
+use crate::ui::modal::{KeyboardHandler, ModalResult, UiModal};
+use crate::ui::widget::BgClear;
+use crate::ui::{Theme, is_portrait, selector_line};
+
+const INPUTS: usize = 6;
+const PRESET: usize = 0;
+const BASS: usize = 1;
+const MID: usize = 2;
+const TREBLE: usize = 3;
+const HIGHPASS: usize = 4;
+const LOWPASS: usize = 5;
+
+#[derive(Debug)]
+pub(crate) struct EqModal {
+    active_input: usize,
+    preset: EqPreset,
+    bands: EqBands,
+}
+
+impl UiModal for EqModal {
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let area = frame.area();
+        let portrait = is_portrait(area);
+        let [modal_area] = Layout::vertical([Constraint::Length(12)])
+            .horizontal_margin(if portrait { 1 } else { area.width / 5 })
+            .flex(Flex::Center)
+            .areas(area);
+        let [
+            preset_area,
+            gains_area,
+            cutoff_area,
+            filters_area,
+            hints_area,
+        ] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::SpaceBetween)
+        .areas(modal_area.inner(Margin::new(2, 1)));
+        let [bass_area, mid_area, treble_area] =
+            Layout::horizontal([Constraint::Fill(1); 3]).areas(gains_area);
+        let [highpass_area, lowpass_area] =
+            Layout::horizontal([Constraint::Fill(1); 2]).areas(cutoff_area);
+
+        frame.render_widget(BgClear::new(theme.background_color()), modal_area);
+        Block::bordered()
+            .title("Audio EQ".fg(theme.modal_title_color()))
+            .border_set(symbols::border::THICK)
+            .border_style(theme.border_modal_style())
+            .render(modal_area, frame.buffer_mut());
+
+        let active = self.active_input;
+        let bands = &self.bands;
+        let lines = [
+            (
+                preset_area,
+                selector_line("Preset", self.preset.label(), active == PRESET, theme),
+            ),
+            (
+                bass_area,
+                selector_line(
+                    "Bass",
+                    &EqBands::gain_label(bands.bass),
+                    active == BASS,
+                    theme,
+                ),
+            ),
+            (
+                mid_area,
+                selector_line("Mid", &EqBands::gain_label(bands.mid), active == MID, theme),
+            ),
+            (
+                treble_area,
+                selector_line(
+                    "Treble",
+                    &EqBands::gain_label(bands.treble),
+                    active == TREBLE,
+                    theme,
+                ),
+            ),
+            (
+                highpass_area,
+                selector_line(
+                    "Highpass",
+                    &EqBands::cutoff_label(bands.highpass),
+                    active == HIGHPASS,
+                    theme,
+                ),
+            ),
+            (
+                lowpass_area,
+                selector_line(
+                    "Lowpass",
+                    &EqBands::cutoff_label(bands.lowpass),
+                    active == LOWPASS,
+                    theme,
+                ),
+            ),
+        ];
+        for (line_area, line) in lines {
+            Paragraph::new(line)
+                .alignment(HorizontalAlignment::Center)
+                .render(line_area, frame.buffer_mut());
+        }
+        Paragraph::new(self.filters_line(theme))
+            .alignment(HorizontalAlignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(filters_area, frame.buffer_mut());
+        self.render_status(hints_area, frame, theme);
+    }
+}
+
+impl KeyboardHandler for EqModal {
+    fn handle_key(&mut self, key: KeyEvent) -> ModalResult {
+        let forward = match key.code {
+            KeyCode::Esc => return ModalResult::Close,
+            KeyCode::Enter => return ModalResult::Eq,
+            KeyCode::BackTab => {
+                self.active_input = (self.active_input + INPUTS - 1) % INPUTS;
+                return ModalResult::None;
+            }
+            KeyCode::Tab => {
+                self.active_input = (self.active_input + 1) % INPUTS;
+                return ModalResult::None;
+            }
+            KeyCode::Left | KeyCode::Char('h') => false,
+            KeyCode::Right | KeyCode::Char('l' | ' ') => true,
+            _ => return ModalResult::None,
+        };
+        if self.active_input == PRESET {
+            self.preset = if forward {
+                self.preset.next()
+            } else {
+                self.preset.prev()
+            };
+            // Custom keeps the current bands to be edited
+            if let Some(bands) = self.preset.bands() {
+                self.bands = bands;
+            }
+            return ModalResult::None;
+        }
+        let bands = &mut self.bands;
+        match self.active_input {
+            BASS => bands.bass = EqBands::step_gain(bands.bass, forward),
+            MID => bands.mid = EqBands::step_gain(bands.mid, forward),
+            TREBLE => bands.treble = EqBands::step_gain(bands.treble, forward),
+            HIGHPASS => {
+                bands.highpass = EqBands::step_cutoff(bands.highpass, &EqBands::HIGHPASS, forward);
+            }
+            LOWPASS => {
+                bands.lowpass = EqBands::step_cutoff(bands.lowpass, &EqBands::LOWPASS, forward);
+            }
+            _ => {}
+        }
+        self.preset = EqData::with_bands(self.bands).preset;
+        ModalResult::None
+    }
+}
+
+impl EqModal {
+    pub fn new(data: EqData) -> Self {
+        Self {
+            active_input: 0,
+            preset: data.preset,
+            bands: data.bands,
+        }
+    }
+
+    /// Resulting FFmpeg filter chain
+    fn filters_line<'a>(&self, theme: &'a Theme) -> Line<'a> {
+        let filters = self.bands.filters();
+        if filters.is_empty() {
+            Line::from("No filters".fg(theme.text_muted_color()))
+        } else {
+            Line::from(filters.join(",").fg(theme.text_muted_color()))
+        }
+    }
+
+    fn render_status(&self, area: Rect, frame: &mut Frame, theme: &Theme) {
+        let key_style = theme.key_style();
+        let text_style = theme.text_color();
+        let line = Line::from(vec![
+            Span::styled("Enter", key_style),
+            Span::styled(": confirm  ", text_style),
+            Span::styled("Esc", key_style),
+            Span::styled(": close  ", text_style),
+            Span::styled("Tab", key_style),
+            Span::styled(": switch focus  ", text_style),
+            Span::styled("←/→", key_style),
+            Span::styled(": change", text_style),
+        ]);
+        frame.render_widget(Paragraph::new(line), area);
+    }
+}
+
+impl From<&EqModal> for EqData {
+    fn from(modal: &EqModal) -> EqData {
+        EqData::with_bands(modal.bands)
+    }
+}
@@ -0,0 +1,184 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{HorizontalAlignment, Margin};
+use ratatui::text::Span;
+use ratatui::{layout::Layout, prelude::Frame};
+use ratatui::{
+    layout::{Constraint, Flex, Rect},
+    style::Stylize as _,
+    symbols,
+    text::Line,
+    widgets::{Block, Paragraph, Widget as _},
+};
+
+use crate::model::{FramesData, FramesMode};
+use crate::ui::modal::{KeyboardHandler, ModalResult, UiModal};
+use crate::ui::widget::BgClear;
+use crate::ui::{Theme, checkbox_line, is_portrait, selector_line};
+
+const MODE: usize = 0;
+const VALUE: usize = 1;
+const HEADER: usize = 2;
+const TIMESTAMPS: usize = 3;
+
+#[derive(Debug)]
+pub(crate) struct FramesModal {
+    active_input: usize,
+    data: FramesData,
+}
+
+impl UiModal for FramesModal {
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let area = frame.area();
+        let portrait = is_portrait(area);
+        let [modal_area] = Layout::vertical([Constraint::Length(10)])
+            .horizontal_margin(if portrait { 1 } else { area.width / 4 })
+            .flex(Flex::Center)
+            .areas(area);
+        let [mode_area, value_area, options_area, note_area, hints_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::SpaceBetween)
+        .areas(modal_area.inner(Margin::new(2, 1)));
+        let [header_area, timestamps_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(options_area);
+
+        frame.render_widget(BgClear::new(theme.background_color()), modal_area);
+        Block::bordered()
+            .title("Frames".fg(theme.modal_title_color()))
+            .border_set(symbols::border::THICK)
+            .border_style(theme.border_modal_style())
+            .render(modal_area, frame.buffer_mut());
+
+        let active = self.active_input.min(self.inputs() - 1);
+        let mut lines = vec![(
+            mode_area,
+            selector_line("Write", self.data.mode.label(), active == MODE, theme),
+        )];
+        if let Some(value) = self.data.value_label() {
+            let label = match self.data.mode {
+                FramesMode::Every => "Every",
+                FramesMode::Sheet => "Grid",
+                _ => "Scene score above",
+            };
+            lines.push((
+                value_area,
+                selector_line(label, &value, active == VALUE, theme),
+            ));
+        }
+        if self.data.mode == FramesMode::Sheet {
+            lines.push((
+                header_area,
+                checkbox_line(self.data.header, "Header", active == HEADER, theme),
+            ));
+            lines.push((
+                timestamps_area,
+                checkbox_line(
+                    self.data.timestamps,
+                    "Timestamps",
+                    active == TIMESTAMPS,
+                    theme,
+                ),
+            ));
+        }
+        for (line_area, line) in lines {
+            Paragraph::new(line)
+                .alignment(HorizontalAlignment::Center)
+                .render(line_area, frame.buffer_mut());
+        }
+        Paragraph::new(self.note_lines(theme))
+            .alignment(HorizontalAlignment::Center)
+            .render(note_area, frame.buffer_mut());
+        self.render_status(hints_area, frame, theme);
+    }
+}
+
+impl KeyboardHandler for FramesModal {
+    fn handle_key(&mut self, key: KeyEvent) -> ModalResult {
+        let inputs = self.inputs();
+        self.active_input = self.active_input.min(inputs - 1);
+        let forward = match key.code {
+            KeyCode::Esc => return ModalResult::Close,
+            KeyCode::Enter => return ModalResult::Frames,
+            KeyCode::BackTab => {
+                self.active_input = (self.active_input + inputs - 1) % inputs;
+                return ModalResult::None;
+            }
+            KeyCode::Tab => {
+                self.active_input = (self.active_input + 1) % inputs;
+                return ModalResult::None;
+            }
+            KeyCode::Left | KeyCode::Char('h') => false,
+            KeyCode::Right | KeyCode::Char('l' | ' ') => true,
+            _ => return ModalResult::None,
+        };
+        match self.active_input {
+            VALUE => self.data.step_value(forward),
+            HEADER => self.data.header = !self.data.header,
+            TIMESTAMPS => self.data.timestamps = !self.data.timestamps,
+            _ => self.data.step_mode(forward),
+        }
+        ModalResult::None
+    }
+}
+
+impl FramesModal {
+    pub fn new(data: FramesData) -> Self {
+        Self {
+            active_input: 0,
+            data,
+        }
+    }
+
+    /// Number of focusable lines for the selected mode
+    fn inputs(&self) -> usize {
+        match self.data.mode {
+            FramesMode::Sheet => 4,
+            _ if self.data.value_label().is_some() => 2,
+            _ => 1,
+        }
+    }
+
+    /// Explain which frames are taken and how the images are named
+    fn note_lines<'a>(&self, theme: &'a Theme) -> Vec<Line<'a>> {
+        let note = match self.data.mode {
+            FramesMode::Single => "Frame at the trim start",
+            FramesMode::Every | FramesMode::All => "Range is set by Trim, size by Scale",
+            FramesMode::Scene => "First frame of each scene in the trim range",
+            FramesMode::Sheet => "Frames are sampled evenly, tiles are 320px wide",
+        };
+        let mut lines = vec![Line::from(note.fg(theme.text_muted_color()))];
+        if !matches!(self.data.mode, FramesMode::Single | FramesMode::Sheet) {
+            lines.push(Line::from(
+                "Images are named {name}_{n:05} unless the name has {n}"
+                    .fg(theme.text_muted_color()),
+            ));
+        }
+        lines
+    }
+
+    fn render_status(&self, area: Rect, frame: &mut Frame, theme: &Theme) {
+        let key_style = theme.key_style();
+        let text_style = theme.text_color();
+        let line = Line::from(vec![
+            Span::styled("Enter", key_style),
+            Span::styled(": confirm  ", text_style),
+            Span::styled("Esc", key_style),
+            Span::styled(": close  ", text_style),
+            Span::styled("Tab", key_style),
+            Span::styled(": switch focus  ", text_style),
+            Span::styled("←/→", key_style),
+            Span::styled(": change", text_style),
+        ]);
+        frame.render_widget(Paragraph::new(line), area);
+    }
+}
+
+impl From<&FramesModal> for FramesData {
+    fn from(modal: &FramesModal) -> FramesData {
+        modal.data.clone()
+    }
+}
@@ -105,6 +105,7 @@ impl<'a> HelpBuilder<'a> {
         lines.push(Line::from("         Key Action".fg(self.theme.pane_title_color())).bold());
         lines.extend(self.navigation_lines());
         lines.extend(self.render_lines());
+        lines.extend(self.variants_lines());
         lines.extend(self.modals_lines());
         lines.extend(self.clipboard_lines());
         Text::from(lines)
@@ -160,6 +161,21 @@ impl<'a> HelpBuilder<'a> {
         lines
     }
 
+    fn variants_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        lines.push(Line::default());
+        lines.push(
+            Line::from(" Variants:")
+                .fg(self.theme.pane_title_color())
+                .bold(),
+        );
+        lines.extend(self.lines(&["a"], "Add a variant with the current parameters"));
+        lines.extend(self.lines(&["x"], "Remove the current variant, asks to confirm"));
+        lines.extend(self.lines(&["["], "Switch to the previous variant"));
+        lines.extend(self.lines(&["]"], "Switch to the next variant"));
+        lines
+    }
+
     fn modals_lines(&self) -> Vec<Line<'static>> {
         let mut lines = Vec::new();
         lines.push(Line::default());
@@ -171,6 +187,7 @@ impl<'a> HelpBuilder<'a> {
         lines.extend(self.lines(&["Tab"], "Focus next field"));
         lines.extend(self.lines(&["Shift+Tab"], "Focus previous field"));
         lines.extend(self.lines(&["Space"], "Toggle a checkbox"));
+        lines.extend(self.lines(&["←", "→"], "Change a selector value"));
         lines.extend(self.lines(&["Esc"], "Close an active modal"));
         lines
     }
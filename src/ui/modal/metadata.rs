@@ -0,0 +1,252 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::layout::{HorizontalAlignment, Margin};
+use ratatui::text::Span;
+use ratatui::{layout::Layout, prelude::Frame};
+use ratatui::{
+    layout::{Constraint, Flex, Rect},
+    style::Stylize as _,
+    symbols,
+    text::Line,
+    widgets::{Block, Paragraph, Widget as _},
+};
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler as _;
+
+use crate::model::{CoverArt, MetadataData, MetadataTags};
+use crate::ui::modal::{KeyboardHandler, ModalResult, UiModal};
+use crate::ui::widget::BgClear;
+use crate::ui::{Theme, checkbox_line, is_portrait, render_input, selector_line};
+
+const INPUTS: usize = 9;
+const LANGUAGE: usize = 5;
+const COVER_PATH: usize = 6;
+const COVER: usize = 7;
+const STRIP: usize = 8;
+
+#[derive(Debug)]
+pub(crate) struct MetadataModal {
+    active_input: usize,
+    /// Tag inputs in `MetadataTags::KEYS` order, then the cover path
+    inputs: [Input; 7],
+    cover: CoverArt,
+    strip: bool,
+    source: MetadataTags,
+    source_cover: bool,
+    error: Option<String>,
+}
+
+impl UiModal for MetadataModal {
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let area = frame.area();
+        let portrait = is_portrait(area);
+        let [modal_area] = Layout::vertical([Constraint::Length(20)])
+            .horizontal_margin(if portrait { 1 } else { area.width / 5 })
+            .flex(Flex::Center)
+            .areas(area);
+        let [
+            title_area,
+            artist_row,
+            comment_area,
+            date_row,
+            cover_path_area,
+            options_area,
+            hints_area,
+        ] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::SpaceBetween)
+        .areas(modal_area.inner(Margin::new(2, 1)));
+        let [artist_area, album_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(artist_row);
+        let [date_area, language_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(date_row);
+        let [cover_area, strip_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(options_area);
+
+        frame.render_widget(BgClear::new(theme.background_color()), modal_area);
+        Block::bordered()
+            .title("Metadata".fg(theme.modal_title_color()))
+            .border_set(symbols::border::THICK)
+            .border_style(theme.border_modal_style())
+            .render(modal_area, frame.buffer_mut());
+
+        let active = self.active_input;
+        let inputs = [
+            (title_area, "Title"),
+            (artist_area, "Artist"),
+            (album_area, "Album"),
+            (comment_area, "Comment"),
+            (date_area, "Date"),
+            (language_area, "Language (eng, deu, ...)"),
+            (cover_path_area, "Cover image (for Replace)"),
+        ];
+        for (i, (input_area, label)) in inputs.into_iter().enumerate() {
+            render_input(
+                frame,
+                input_area,
+                &self.inputs[i],
+                label,
+                active == i,
+                theme,
+            );
+        }
+
+        let cover_label = if self.source_cover || self.cover != CoverArt::Keep {
+            self.cover.id()
+        } else {
+            "none"
+        };
+        let lines = [
+            (
+                cover_area,
+                selector_line("Audio cover", cover_label, active == COVER, theme),
+            ),
+            (
+                strip_area,
+                checkbox_line(self.strip, "Strip all", active == STRIP, theme),
+            ),
+        ];
+        for (line_area, line) in lines {
+            Paragraph::new(line)
+                .alignment(HorizontalAlignment::Center)
+                .render(line_area, frame.buffer_mut());
+        }
+        self.render_status(hints_area, frame, theme);
+    }
+}
+
+impl KeyboardHandler for MetadataModal {
+    fn handle_key(&mut self, key: KeyEvent) -> ModalResult {
+        self.error.take();
+        match (self.active_input, key.code) {
+            (_, KeyCode::Esc) => return ModalResult::Close,
+            (_, KeyCode::BackTab) => self.active_input = (self.active_input + INPUTS - 1) % INPUTS,
+            (_, KeyCode::Tab) => self.active_input = (self.active_input + 1) % INPUTS,
+            (_, KeyCode::Enter) => {
+                return match self.validate() {
+                    Ok(()) => ModalResult::Metadata,
+                    Err(msg) => {
+                        self.error = Some(msg.to_owned());
+                        ModalResult::None
+                    }
+                };
+            }
+            (COVER, KeyCode::Left | KeyCode::Char('h')) => self.cover = self.cover.prev(),
+            (COVER, KeyCode::Right | KeyCode::Char('l' | ' ')) => self.cover = self.cover.next(),
+            (STRIP, KeyCode::Char(' ')) => self.strip = !self.strip,
+            (index, KeyCode::Char(x)) => {
+                let allowed = match index {
+                    LANGUAGE => x.is_ascii_lowercase() && self.inputs[LANGUAGE].value().len() < 3,
+                    _ => true,
+                };
+                if allowed && let Some(input) = self.inputs.get_mut(index) {
+                    input.handle_event(&Event::Key(key));
+                }
+            }
+            (
+                index,
+                KeyCode::Backspace
+                | KeyCode::Delete
+                | KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::Home
+                | KeyCode::End,
+            ) => {
+                if let Some(input) = self.inputs.get_mut(index) {
+                    input.handle_event(&Event::Key(key));
+                }
+            }
+            _ => {}
+        }
+        ModalResult::None
+    }
+}
+
+impl MetadataModal {
+    pub fn new(data: MetadataData) -> Self {
+        let tag = |key| Input::new(data.tags.get(key).to_owned());
+        Self {
+            active_input: 0,
+            inputs: [
+                tag("title"),
+                tag("artist"),
+                tag("album"),
+                tag("comment"),
+                tag("date"),
+                tag("language"),
+                Input::new(data.cover_path.unwrap_or_default()),
+            ],
+            cover: data.cover,
+            strip: data.strip,
+            source: data.source,
+            source_cover: data.source_cover,
+            error: None,
+        }
+    }
+
+    fn validate(&self) -> Result<(), &'static str> {
+        MetadataTags::validate(self.inputs[LANGUAGE].value())?;
+        let path = self.inputs[COVER_PATH].value().trim();
+        if self.cover == CoverArt::Replace && (path.is_empty() || std::fs::metadata(path).is_err())
+        {
+            return Err("Cover image does not exist");
+        }
+        Ok(())
+    }
+
+    fn render_status(&self, area: Rect, frame: &mut Frame, theme: &Theme) {
+        let line = if let Some(error) = &self.error {
+            Line::from(Span::styled(error, theme.error_style().bold())).centered()
+        } else {
+            let key_style = theme.key_style();
+            let text_style = theme.text_color();
+            let mut parts = vec![
+                Span::styled("Enter", key_style),
+                Span::styled(": confirm  ", text_style),
+                Span::styled("Esc", key_style),
+                Span::styled(": close  ", text_style),
+                Span::styled("Tab", key_style),
+                Span::styled(": switch focus", text_style),
+            ];
+            match self.active_input {
+                COVER => parts.append(&mut vec![
+                    Span::styled("  ←/→", key_style),
+                    Span::styled(": change", text_style),
+                ]),
+                STRIP => parts.append(&mut vec![
+                    Span::styled("  Space", key_style),
+                    Span::styled(": toggle", text_style),
+                ]),
+                _ => {}
+            }
+            Line::from(parts)
+        };
+        frame.render_widget(Paragraph::new(line), area);
+    }
+}
+
+impl From<&MetadataModal> for MetadataData {
+    fn from(modal: &MetadataModal) -> MetadataData {
+        let mut tags = MetadataTags::default();
+        for (i, key) in MetadataTags::KEYS.into_iter().enumerate() {
+            if let Some(tag) = tags.get_mut(key) {
+                modal.inputs[i].value().trim().clone_into(tag);
+            }
+        }
+        let cover_path = modal.inputs[COVER_PATH].value().trim();
+        MetadataData {
+            strip: modal.strip,
+            tags,
+            source: modal.source.clone(),
+            cover: modal.cover,
+            cover_path: Some(cover_path.to_owned()).filter(|v| !v.is_empty()),
+            source_cover: modal.source_cover,
+        }
+    }
+}
@@ -11,6 +11,22 @@ pub(crate) enum ModalResult {
     Filename(String),
     /// Trim modal
     Trim,
+    /// Overlay modal
+    Overlay,
+    /// Text overlay modal
+    Text,
+    /// Aspect ratio modal
+    Aspect,
+    /// Audio equalizer modal
+    Eq,
+    /// Silence removal modal
+    Silence,
+    /// Metadata modal
+    Metadata,
+    /// Output split modal
+    Split,
+    /// Image output modal
+    Frames,
     /// Modal for custom parameter values
     CustomSelect(String),
     /// Copy command (y y)
@@ -21,6 +37,8 @@ pub(crate) enum ModalResult {
     CopyInfo,
     /// Copy output (y o)
     CopyOutput,
+    /// Remove variant confirmed (x y)
+    RemoveVariant,
 }
 
 pub(crate) trait UiModal: Any + KeyboardHandler {
@@ -38,15 +56,33 @@ impl dyn UiModal {
 }
 
 mod alert;
+mod aspect;
+mod confirm;
 mod copy;
 mod custom_select;
+mod eq;
+mod frames;
 mod help;
+mod metadata;
+mod overlay;
 mod save_as_file;
+mod silence;
+mod split;
+mod text;
 mod trim;
 
 pub(crate) use alert::{AlertKind, AlertModal};
+pub(crate) use aspect::AspectModal;
+pub(crate) use confirm::ConfirmModal;
 pub(crate) use copy::CopyModal;
 pub(crate) use custom_select::CustomSelectModal;
+pub(crate) use eq::EqModal;
+pub(crate) use frames::FramesModal;
 pub(crate) use help::HelpModal;
-pub(crate) use save_as_file::SaveAsFileModal;
+pub(crate) use metadata::MetadataModal;
+pub(crate) use overlay::OverlayModal;
+pub(crate) use save_as_file::{SaveAsFileModal, VariantFiles};
+pub(crate) use silence::SilenceModal;
+pub(crate) use split::SplitModal;
+pub(crate) use text::TextModal;
 pub(crate) use trim::TrimModal;
@@ -0,0 +1,227 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::layout::{HorizontalAlignment, Margin};
+use ratatui::text::Span;
+use ratatui::{layout::Layout, prelude::Frame};
+use ratatui::{
+    layout::{Constraint, Flex, Rect},
+    style::Stylize as _,
+    symbols,
+    text::Line,
+    widgets::{Block, Paragraph, Widget as _},
+};
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler as _;
+
+use crate::model::{OverlayData, OverlayPosition};
+use crate::ui::modal::{KeyboardHandler, ModalResult, UiModal};
+use crate::ui::widget::BgClear;
+use crate::ui::{Theme, is_portrait, render_input, selector_line};
+
+const INPUTS: usize = 5;
+
+#[derive(Debug)]
+pub(crate) struct OverlayModal {
+    active_input: usize,
+    path: Input,
+    margin: Input,
+    scale: Input,
+    opacity: Input,
+    position: OverlayPosition,
+    error: Option<String>,
+}
+
+impl UiModal for OverlayModal {
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let area = frame.area();
+        let portrait = is_portrait(area);
+        let [modal_area] = Layout::vertical([Constraint::Length(11)])
+            .horizontal_margin(if portrait { 1 } else { area.width / 5 })
+            .flex(Flex::Center)
+            .areas(area);
+        let [path_area, numbers_area, position_area, hints_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::SpaceBetween)
+        .areas(modal_area.inner(Margin::new(2, 1)));
+        let [margin_area, scale_area, opacity_area] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ])
+        .areas(numbers_area);
+
+        frame.render_widget(BgClear::new(theme.background_color()), modal_area);
+        Block::bordered()
+            .title("Overlay".fg(theme.modal_title_color()))
+            .border_set(symbols::border::THICK)
+            .border_style(theme.border_modal_style())
+            .render(modal_area, frame.buffer_mut());
+
+        let active = self.active_input;
+        render_input(frame, path_area, &self.path, "Image", active == 0, theme);
+        render_input(
+            frame,
+            margin_area,
+            &self.margin,
+            "Margin, px",
+            active == 1,
+            theme,
+        );
+        render_input(
+            frame,
+            scale_area,
+            &self.scale,
+            "Height, % (0 - orig)",
+            active == 2,
+            theme,
+        );
+        render_input(
+            frame,
+            opacity_area,
+            &self.opacity,
+            "Opacity, %",
+            active == 3,
+            theme,
+        );
+
+        let position_line = selector_line("Position", self.position.label(), active == 4, theme);
+        Paragraph::new(position_line)
+            .alignment(HorizontalAlignment::Center)
+            .render(position_area, frame.buffer_mut());
+        self.render_status(hints_area, frame, theme);
+    }
+}
+
+impl KeyboardHandler for OverlayModal {
+    fn handle_key(&mut self, key: KeyEvent) -> ModalResult {
+        self.error.take();
+        match key.code {
+            KeyCode::Esc => return ModalResult::Close,
+            KeyCode::BackTab => self.active_input = (self.active_input + INPUTS - 1) % INPUTS,
+            KeyCode::Tab => self.active_input = (self.active_input + 1) % INPUTS,
+            KeyCode::Enter => {
+                return match OverlayData::validate(
+                    self.path.value().trim(),
+                    self.margin.value(),
+                    self.scale.value(),
+                    self.opacity.value(),
+                ) {
+                    Ok(_) => ModalResult::Overlay,
+                    Err(msg) => {
+                        self.error = Some(msg.to_owned());
+                        ModalResult::None
+                    }
+                };
+            }
+            KeyCode::Left | KeyCode::Char('h') if self.active_input == 4 => {
+                self.position = self.position.prev();
+            }
+            KeyCode::Right | KeyCode::Char('l' | ' ') if self.active_input == 4 => {
+                self.position = self.position.next();
+            }
+            KeyCode::Char(x) => match (self.active_input, x) {
+                (0, _) => {
+                    self.path.handle_event(&Event::Key(key));
+                }
+                (1..=3, '0'..='9') => {
+                    if let Some(input) = self.active_number_input()
+                        && input.value().len() < 4
+                    {
+                        input.handle_event(&Event::Key(key));
+                    }
+                }
+                _ => {}
+            },
+            KeyCode::Backspace
+            | KeyCode::Delete
+            | KeyCode::Left
+            | KeyCode::Right
+            | KeyCode::Home
+            | KeyCode::End => {
+                if self.active_input == 0 {
+                    self.path.handle_event(&Event::Key(key));
+                } else if let Some(input) = self.active_number_input() {
+                    input.handle_event(&Event::Key(key));
+                }
+            }
+            _ => {}
+        }
+        ModalResult::None
+    }
+}
+
+impl OverlayModal {
+    pub fn new(data: OverlayData) -> Self {
+        Self {
+            active_input: 0,
+            path: Input::new(data.path.unwrap_or_default()),
+            margin: Input::new(data.margin.to_string()),
+            scale: Input::new(data.scale.to_string()),
+            opacity: Input::new(data.opacity.to_string()),
+            position: data.position,
+            error: None,
+        }
+    }
+
+    fn active_number_input(&mut self) -> Option<&mut Input> {
+        match self.active_input {
+            1 => Some(&mut self.margin),
+            2 => Some(&mut self.scale),
+            3 => Some(&mut self.opacity),
+            _ => None,
+        }
+    }
+
+    fn render_status(&self, area: Rect, frame: &mut Frame, theme: &Theme) {
+        let line = if let Some(error) = &self.error {
+            Line::from(Span::styled(error, theme.error_style().bold())).centered()
+        } else {
+            let key_style = theme.key_style();
+            let text_style = theme.text_color();
+            let mut parts = vec![
+                Span::styled("Enter", key_style),
+                Span::styled(": confirm  ", text_style),
+                Span::styled("Esc", key_style),
+                Span::styled(": close  ", text_style),
+                Span::styled("Tab", key_style),
+                Span::styled(": switch focus", text_style),
+            ];
+            if self.active_input == 4 {
+                parts.append(&mut vec![
+                    Span::styled("  ←/→", key_style),
+                    Span::styled(": change", text_style),
+                ]);
+            } else if self.active_input == 0 {
+                parts.append(&mut vec![Span::styled(
+                    "  Empty path removes the overlay",
+                    theme.text_muted_color(),
+                )]);
+            }
+            Line::from(parts)
+        };
+        frame.render_widget(Paragraph::new(line), area);
+    }
+}
+
+impl From<&OverlayModal> for OverlayData {
+    fn from(modal: &OverlayModal) -> OverlayData {
+        let path = modal.path.value().trim();
+        let (margin, scale, opacity) = OverlayData::validate(
+            path,
+            modal.margin.value(),
+            modal.scale.value(),
+            modal.opacity.value(),
+        )
+        .unwrap_or_default();
+        OverlayData {
+            path: Some(path.to_owned()).filter(|p| !p.is_empty()),
+            position: modal.position,
+            margin,
+            scale,
+            opacity,
+        }
+    }
+}
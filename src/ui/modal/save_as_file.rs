@@ -15,6 +15,7 @@ use tracing::debug;
 use tui_input::Input;
 use tui_input::backend::crossterm::EventHandler as _;
 
+use crate::model::OutputFiles;
 use crate::ui::modal::{KeyboardHandler, ModalResult, UiModal};
 use crate::ui::widget::BgClear;
 use crate::ui::{Theme, input_value_and_pos, is_portrait};
@@ -33,9 +34,20 @@ pub(crate) struct SaveAsFileModal {
     original_filename: Box<str>,
     folder: Box<str>,
     ext: Box<str>,
+    files: OutputFiles,
+    // Written by the other variants of the same command, their names don't change here
+    other_files: Vec<VariantFiles>,
     validation: ValidationResult,
 }
 
+/// Output files of a variant
+#[derive(Debug)]
+pub(crate) struct VariantFiles {
+    pub(crate) filename: String,
+    pub(crate) ext: String,
+    pub(crate) files: OutputFiles,
+}
+
 impl UiModal for SaveAsFileModal {
     fn render(&mut self, frame: &mut Frame, theme: &Theme) {
         let area = frame.area();
@@ -53,8 +65,13 @@ impl UiModal for SaveAsFileModal {
         let (display_value, x) = input_value_and_pos(&self.filename, input_area.width - 2);
 
         frame.render_widget(BgClear::new(theme.background_color()), modal_area);
+        let title = match (&self.files, self.files.count()) {
+            (OutputFiles::Frames(None), _) => "Render images as".to_owned(),
+            (_, 1) => "Render as".to_owned(),
+            (_, count) => format!("Render {count} files as"),
+        };
         Block::bordered()
-            .title("Render as".fg(theme.modal_title_color()))
+            .title(title.fg(theme.modal_title_color()))
             .border_set(symbols::border::THICK)
             .border_style(theme.border_modal_style())
             .render(modal_area, frame.buffer_mut());
@@ -99,12 +116,21 @@ impl KeyboardHandler for SaveAsFileModal {
 }
 
 impl SaveAsFileModal {
-    pub(crate) fn new(original_filename: &str, folder: &str, filename: &str, ext: &str) -> Self {
+    pub(crate) fn new(
+        original_filename: &str,
+        folder: &str,
+        filename: &str,
+        ext: &str,
+        files: OutputFiles,
+        other_files: Vec<VariantFiles>,
+    ) -> Self {
         Self {
             filename: Input::new(filename.to_owned()),
             original_filename: original_filename.into(),
             folder: folder.into(),
             ext: ext.into(),
+            files,
+            other_files,
             validation: ValidationResult::Reset,
         }
     }
@@ -113,8 +139,16 @@ impl SaveAsFileModal {
         let line = match &self.validation {
             ValidationResult::Exists => {
                 let error_style = theme.error_style().bold();
+                let exists = if self.files.count() > 1
+                    || self.files == OutputFiles::Frames(None)
+                    || !self.other_files.is_empty()
+                {
+                    "Some files already exist. Press "
+                } else {
+                    "File already exists. Press "
+                };
                 Line::from(vec![
-                    Span::styled("File already exists. Press ", error_style),
+                    Span::styled(exists, error_style),
                     Span::styled("Enter", theme.key_style()),
                     Span::styled(" again to overwrite", error_style),
                 ])
@@ -154,18 +188,75 @@ impl SaveAsFileModal {
         }
     }
 
+    /// The segment number placeholder like `{n:03}` is allowed in the filename
     fn contains_invalid_chars(filename: &str) -> bool {
+        let filename = OutputFiles::strip_number(filename);
         filename.starts_with('-')
             || filename.starts_with('~')
             || std::str::from_utf8(filename.as_bytes()).is_err()
             || Regex::new(r"[/\\|<>$:\x00-\x1F\x7F\x80-\x9F]+")
                 .unwrap()
-                .is_match(filename)
+                .is_match(&filename)
     }
 
+    /// Any of the files the render writes exists, including the other variants
     fn is_file_exists(&self, filename: &str) -> bool {
-        let mut path = PathBuf::new().join(self.folder.as_ref()).join(filename);
-        path.add_extension(self.ext.as_ref());
-        path.exists()
+        let exists = |filename: &str, ext: &str, files: &OutputFiles| {
+            files.names(filename).iter().any(|name| {
+                let mut path = PathBuf::new().join(self.folder.as_ref()).join(name);
+                path.add_extension(ext);
+                path.exists()
+            })
+        };
+        exists(filename, &self.ext, &self.files)
+            || self
+                .other_files
+                .iter()
+                .any(|other| exists(&other.filename, &other.ext, &other.files))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_other_variant_file_exists() {
+        let folder = std::env::temp_dir().join("effy_save_as_variants");
+        std::fs::create_dir_all(&folder).expect("temp folder");
+        let folder_name = folder.to_string_lossy();
+        let other = || VariantFiles {
+            filename: "in_out1".to_owned(),
+            ext: "mp3".to_owned(),
+            files: OutputFiles::Single,
+        };
+        let modal = SaveAsFileModal::new(
+            "in",
+            &folder_name,
+            "in_out",
+            "mp4",
+            OutputFiles::Single,
+            vec![other()],
+        );
+        let _ = std::fs::remove_file(folder.join("in_out1.mp3"));
+        assert!(!modal.is_file_exists("in_out"));
+
+        std::fs::write(folder.join("in_out1.mp3"), b"").expect("temp file");
+        let single = SaveAsFileModal::new(
+            "in",
+            &folder_name,
+            "in_out",
+            "mp4",
+            OutputFiles::Single,
+            Vec::new(),
+        );
+        let (exists, single_exists) = (
+            modal.is_file_exists("in_out"),
+            single.is_file_exists("in_out"),
+        );
+        let _ = std::fs::remove_dir_all(&folder);
+
+        assert!(exists, "the other variant would be overwritten");
+        assert!(!single_exists);
     }
 }
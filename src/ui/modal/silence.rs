@@ -0,0 +1,169 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{HorizontalAlignment, Margin};
+use ratatui::text::Span;
+use ratatui::{layout::Layout, prelude::Frame};
+use ratatui::{
+    layout::{Constraint, Flex, Rect},
+    style::Stylize as _,
+    symbols,
+    text::Line,
+    widgets::{Block, Paragraph, Widget as _},
+};
+
+use crate::model::SilenceData;
+use crate::ui::modal::{KeyboardHandler, ModalResult, UiModal};
+use crate::ui::widget::BgClear;
+use crate::ui::{Theme, is_portrait, selector_line};
+
+const INPUTS: usize = 3;
+const SCOPE: usize = 0;
+const THRESHOLD: usize = 1;
+const DURATION: usize = 2;
+
+#[derive(Debug)]
+pub(crate) struct SilenceModal {
+    active_input: usize,
+    data: SilenceData,
+}
+
+impl UiModal for SilenceModal {
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let area = frame.area();
+        let portrait = is_portrait(area);
+        let [modal_area] = Layout::vertical([Constraint::Length(9)])
+            .horizontal_margin(if portrait { 1 } else { area.width / 4 })
+            .flex(Flex::Center)
+            .areas(area);
+        let [scope_area, levels_area, note_area, hints_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::SpaceBetween)
+        .areas(modal_area.inner(Margin::new(2, 1)));
+        let [threshold_area, duration_area] =
+            Layout::horizontal([Constraint::Fill(1); 2]).areas(levels_area);
+
+        frame.render_widget(BgClear::new(theme.background_color()), modal_area);
+        Block::bordered()
+            .title("Remove Silence".fg(theme.modal_title_color()))
+            .border_set(symbols::border::THICK)
+            .border_style(theme.border_modal_style())
+            .render(modal_area, frame.buffer_mut());
+
+        let active = self.active_input;
+        let lines = [
+            (
+                scope_area,
+                selector_line("Remove", self.data.scope.label(), active == SCOPE, theme),
+            ),
+            (
+                threshold_area,
+                selector_line(
+                    "Below",
+                    &format!("{}dB", self.data.threshold),
+                    active == THRESHOLD,
+                    theme,
+                ),
+            ),
+            (
+                duration_area,
+                selector_line(
+                    "Longer than",
+                    &format!("{}s", self.data.duration),
+                    active == DURATION,
+                    theme,
+                ),
+            ),
+        ];
+        for (line_area, line) in lines {
+            Paragraph::new(line)
+                .alignment(HorizontalAlignment::Center)
+                .render(line_area, frame.buffer_mut());
+        }
+        Paragraph::new(self.note_line(theme))
+            .alignment(HorizontalAlignment::Center)
+            .render(note_area, frame.buffer_mut());
+        self.render_status(hints_area, frame, theme);
+    }
+}
+
+impl KeyboardHandler for SilenceModal {
+    fn handle_key(&mut self, key: KeyEvent) -> ModalResult {
+        let forward = match key.code {
+            KeyCode::Esc => return ModalResult::Close,
+            KeyCode::Enter => return ModalResult::Silence,
+            KeyCode::BackTab => {
+                self.active_input = (self.active_input + INPUTS - 1) % INPUTS;
+                return ModalResult::None;
+            }
+            KeyCode::Tab => {
+                self.active_input = (self.active_input + 1) % INPUTS;
+                return ModalResult::None;
+            }
+            KeyCode::Left | KeyCode::Char('h') => false,
+            KeyCode::Right | KeyCode::Char('l' | ' ') => true,
+            _ => return ModalResult::None,
+        };
+        match self.active_input {
+            SCOPE => {
+                self.data.scope = if forward {
+                    self.data.scope.next()
+                } else {
+                    self.data.scope.prev()
+                };
+            }
+            THRESHOLD => self.data.step_threshold(forward),
+            DURATION => self.data.step_duration(forward),
+            _ => {}
+        }
+        ModalResult::None
+    }
+}
+
+impl SilenceModal {
+    pub fn new(data: SilenceData) -> Self {
+        Self {
+            active_input: 0,
+            data,
+        }
+    }
+
+    /// Explain how the silence is found for the video
+    fn note_line<'a>(&self, theme: &'a Theme) -> Line<'a> {
+        let note = if !self.data.video {
+            "Silence is removed while encoding".to_owned()
+        } else if let Some(ranges) = self.data.detected_ranges() {
+            format!(
+                "{} silent parts found, the video is cut with them",
+                ranges.len()
+            )
+        } else {
+            "Silence will be detected after confirming to keep the video in sync".to_owned()
+        };
+        Line::from(note.fg(theme.text_muted_color()))
+    }
+
+    fn render_status(&self, area: Rect, frame: &mut Frame, theme: &Theme) {
+        let key_style = theme.key_style();
+        let text_style = theme.text_color();
+        let line = Line::from(vec![
+            Span::styled("Enter", key_style),
+            Span::styled(": confirm  ", text_style),
+            Span::styled("Esc", key_style),
+            Span::styled(": close  ", text_style),
+            Span::styled("Tab", key_style),
+            Span::styled(": switch focus  ", text_style),
+            Span::styled("←/→", key_style),
+            Span::styled(": change", text_style),
+        ]);
+        frame.render_widget(Paragraph::new(line), area);
+    }
+}
+
+impl From<&SilenceModal> for SilenceData {
+    fn from(modal: &SilenceModal) -> SilenceData {
+        modal.data.clone()
+    }
+}
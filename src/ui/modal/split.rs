@@ -0,0 +1,162 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{HorizontalAlignment, Margin};
+use ratatui::text::Span;
+use ratatui::{layout::Layout, prelude::Frame};
+use ratatui::{
+    layout::{Constraint, Flex, Rect},
+    style::Stylize as _,
+    symbols,
+    text::Line,
+    widgets::{Block, Paragraph, Widget as _},
+};
+
+use crate::model::{SplitData, SplitMode};
+use crate::ui::modal::{KeyboardHandler, ModalResult, UiModal};
+use crate::ui::widget::BgClear;
+use crate::ui::{Theme, is_portrait, selector_line};
+
+const INPUTS: usize = 2;
+const MODE: usize = 0;
+const VALUE: usize = 1;
+
+#[derive(Debug)]
+pub(crate) struct SplitModal {
+    active_input: usize,
+    data: SplitData,
+}
+
+impl UiModal for SplitModal {
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let area = frame.area();
+        let portrait = is_portrait(area);
+        let [modal_area] = Layout::vertical([Constraint::Length(9)])
+            .horizontal_margin(if portrait { 1 } else { area.width / 4 })
+            .flex(Flex::Center)
+            .areas(area);
+        let [mode_area, value_area, note_area, hints_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::SpaceBetween)
+        .areas(modal_area.inner(Margin::new(2, 1)));
+
+        frame.render_widget(BgClear::new(theme.background_color()), modal_area);
+        Block::bordered()
+            .title("Split Output".fg(theme.modal_title_color()))
+            .border_set(symbols::border::THICK)
+            .border_style(theme.border_modal_style())
+            .render(modal_area, frame.buffer_mut());
+
+        let active = if self.data.value_label().is_some() {
+            self.active_input
+        } else {
+            MODE
+        };
+        let mut lines = vec![(
+            mode_area,
+            selector_line("Split", self.data.mode.label(), active == MODE, theme),
+        )];
+        if let Some(value) = self.data.value_label() {
+            let label = match self.data.mode {
+                SplitMode::Duration => "Every",
+                SplitMode::Size => "Up to",
+                _ => "Scene score above",
+            };
+            lines.push((
+                value_area,
+                selector_line(label, &value, active == VALUE, theme),
+            ));
+        }
+        for (line_area, line) in lines {
+            Paragraph::new(line)
+                .alignment(HorizontalAlignment::Center)
+                .render(line_area, frame.buffer_mut());
+        }
+        Paragraph::new(self.note_lines(theme))
+            .alignment(HorizontalAlignment::Center)
+            .render(note_area, frame.buffer_mut());
+        self.render_status(hints_area, frame, theme);
+    }
+}
+
+impl KeyboardHandler for SplitModal {
+    fn handle_key(&mut self, key: KeyEvent) -> ModalResult {
+        let forward = match key.code {
+            KeyCode::Esc => return ModalResult::Close,
+            KeyCode::Enter => return ModalResult::Split,
+            KeyCode::BackTab | KeyCode::Tab => {
+                if self.data.value_label().is_some() {
+                    self.active_input = (self.active_input + 1) % INPUTS;
+                }
+                return ModalResult::None;
+            }
+            KeyCode::Left | KeyCode::Char('h') => false,
+            KeyCode::Right | KeyCode::Char('l' | ' ') => true,
+            _ => return ModalResult::None,
+        };
+        if self.active_input == VALUE && self.data.value_label().is_some() {
+            self.data.step_value(forward);
+        } else {
+            self.data.step_mode(forward);
+        }
+        ModalResult::None
+    }
+}
+
+impl SplitModal {
+    pub fn new(data: SplitData) -> Self {
+        Self {
+            active_input: 0,
+            data,
+        }
+    }
+
+    /// Explain how the files are cut and named
+    fn note_lines<'a>(&self, theme: &'a Theme) -> Vec<Line<'a>> {
+        let note = match self.data.mode {
+            SplitMode::Off => "Single output file".to_owned(),
+            SplitMode::Chapters => format!("{} files, one per chapter", self.data.chapters),
+            SplitMode::Duration => "Key frames are forced at the cuts".to_owned(),
+            SplitMode::Size => "Segment length is estimated from the output bitrate".to_owned(),
+            SplitMode::Scene => match self.data.detected_scenes() {
+                Some(times) => format!("{} scene changes found", times.len()),
+                None => "Scene changes will be detected after confirming".to_owned(),
+            },
+        };
+        let mut lines = vec![Line::from(note.fg(theme.text_muted_color()))];
+        if matches!(
+            self.data.mode,
+            SplitMode::Duration | SplitMode::Size | SplitMode::Scene
+        ) {
+            lines.push(Line::from(
+                "Files are named {name}_{n:03} unless the name has {n}"
+                    .fg(theme.text_muted_color()),
+            ));
+        }
+        lines
+    }
+
+    fn render_status(&self, area: Rect, frame: &mut Frame, theme: &Theme) {
+        let key_style = theme.key_style();
+        let text_style = theme.text_color();
+        let line = Line::from(vec![
+            Span::styled("Enter", key_style),
+            Span::styled(": confirm  ", text_style),
+            Span::styled("Esc", key_style),
+            Span::styled(": close  ", text_style),
+            Span::styled("Tab", key_style),
+            Span::styled(": switch focus  ", text_style),
+            Span::styled("←/→", key_style),
+            Span::styled(": change", text_style),
+        ]);
+        frame.render_widget(Paragraph::new(line), area);
+    }
+}
+
+impl From<&SplitModal> for SplitData {
+    fn from(modal: &SplitModal) -> SplitData {
+        modal.data.clone()
+    }
+}
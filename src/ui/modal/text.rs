@@ -0,0 +1,267 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::layout::{HorizontalAlignment, Margin};
+use ratatui::text::Span;
+use ratatui::{layout::Layout, prelude::Frame};
+use ratatui::{
+    layout::{Constraint, Flex, Rect},
+    style::Stylize as _,
+    symbols,
+    text::Line,
+    widgets::{Block, Paragraph, Widget as _},
+};
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler as _;
+
+use crate::model::{OverlayPosition, TextData};
+use crate::ui::modal::{KeyboardHandler, ModalResult, UiModal};
+use crate::ui::widget::BgClear;
+use crate::ui::{Theme, checkbox_line, is_portrait, render_input, selector_line};
+
+const INPUTS: usize = 9;
+const POSITION: usize = 6;
+const BOXED: usize = 7;
+const TIMECODE: usize = 8;
+
+#[derive(Debug)]
+pub(crate) struct TextModal {
+    active_input: usize,
+    text: Input,
+    font: Input,
+    size: Input,
+    color: Input,
+    start: Input,
+    end: Input,
+    position: OverlayPosition,
+    boxed: bool,
+    timecode: bool,
+    error: Option<String>,
+}
+
+impl UiModal for TextModal {
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let area = frame.area();
+        let portrait = is_portrait(area);
+        let [modal_area] = Layout::vertical([Constraint::Length(14)])
+            .horizontal_margin(if portrait { 1 } else { area.width / 5 })
+            .flex(Flex::Center)
+            .areas(area);
+        let [text_area, style_area, time_area, options_area, hints_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::SpaceBetween)
+        .areas(modal_area.inner(Margin::new(2, 1)));
+        let [font_area, size_area, color_area] = Layout::horizontal([
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ])
+        .areas(style_area);
+        let [start_area, end_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(time_area);
+        let [position_area, boxed_area, timecode_area] = Layout::horizontal([
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ])
+        .areas(options_area);
+
+        frame.render_widget(BgClear::new(theme.background_color()), modal_area);
+        Block::bordered()
+            .title("Text".fg(theme.modal_title_color()))
+            .border_set(symbols::border::THICK)
+            .border_style(theme.border_modal_style())
+            .render(modal_area, frame.buffer_mut());
+
+        let active = self.active_input;
+        render_input(frame, text_area, &self.text, "Text", active == 0, theme);
+        render_input(
+            frame,
+            font_area,
+            &self.font,
+            "Font file (optional)",
+            active == 1,
+            theme,
+        );
+        render_input(frame, size_area, &self.size, "Size", active == 2, theme);
+        render_input(frame, color_area, &self.color, "Color", active == 3, theme);
+        render_input(
+            frame,
+            start_area,
+            &self.start,
+            "Show from",
+            active == 4,
+            theme,
+        );
+        render_input(frame, end_area, &self.end, "Show until", active == 5, theme);
+
+        let lines = [
+            (
+                position_area,
+                selector_line("Position", self.position.label(), active == POSITION, theme),
+            ),
+            (
+                boxed_area,
+                checkbox_line(self.boxed, "Box", active == BOXED, theme),
+            ),
+            (
+                timecode_area,
+                checkbox_line(self.timecode, "Timecode", active == TIMECODE, theme),
+            ),
+        ];
+        for (line_area, line) in lines {
+            Paragraph::new(line)
+                .alignment(HorizontalAlignment::Center)
+                .render(line_area, frame.buffer_mut());
+        }
+        self.render_status(hints_area, frame, theme);
+    }
+}
+
+impl KeyboardHandler for TextModal {
+    fn handle_key(&mut self, key: KeyEvent) -> ModalResult {
+        self.error.take();
+        match (self.active_input, key.code) {
+            (_, KeyCode::Esc) => return ModalResult::Close,
+            (_, KeyCode::BackTab) => self.active_input = (self.active_input + INPUTS - 1) % INPUTS,
+            (_, KeyCode::Tab) => self.active_input = (self.active_input + 1) % INPUTS,
+            (_, KeyCode::Enter) => {
+                return match self.validate() {
+                    Ok(_) => ModalResult::Text,
+                    Err(msg) => {
+                        self.error = Some(msg.to_owned());
+                        ModalResult::None
+                    }
+                };
+            }
+            (POSITION, KeyCode::Left | KeyCode::Char('h')) => {
+                self.position = self.position.prev();
+            }
+            (POSITION, KeyCode::Right | KeyCode::Char('l' | ' ')) => {
+                self.position = self.position.next();
+            }
+            (BOXED, KeyCode::Char(' ')) => self.boxed = !self.boxed,
+            (TIMECODE, KeyCode::Char(' ')) => self.timecode = !self.timecode,
+            (index, KeyCode::Char(x)) => {
+                let allowed = match index {
+                    0 | 1 => true,
+                    2 => x.is_ascii_digit() && self.size.value().len() < 3,
+                    3 => {
+                        (x.is_ascii_alphanumeric() || matches!(x, '#' | '@' | '.'))
+                            && self.color.value().len() < 16
+                    }
+                    4 | 5 => x.is_ascii_digit() || matches!(x, '.' | ':'),
+                    _ => false,
+                };
+                if allowed && let Some(input) = self.active_text_input() {
+                    input.handle_event(&Event::Key(key));
+                }
+            }
+            (
+                _,
+                KeyCode::Backspace
+                | KeyCode::Delete
+                | KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::Home
+                | KeyCode::End,
+            ) => {
+                if let Some(input) = self.active_text_input() {
+                    input.handle_event(&Event::Key(key));
+                }
+            }
+            _ => {}
+        }
+        ModalResult::None
+    }
+}
+
+impl TextModal {
+    pub fn new(data: TextData) -> Self {
+        Self {
+            active_input: 0,
+            text: Input::new(data.text),
+            font: Input::new(data.font.unwrap_or_default()),
+            size: Input::new(data.size.to_string()),
+            color: Input::new(data.color),
+            start: Input::new(data.start.unwrap_or_default()),
+            end: Input::new(data.end.unwrap_or_default()),
+            position: data.position,
+            boxed: data.boxed,
+            timecode: data.timecode,
+            error: None,
+        }
+    }
+
+    fn validate(&self) -> Result<u32, &'static str> {
+        TextData::validate(
+            self.font.value().trim(),
+            self.size.value(),
+            self.color.value(),
+            self.start.value(),
+            self.end.value(),
+        )
+    }
+
+    fn active_text_input(&mut self) -> Option<&mut Input> {
+        match self.active_input {
+            0 => Some(&mut self.text),
+            1 => Some(&mut self.font),
+            2 => Some(&mut self.size),
+            3 => Some(&mut self.color),
+            4 => Some(&mut self.start),
+            5 => Some(&mut self.end),
+            _ => None,
+        }
+    }
+
+    fn render_status(&self, area: Rect, frame: &mut Frame, theme: &Theme) {
+        let line = if let Some(error) = &self.error {
+            Line::from(Span::styled(error, theme.error_style().bold())).centered()
+        } else {
+            let key_style = theme.key_style();
+            let text_style = theme.text_color();
+            let mut parts = vec![
+                Span::styled("Enter", key_style),
+                Span::styled(": confirm  ", text_style),
+                Span::styled("Esc", key_style),
+                Span::styled(": close  ", text_style),
+                Span::styled("Tab", key_style),
+                Span::styled(": switch focus", text_style),
+            ];
+            match self.active_input {
+                POSITION => parts.append(&mut vec![
+                    Span::styled("  ←/→", key_style),
+                    Span::styled(": change", text_style),
+                ]),
+                BOXED | TIMECODE => parts.append(&mut vec![
+                    Span::styled("  Space", key_style),
+                    Span::styled(": toggle", text_style),
+                ]),
+                _ => {}
+            }
+            Line::from(parts)
+        };
+        frame.render_widget(Paragraph::new(line), area);
+    }
+}
+
+impl From<&TextModal> for TextData {
+    fn from(modal: &TextModal) -> TextData {
+        let non_empty = |v: &str| Some(v.trim().to_owned()).filter(|v| !v.is_empty());
+        TextData {
+            text: modal.text.value().to_owned(),
+            timecode: modal.timecode,
+            font: non_empty(modal.font.value()),
+            size: modal.validate().unwrap_or(TextData::default().size),
+            color: modal.color.value().to_owned(),
+            boxed: modal.boxed,
+            position: modal.position,
+            start: non_empty(modal.start.value()),
+            end: non_empty(modal.end.value()),
+        }
+    }
+}
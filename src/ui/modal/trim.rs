@@ -14,10 +14,15 @@ use ratatui::{
 use tui_input::Input;
 use tui_input::backend::crossterm::EventHandler as _;
 
+use crate::info::{InfoChapter, format_timestamp};
 use crate::model::TrimData;
+use crate::params::Trim;
 use crate::ui::modal::{KeyboardHandler, ModalResult, UiModal};
 use crate::ui::widget::BgClear;
-use crate::ui::{Theme, checkbox_line, input_value_and_pos, is_portrait};
+use crate::ui::{Theme, checkbox_line, input_value_and_pos, is_portrait, selector_line};
+
+const FROM_CHAPTER: usize = 4;
+const TO_CHAPTER: usize = 5;
 
 #[derive(Debug)]
 pub(crate) struct TrimModal {
@@ -27,6 +32,10 @@ pub(crate) struct TrimModal {
     precise: bool,
     use_to: bool, // -t or -to
     duration: Option<f64>,
+    /// Title, start and end of the input chapters
+    chapters: Vec<(String, f64, f64)>,
+    from_chapter: Option<usize>,
+    to_chapter: Option<usize>,
     error: Option<String>,
 }
 
@@ -34,13 +43,15 @@ impl UiModal for TrimModal {
     fn render(&mut self, frame: &mut Frame, theme: &Theme) {
         let area = frame.area();
         let portrait = is_portrait(area);
-        let [modal_area] = Layout::vertical([Constraint::Length(8)])
+        let chapters_height = if self.chapters.is_empty() { 0 } else { 2 };
+        let [modal_area] = Layout::vertical([Constraint::Length(8 + chapters_height)])
             .horizontal_margin(if portrait { 1 } else { area.width / 5 })
             .flex(Flex::Center)
             .areas(area);
-        let [inputs_area, chackbox_area, hints_area] = Layout::vertical([
+        let [inputs_area, chackbox_area, chapters_area, hints_area] = Layout::vertical([
             Constraint::Length(3),
             Constraint::Length(2),
+            Constraint::Length(chapters_height),
             Constraint::Length(1),
         ])
         .flex(Flex::SpaceBetween)
@@ -77,8 +88,10 @@ impl UiModal for TrimModal {
             .border_style(theme.border_modal_style())
             .render(modal_area, frame.buffer_mut());
 
-        let mut borders = [inactive_border_style; 4];
-        borders[self.active_input] = active_border_style;
+        let mut borders = [inactive_border_style; 2];
+        if let Some(border) = borders.get_mut(self.active_input) {
+            *border = active_border_style;
+        }
         // Inputs
         Paragraph::new(ss_value)
             .block(
@@ -132,6 +145,25 @@ impl UiModal for TrimModal {
         Paragraph::new(use_to_line)
             .alignment(HorizontalAlignment::Center)
             .render(use_to_area, frame.buffer_mut());
+        if !self.chapters.is_empty() {
+            let [from_area, to_area] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(chapters_area);
+            let lines = [
+                (from_area, "From chapter", self.from_chapter, FROM_CHAPTER),
+                (to_area, "To chapter", self.to_chapter, TO_CHAPTER),
+            ];
+            for (line_area, label, chapter, index) in lines {
+                let value = self.chapter_label(chapter);
+                Paragraph::new(selector_line(
+                    label,
+                    &value,
+                    self.active_input == index,
+                    theme,
+                ))
+                .alignment(HorizontalAlignment::Center)
+                .render(line_area, frame.buffer_mut());
+            }
+        }
         self.render_status(hints_area, frame, theme);
     }
 }
@@ -139,10 +171,17 @@ impl UiModal for TrimModal {
 impl KeyboardHandler for TrimModal {
     fn handle_key(&mut self, key: KeyEvent) -> ModalResult {
         self.error.take();
+        let inputs = if self.chapters.is_empty() { 4 } else { 6 };
         match key.code {
             KeyCode::Esc => return ModalResult::Close,
-            KeyCode::BackTab => self.active_input = (self.active_input + 3) % 4,
-            KeyCode::Tab => self.active_input = (self.active_input + 1) % 4,
+            KeyCode::BackTab => self.active_input = (self.active_input + inputs - 1) % inputs,
+            KeyCode::Tab => self.active_input = (self.active_input + 1) % inputs,
+            KeyCode::Left | KeyCode::Char('h') if self.active_input >= FROM_CHAPTER => {
+                self.change_chapter(false);
+            }
+            KeyCode::Right | KeyCode::Char('l' | ' ') if self.active_input >= FROM_CHAPTER => {
+                self.change_chapter(true);
+            }
             KeyCode::Char(x) => match (self.active_input, x) {
                 (0, '0'..='9' | '.' | ':' | '%' | '-')
                     if Self::prevalidate_value(x, self.ss.value()) =>
@@ -183,7 +222,25 @@ impl KeyboardHandler for TrimModal {
 }
 
 impl TrimModal {
-    pub fn new(data: TrimData, duration: Option<f64>) -> Self {
+    pub fn new(data: TrimData, duration: Option<f64>, chapters: &[InfoChapter]) -> Self {
+        let chapters: Vec<_> = chapters
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.title(i), c.start(), c.end()))
+            .collect();
+        // Preselect the chapters matching the current range
+        let find = |value: Option<&String>, time: fn(&(String, f64, f64)) -> f64| {
+            let seconds = Trim::to_seconds(value?);
+            chapters
+                .iter()
+                .position(|c| (time(c) - seconds).abs() < 0.001)
+        };
+        let from_chapter = find(data.ss.as_ref(), |c| c.1);
+        let to_chapter = if data.use_to {
+            find(data.to.as_ref(), |c| c.2)
+        } else {
+            None
+        };
         Self {
             active_input: 0,
             ss: Input::new(data.ss.unwrap_or_default()),
@@ -191,9 +248,52 @@ impl TrimModal {
             precise: data.precise,
             use_to: data.use_to,
             duration,
+            chapters,
+            from_chapter,
+            to_chapter,
             error: None,
         }
     }
+
+    fn chapter_label(&self, chapter: Option<usize>) -> String {
+        chapter
+            .and_then(|i| Some((i, self.chapters.get(i)?)))
+            .map_or_else(|| "-".to_owned(), |(i, c)| format!("{}. {}", i + 1, c.0))
+    }
+
+    /// Select the previous or next chapter and set the range to its boundaries
+    fn change_chapter(&mut self, forward: bool) {
+        let count = self.chapters.len();
+        if count == 0 {
+            return;
+        }
+        let step = |chapter: Option<usize>| match (chapter, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+        };
+        if self.active_input == FROM_CHAPTER {
+            let from = step(self.from_chapter);
+            self.from_chapter = Some(from);
+            if self.to_chapter.is_none_or(|to| to < from) {
+                self.to_chapter = Some(from);
+            }
+        } else {
+            let to = step(self.to_chapter);
+            self.to_chapter = Some(to);
+            if self.from_chapter.is_none_or(|from| from > to) {
+                self.from_chapter = Some(to);
+            }
+        }
+        if let Some(from) = self.from_chapter {
+            self.ss = Input::new(format_timestamp(self.chapters[from].1));
+        }
+        if let Some(to) = self.to_chapter {
+            self.use_to = true;
+            self.to = Input::new(format_timestamp(self.chapters[to].2));
+        }
+    }
 }
 
 impl From<&TrimModal> for TrimData {
@@ -222,7 +322,12 @@ impl TrimModal {
                 Span::styled("Tab", key_style),
                 Span::styled(": switch focus", text_style),
             ];
-            if self.active_input > 1 {
+            if self.active_input >= FROM_CHAPTER {
+                parts.append(&mut vec![
+                    Span::styled("  ←/→", key_style),
+                    Span::styled(": change", text_style),
+                ]);
+            } else if self.active_input > 1 {
                 parts.append(&mut vec![
                     Span::styled("  Space", key_style),
                     Span::styled(": toggle", text_style),
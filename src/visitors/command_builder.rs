@@ -1,4 +1,7 @@
+use std::ops::Range;
+
 use crate::{
+    model::{OutputFiles, PackageFormat},
     params::*,
     visitors::{ParameterVisitor, VisitorContext},
 };
@@ -8,17 +11,125 @@ use crate::{
 #[derive(Debug, Default)]
 pub(crate) struct CommandBuilder {
     pub(crate) ctx: VisitorContext,
+    // Output variant rendered by the same command, its filter graph outputs are renamed
+    pub(crate) variant: usize,
+    // Extra inputs of the preceding variants, the indices of this one's follow them
+    pub(crate) input_offset: usize,
     // from params
     pub(crate) discard_audio: bool,
     pub(crate) hwaccel: HWAccel,
     pub(crate) speed_factor: Option<f64>,
+    // Change the audio pitch along with the speed
+    pub(crate) change_pitch: bool,
+    pub(crate) output_frame_rate: Option<f64>,
+    // Audio encoder, `-c:a` is added by the finalizer
+    pub(crate) audio_codec: Option<String>,
+    // Algorithm for software scale filters
+    pub(crate) scale_flags: Option<String>,
+    // Input repeated by the loop, applied by the finalizer once the filters are known
+    pub(crate) input_loop: Option<InputLoop>,
+    // The input is shared with variants trimmed differently, a trim seeks the output
+    pub(crate) seek_output: bool,
+    // Input seek position in seconds, timestamps start from zero after it
+    pub(crate) input_seek: Option<f64>,
+    // Source range kept by the trim, when its start is known
+    pub(crate) trim: Option<TrimRange>,
+    // Frames are dropped by timelapse or silence removal, source times don't match the output
+    pub(crate) timeline_changed: bool,
     pub(crate) audio_filters: Vec<String>,
     pub(crate) video_filters: Vec<String>,
+    // Filters with additional inputs, turn -vf into -filter_complex
+    pub(crate) complex_video_filters: Vec<ComplexFilter>,
     pub(crate) pre_input_args: Vec<String>,
+    pub(crate) extra_inputs: Vec<ExtraInput>,
+    // Input index the audio is mapped from, the source is 0
+    pub(crate) audio_input: usize,
+    // Input index of the cover art picture for audio outputs
+    pub(crate) cover_input: Option<usize>,
+    // Metadata option and key=value pairs, values are quoted
+    pub(crate) metadata: Vec<(String, String)>,
+    // Several output files instead of one, e.g. one per chapter
+    pub(crate) output_parts: Vec<OutputPart>,
+    // Estimated number of files written by the segment muxer
+    pub(crate) segments: Option<usize>,
+    // Numbered images of the image muxer, with the estimated count when it is known
+    pub(crate) frames: Option<Option<usize>>,
+    // Video streams of an adaptive streaming package, the filtered video is split between them
+    pub(crate) renditions: Vec<RenditionOutput>,
+    // Source links of the filter graph shared by the variants of one command
+    pub(crate) shared: Option<SharedSource>,
     pub(crate) args: Vec<String>,
     // Mainly for output codec
     pub(crate) pre_output_args: Vec<String>,
     pub(crate) ext: String,
+    // Shown to the user before rendering
+    pub(crate) warnings: Vec<String>,
+}
+
+/// Additional input file, e.g. an overlay image.
+/// Its index in the FFmpeg command is returned by `CommandBuilder::push_input`
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ExtraInput {
+    pub(crate) args: Vec<String>,
+    pub(crate) path: String,
+    // Open the source file again, `path` is ignored
+    pub(crate) source: bool,
+}
+
+/// One of several output files
+#[derive(Debug, Default, Clone)]
+pub(crate) struct OutputPart {
+    // Output options of the part, e.g. -ss/-to
+    pub(crate) args: Vec<String>,
+    // Appended to the output filename
+    pub(crate) suffix: String,
+}
+
+/// One video stream of several encoded from the same filtered video
+#[derive(Debug, Default, Clone)]
+pub(crate) struct RenditionOutput {
+    // Applied after the common video filters, e.g. scale
+    pub(crate) filter: String,
+    // Per stream output options, e.g. -b:v:0
+    pub(crate) args: Vec<String>,
+}
+
+/// Decoded source streams split between the variants rendered by one command
+#[derive(Debug, Default, Clone)]
+pub(crate) struct SharedSource {
+    // `[0:v]` or a `split` output, set when the variant filters the video
+    pub(crate) video: Option<String>,
+    // `[0:a]` or an `asplit` output, set when the variant filters the audio
+    pub(crate) audio: Option<String>,
+}
+
+/// Input played several times in a row
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct InputLoop {
+    // Plays after the first one
+    pub(crate) repeats: u32,
+    // Duration of a single play
+    pub(crate) duration: Option<f64>,
+    // The output is cut at the target duration
+    pub(crate) target: Option<f64>,
+}
+
+/// Source time range kept by the trim, in seconds
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct TrimRange {
+    pub(crate) start: f64,
+    pub(crate) end: Option<f64>,
+    // Position of the `-ss`/`-to` output options in `args` for a precise trim,
+    // the input is seeked otherwise
+    pub(crate) output_args: Option<Range<usize>>,
+}
+
+/// Part of a filter graph that is inserted into the video filters chain at `position`.
+/// `{in}` and `{out}` in the `graph` are replaced with the actual link labels
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ComplexFilter {
+    pub(crate) position: usize,
+    pub(crate) graph: String,
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -45,29 +156,491 @@ impl CommandBuilder {
         }
     }
 
+    /// Files the command writes, see `build_args` and `build_parts_args`
+    pub(crate) fn output_files(&self) -> OutputFiles {
+        if let Some(format) = PackageFormat::from_ext(&self.ext) {
+            OutputFiles::Package(format)
+        } else if let Some(count) = self.frames {
+            OutputFiles::Frames(count)
+        } else if let Some(count) = self.segments {
+            OutputFiles::Segments(count)
+        } else if !self.output_parts.is_empty() {
+            OutputFiles::Parts(
+                self.output_parts
+                    .iter()
+                    .map(|part| part.suffix.clone())
+                    .collect(),
+            )
+        } else {
+            OutputFiles::Single
+        }
+    }
+
+    /// Kept source range in seconds, the output timeline starts at its start
+    pub(crate) fn source_range(&self) -> (f64, Option<f64>) {
+        match &self.trim {
+            Some(trim) => (trim.start, trim.end),
+            None => (self.input_seek.unwrap_or(0.0), None),
+        }
+    }
+
     pub(crate) fn build_pre_input_args(&self) -> &[String] {
         &self.pre_input_args
     }
 
+    /// Add an input file, returns its index in the FFmpeg command
+    pub(crate) fn push_input(&mut self, input: ExtraInput) -> usize {
+        self.extra_inputs.push(input);
+        self.input_offset + self.extra_inputs.len()
+    }
+
+    /// Filter graph link, unique between the variants of the same command
+    pub(crate) fn label(&self, name: &str) -> String {
+        if self.variant == 0 {
+            format!("[{name}]")
+        } else {
+            format!("[o{}{name}]", self.variant)
+        }
+    }
+
+    /// Output label of the video filters chain
+    fn vout(&self) -> String {
+        self.label("vout")
+    }
+
+    /// Output label of the audio filters chain, one per audio stream when there are several
+    fn aout(&self, index: usize) -> String {
+        if self.audio_streams() == 1 {
+            self.label("aout")
+        } else {
+            self.label(&format!("aout{index}"))
+        }
+    }
+
+    /// Source video link, a `split` output when the graph is shared by the variants
+    fn video_input(&self) -> String {
+        self.shared
+            .as_ref()
+            .and_then(|shared| shared.video.clone())
+            .unwrap_or_else(|| "[0:v]".to_owned())
+    }
+
+    /// The source video goes through a filter graph
+    pub(crate) fn filters_video(&self) -> bool {
+        !self.video_filters.is_empty()
+            || !self.complex_video_filters.is_empty()
+            || !self.renditions.is_empty()
+    }
+
+    /// The source audio is filtered, the audio of another input is filtered by -af
+    pub(crate) fn filters_audio(&self) -> bool {
+        !self.discard_audio && self.audio_input == 0 && !self.audio_filters.is_empty()
+    }
+
+    /// Audio streams written, one per part or rendition. DASH shares one between the renditions
+    fn audio_streams(&self) -> usize {
+        if !self.output_parts.is_empty() {
+            self.output_parts.len()
+        } else if self.renditions.is_empty()
+            || matches!(
+                PackageFormat::from_ext(&self.ext),
+                Some(PackageFormat::Dash)
+            )
+        {
+            1
+        } else {
+            self.renditions.len()
+        }
+    }
+
     pub(crate) fn build_args(&self, quote: bool) -> Vec<String> {
         let mut args = Vec::new();
         args.extend(self.args.iter().cloned());
+        if self.shared.is_some() {
+            self.push_shared_maps(&mut args, None, quote);
+        } else {
+            self.push_audio_filters(&mut args, quote);
+            if !self.renditions.is_empty() {
+                self.push_renditions(&mut args, quote);
+            } else if !self.complex_video_filters.is_empty() {
+                args.push("-filter_complex".to_owned());
+                let graph = self.build_filter_complex();
+                args.push(if quote { quote_arg(&graph) } else { graph });
+                self.push_maps(&mut args, Some(&self.vout()), quote);
+            } else {
+                if !self.video_filters.is_empty() {
+                    args.push("-vf".to_owned());
+                    let vf = self.video_filters.join(",");
+                    args.push(if quote { quote_arg(&vf) } else { vf });
+                }
+                self.push_maps(&mut args, None, quote);
+            }
+        }
+        self.push_output_args(&mut args, quote);
+        args
+    }
+
+    /// Arguments of each output part, the video filters run once and are split between them
+    pub(crate) fn build_parts_args(&self, quote: bool) -> Vec<Vec<String>> {
+        let graph = self.parts_graph();
+        let mut result = Vec::new();
+        for (i, part) in self.output_parts.iter().enumerate() {
+            let mut args = Vec::new();
+            if i == 0
+                && self.shared.is_none()
+                && let Some(graph) = &graph
+            {
+                args.push("-filter_complex".to_owned());
+                args.push(if quote {
+                    quote_arg(graph)
+                } else {
+                    graph.clone()
+                });
+            }
+            args.extend(self.args.iter().cloned());
+            args.extend(part.args.iter().cloned());
+            if self.shared.is_some() {
+                self.push_shared_maps(&mut args, Some(i), quote);
+            } else {
+                self.push_audio_filters(&mut args, quote);
+                let label = self.label(&format!("vout{i}"));
+                self.push_maps(&mut args, graph.as_ref().map(|_| label.as_str()), quote);
+            }
+            self.push_output_args(&mut args, quote);
+            result.push(args);
+        }
+        result
+    }
+
+    /// Filtered video split between the output parts
+    fn parts_graph(&self) -> Option<String> {
+        let count = self.output_parts.len();
+        let vout = self.vout();
+        let graph = if !self.complex_video_filters.is_empty() {
+            self.build_filter_complex()
+        } else if !self.video_filters.is_empty() {
+            format!(
+                "{}{}{vout}",
+                self.video_input(),
+                self.video_filters.join(",")
+            )
+        } else {
+            return None;
+        };
+        let outputs: String = (0..count)
+            .map(|i| self.label(&format!("vout{i}")))
+            .collect();
+        Some(format!("{graph};{vout}split={count}{outputs}"))
+    }
+
+    /// Split the filtered video between the renditions, each one gets its own video and audio stream.
+    /// DASH shares the audio stream between them
+    fn push_renditions(&self, args: &mut Vec<String>, quote: bool) {
+        let count = self.renditions.len();
+        let graph = self.renditions_graph();
+        args.push("-filter_complex".to_owned());
+        args.push(if quote { quote_arg(&graph) } else { graph });
+        for i in 0..count {
+            args.push("-map".to_owned());
+            let label = self.label(&format!("r{i}"));
+            args.push(if quote { quote_arg(&label) } else { label });
+        }
+        if !self.discard_audio {
+            for _ in 0..self.audio_streams() {
+                args.push("-map".to_owned());
+                args.push(format!("{}:a?", self.audio_input));
+            }
+        }
+        for rendition in &self.renditions {
+            args.extend(rendition.args.iter().cloned());
+        }
+    }
+
+    fn renditions_graph(&self) -> String {
+        let count = self.renditions.len();
+        let vout = self.vout();
+        let (mut graph, input) = if !self.complex_video_filters.is_empty() {
+            (vec![self.build_filter_complex()], vout)
+        } else if !self.video_filters.is_empty() {
+            (
+                vec![format!(
+                    "{}{}{vout}",
+                    self.video_input(),
+                    self.video_filters.join(",")
+                )],
+                vout,
+            )
+        } else {
+            (Vec::new(), self.video_input())
+        };
+        let inputs: Vec<String> = if count > 1 {
+            let labels: Vec<String> = (0..count).map(|i| self.label(&format!("s{i}"))).collect();
+            graph.push(format!("{input}split={count}{}", labels.concat()));
+            labels
+        } else {
+            vec![input]
+        };
+        for (i, (rendition, input)) in self.renditions.iter().zip(inputs).enumerate() {
+            graph.push(format!(
+                "{input}{}{}",
+                rendition.filter,
+                self.label(&format!("r{i}"))
+            ));
+        }
+        graph.join(";")
+    }
+
+    /// Filter graph of the variants rendered by one command. The decoded source video and audio
+    /// are split between the variants that filter them, each variant adds its filters
+    pub(crate) fn share_source(mut builders: Vec<&mut CommandBuilder>) -> Option<String> {
+        let mut graph = Vec::new();
+        let video: Vec<usize> = (0..builders.len())
+            .filter(|i| builders[*i].filters_video())
+            .collect();
+        let links = Self::split_source(&mut graph, "[0:v]", "split", "sv", video.len());
+        for (i, link) in video.into_iter().zip(links) {
+            builders[i].shared.get_or_insert_default().video = Some(link);
+        }
+        let audio: Vec<usize> = (0..builders.len())
+            .filter(|i| builders[*i].filters_audio())
+            .collect();
+        let links = Self::split_source(&mut graph, "[0:a]", "asplit", "sa", audio.len());
+        for (i, link) in audio.into_iter().zip(links) {
+            builders[i].shared.get_or_insert_default().audio = Some(link);
+        }
+        for builder in &builders {
+            if builder.shared.is_some() {
+                graph.extend(builder.build_graph());
+            }
+        }
+        (!graph.is_empty()).then(|| graph.join(";"))
+    }
+
+    /// Links of the source stream for each of its readers, split when there are several
+    fn split_source(
+        graph: &mut Vec<String>,
+        input: &str,
+        filter: &str,
+        prefix: &str,
+        count: usize,
+    ) -> Vec<String> {
+        if count < 2 {
+            return vec![input.to_owned(); count];
+        }
+        let links: Vec<String> = (0..count).map(|i| format!("[{prefix}{i}]")).collect();
+        graph.push(format!("{input}{filter}={count}{}", links.concat()));
+        links
+    }
+
+    /// Filters of the variant in the shared graph, their outputs are mapped by `push_shared_maps`
+    fn build_graph(&self) -> Vec<String> {
+        let mut graph = Vec::new();
+        if self.filters_video() {
+            graph.push(if !self.renditions.is_empty() {
+                self.renditions_graph()
+            } else if !self.output_parts.is_empty()
+                && let Some(parts) = self.parts_graph()
+            {
+                parts
+            } else if !self.complex_video_filters.is_empty() {
+                self.build_filter_complex()
+            } else {
+                format!(
+                    "{}{}{}",
+                    self.video_input(),
+                    self.video_filters.join(","),
+                    self.vout()
+                )
+            });
+        }
+        if self.filters_audio() {
+            let input = self
+                .shared
+                .as_ref()
+                .and_then(|shared| shared.audio.clone())
+                .unwrap_or_else(|| "[0:a]".to_owned());
+            let chain = self.audio_filters.join(",");
+            let count = self.audio_streams();
+            graph.push(if count == 1 {
+                format!("{input}{chain}{}", self.aout(0))
+            } else {
+                let outputs: String = (0..count).map(|i| self.aout(i)).collect();
+                format!("{input}{chain},asplit={count}{outputs}")
+            });
+        }
+        graph
+    }
+
+    /// Stream mapping of a variant whose filters are in the shared graph, of a part when it is given
+    fn push_shared_maps(&self, args: &mut Vec<String>, part: Option<usize>, quote: bool) {
+        let map = |args: &mut Vec<String>, stream: String| {
+            args.push("-map".to_owned());
+            args.push(if quote && stream.starts_with('[') {
+                quote_arg(&stream)
+            } else {
+                stream
+            });
+        };
+        if !self.renditions.is_empty() {
+            for i in 0..self.renditions.len() {
+                map(args, self.label(&format!("r{i}")));
+            }
+        } else if self.filters_video() {
+            map(
+                args,
+                part.map_or_else(|| self.vout(), |i| self.label(&format!("vout{i}"))),
+            );
+        } else if !OutputFormat::is_audio(&self.ext) {
+            map(args, "0:v?".to_owned());
+        }
+        if !self.discard_audio {
+            let streams = match part {
+                Some(i) => i..i + 1,
+                None => 0..self.audio_streams(),
+            };
+            if self.filters_audio() {
+                for i in streams {
+                    map(args, self.aout(i));
+                }
+            } else {
+                self.push_audio_filters(args, quote);
+                for _ in streams {
+                    map(args, format!("{}:a?", self.audio_input));
+                }
+            }
+        }
+        if let Some(cover) = self.cover_input {
+            map(args, format!("{cover}:v:0"));
+        }
+        for rendition in &self.renditions {
+            args.extend(rendition.args.iter().cloned());
+        }
+    }
+
+    fn push_audio_filters(&self, args: &mut Vec<String>, quote: bool) {
         if !self.discard_audio && !self.audio_filters.is_empty() {
             args.push("-af".to_owned());
             let af = self.audio_filters.join(",");
-            args.push(if quote { format!("\"{af}\"") } else { af });
+            args.push(if quote { quote_arg(&af) } else { af });
+        }
+    }
+
+    /// Stream mapping, the video comes from the filter graph output when it is given
+    fn push_maps(&self, args: &mut Vec<String>, video: Option<&str>, quote: bool) {
+        if let Some(label) = video {
+            args.push("-map".to_owned());
+            args.push(if quote {
+                quote_arg(label)
+            } else {
+                label.to_owned()
+            });
+            if !self.discard_audio {
+                args.push("-map".to_owned());
+                args.push(format!("{}:a?", self.audio_input));
+            }
+            return;
         }
-        if !self.video_filters.is_empty() {
-            args.push("-vf".to_owned());
-            let vf = self.video_filters.join(",");
-            args.push(if quote { format!("\"{vf}\"") } else { vf });
+        if self.audio_input > 0 && !self.discard_audio {
+            if !OutputFormat::is_audio(&self.ext) {
+                args.push("-map".to_owned());
+                args.push("0:v?".to_owned());
+            }
+            args.push("-map".to_owned());
+            args.push(format!("{}:a?", self.audio_input));
         }
-        args.extend(self.pre_output_args.iter().cloned());
-        args
+        if let Some(cover) = self.cover_input {
+            if self.audio_input == 0 && !self.discard_audio {
+                args.push("-map".to_owned());
+                args.push("0:a?".to_owned());
+            }
+            args.push("-map".to_owned());
+            args.push(format!("{cover}:v:0"));
+        }
+    }
+
+    fn push_output_args(&self, args: &mut Vec<String>, quote: bool) {
+        for (option, value) in &self.metadata {
+            args.push(option.clone());
+            args.push(if quote {
+                quote_arg(value)
+            } else {
+                value.clone()
+            });
+        }
+        // Some muxer options are lists separated by spaces
+        args.extend(self.pre_output_args.iter().map(|arg| {
+            if quote && arg.contains(' ') {
+                quote_arg(arg)
+            } else {
+                arg.clone()
+            }
+        }));
+    }
+
+    /// Split the video filters chain by complex filters and link them together
+    fn build_filter_complex(&self) -> String {
+        let mut graph = Vec::new();
+        let mut label = self.video_input();
+        let mut start = 0;
+        let last = self.complex_video_filters.len() - 1;
+        for (i, filter) in self.complex_video_filters.iter().enumerate() {
+            let position = filter.position.clamp(start, self.video_filters.len());
+            if position > start {
+                let out = self.label(&format!("vc{i}"));
+                let chain = self.video_filters[start..position].join(",");
+                graph.push(format!("{label}{chain}{out}"));
+                label = out;
+                start = position;
+            }
+            let out = if i == last && start == self.video_filters.len() {
+                self.vout()
+            } else {
+                self.label(&format!("v{i}"))
+            };
+            graph.push(
+                filter
+                    .graph
+                    .replace("[{in}]", &label)
+                    .replace("[{out}]", &out),
+            );
+            label = out;
+        }
+        if start < self.video_filters.len() {
+            let chain = self.video_filters[start..].join(",");
+            graph.push(format!("{label}{chain}{}", self.vout()));
+        }
+        graph.join(";")
     }
 }
 
+/// Quote an argument of the copied command, so it can be pasted into a shell as is
+pub(crate) fn quote_arg(arg: &str) -> String {
+    #[cfg(not(target_os = "windows"))]
+    let escaped = arg
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('$', "\\$")
+        .replace('`', "\\`");
+    #[cfg(target_os = "windows")]
+    let escaped = arg.replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+/// Escape a filter option value.
+/// Option separators are escaped with a backslash, then the value is quoted for the filter graph
+pub(crate) fn escape_filter_value(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('\'', "\\'")
+        .replace(':', "\\:");
+    format!("'{}'", escaped.replace('\'', "'\\''"))
+}
+
 impl ParameterVisitor for CommandBuilder {
+    fn visit_loop(&mut self, data: &mut ParameterData) {
+        Loop::build_command(self, data);
+    }
+
     fn visit_trim(&mut self, data: &mut ParameterData) {
         Trim::build_command(self, data);
     }
@@ -80,10 +653,46 @@ impl ParameterVisitor for CommandBuilder {
         AudioBitrate::build_command(self, data);
     }
 
+    fn visit_audio_codec(&mut self, data: &mut ParameterData) {
+        AudioCodec::build_command(self, data);
+    }
+
+    fn visit_audio_vbr(&mut self, data: &mut ParameterData) {
+        AudioVbr::build_command(self, data);
+    }
+
+    fn visit_audio_channels(&mut self, data: &mut ParameterData) {
+        AudioChannels::build_command(self, data);
+    }
+
+    fn visit_audio_offset(&mut self, data: &mut ParameterData) {
+        AudioOffset::build_command(self, data);
+    }
+
+    fn visit_audio_sample_rate(&mut self, data: &mut ParameterData) {
+        AudioSampleRate::build_command(self, data);
+    }
+
+    fn visit_audio_bit_depth(&mut self, data: &mut ParameterData) {
+        AudioBitDepth::build_command(self, data);
+    }
+
+    fn visit_flac_compression(&mut self, data: &mut ParameterData) {
+        FlacCompression::build_command(self, data);
+    }
+
+    fn visit_voice_cleanup(&mut self, data: &mut ParameterData) {
+        VoiceCleanup::build_command(self, data);
+    }
+
     fn visit_audio_crystalizer(&mut self, data: &mut ParameterData) {
         AudioCrystalizer::build_command(self, data);
     }
 
+    fn visit_audio_eq(&mut self, data: &mut ParameterData) {
+        AudioEq::build_command(self, data);
+    }
+
     fn visit_audio_volume(&mut self, data: &mut ParameterData) {
         AudioVolume::build_command(self, data);
     }
@@ -92,10 +701,22 @@ impl ParameterVisitor for CommandBuilder {
         AudioPitch::build_command(self, data);
     }
 
+    fn visit_remove_silence(&mut self, data: &mut ParameterData) {
+        RemoveSilence::build_command(self, data);
+    }
+
     fn visit_speed_factor(&mut self, data: &mut ParameterData) {
         SpeedFactor::build_command(self, data);
     }
 
+    fn visit_preserve_pitch(&mut self, data: &mut ParameterData) {
+        PreservePitch::build_command(self, data);
+    }
+
+    fn visit_timelapse(&mut self, data: &mut ParameterData) {
+        Timelapse::build_command(self, data);
+    }
+
     fn visit_video_bitrate(&mut self, data: &mut ParameterData) {
         VideoBitrate::build_command(self, data);
     }
@@ -104,14 +725,54 @@ impl ParameterVisitor for CommandBuilder {
         VideoFrameRate::build_command(self, data);
     }
 
+    fn visit_aspect(&mut self, data: &mut ParameterData) {
+        Aspect::build_command(self, data);
+    }
+
     fn visit_video_scale(&mut self, data: &mut ParameterData) {
         VideoScale::build_command(self, data);
     }
 
+    fn visit_scale_algorithm(&mut self, data: &mut ParameterData) {
+        ScaleAlgorithm::build_command(self, data);
+    }
+
+    fn visit_frame_interpolation(&mut self, data: &mut ParameterData) {
+        FrameInterpolation::build_command(self, data);
+    }
+
+    fn visit_overlay(&mut self, data: &mut ParameterData) {
+        Overlay::build_command(self, data);
+    }
+
+    fn visit_text(&mut self, data: &mut ParameterData) {
+        Text::build_command(self, data);
+    }
+
     fn visit_hardware_acceleration(&mut self, data: &mut ParameterData) {
         HardwareAcceleration::build_command(self, data);
     }
 
+    fn visit_split(&mut self, data: &mut ParameterData) {
+        Split::build_command(self, data);
+    }
+
+    fn visit_frames(&mut self, data: &mut ParameterData) {
+        Frames::build_command(self, data);
+    }
+
+    fn visit_image_quality(&mut self, data: &mut ParameterData) {
+        ImageQuality::build_command(self, data);
+    }
+
+    fn visit_renditions(&mut self, data: &mut ParameterData) {
+        Renditions::build_command(self, data);
+    }
+
+    fn visit_metadata(&mut self, data: &mut ParameterData) {
+        Metadata::build_command(self, data);
+    }
+
     fn visit_output_format(&mut self, data: &mut ParameterData) {
         OutputFormat::build_command(self, data);
     }
@@ -124,14 +785,20 @@ impl ParameterVisitor for CommandBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::features::Features;
     use crate::info::{Info, InfoFormat};
+    use crate::model::{
+        AspectData, AspectMode, CoverArt, EqBands, EqData, EqPreset, MetadataData, MetadataTags,
+        OverlayData, OverlayPosition, PackageFormat, SceneDetection, SilenceData, SilenceDetection,
+        SilenceScope, SplitData, SplitMode, TextData, TrimData,
+    };
 
     // ------ Audio ------
 
     #[test]
     fn audio_bitrate_default() {
         let mut cb = CommandBuilder::default();
-        let mut p = AudioBitrate::new_parameter();
+        let mut p = AudioBitrate::new_parameter(&Info::default());
 
         cb.visit_audio_bitrate(&mut p.data);
 
@@ -141,7 +808,7 @@ mod tests {
     #[test]
     fn audio_bitrate() {
         let mut cb = CommandBuilder::default();
-        let mut p = AudioBitrate::new_parameter();
+        let mut p = AudioBitrate::new_parameter(&Info::default());
         set_custom_value(&mut p, "80");
 
         cb.visit_audio_bitrate(&mut p.data);
@@ -171,155 +838,1691 @@ mod tests {
     }
 
     #[test]
-    fn audio_pitch_default() {
+    fn audio_codec_default_bitrate() {
         let mut cb = CommandBuilder::default();
-        let mut p = AudioPitch::new_parameter();
+        let mut p = AudioCodec::new_parameter();
+        AudioCodec::apply_preset(&cb.ctx, &mut p.data, "libopus");
 
-        cb.visit_audio_pitch(&mut p.data);
+        cb.visit_audio_codec(&mut p.data);
+        cb.visit_last();
 
-        assert!(cb.audio_filters.is_empty());
+        assert_eq!(cb.pre_output_args, vec!["-c:a", "libopus", "-b:a", "128k"]);
     }
 
     #[test]
-    fn audio_pitch() {
+    fn audio_codec_with_bitrate() {
+        let info = Info::default();
         let mut cb = CommandBuilder::default();
-        let mut p = AudioPitch::new_parameter();
-        set_custom_value(&mut p, "1.5");
+        let mut codec = AudioCodec::new_parameter();
+        AudioCodec::apply_preset(&cb.ctx, &mut codec.data, "aac");
+        let mut bitrate = AudioBitrate::new_parameter(&info);
+        set_custom_value(&mut bitrate, "96");
 
-        cb.visit_audio_pitch(&mut p.data);
+        cb.visit_audio_codec(&mut codec.data);
+        cb.visit_audio_bitrate(&mut bitrate.data);
+        cb.visit_last();
 
-        assert_eq!(
-            cb.audio_filters,
-            vec!["rubberband=pitchq=quality:pitch=1.5"]
-        );
+        assert_eq!(cb.pre_output_args, vec!["-b:a", "96k", "-c:a", "aac"]);
     }
 
     #[test]
-    fn audio_volume_default() {
+    fn audio_codec_copy_with_filters() {
         let mut cb = CommandBuilder::default();
-        let mut p = AudioVolume::new_parameter();
+        cb.audio_filters.push("volume=5dB".to_owned());
+        let mut p = AudioCodec::new_parameter();
+        AudioCodec::apply_preset(&cb.ctx, &mut p.data, "copy");
 
-        cb.visit_audio_volume(&mut p.data);
+        cb.visit_audio_codec(&mut p.data);
+        cb.visit_last();
 
-        assert!(cb.audio_filters.is_empty());
+        assert!(cb.pre_output_args.is_empty());
+        assert_eq!(cb.warnings.len(), 1);
     }
 
     #[test]
-    fn audio_volume() {
-        let mut cb = CommandBuilder::default();
-        let mut p = AudioVolume::new_parameter();
-        set_custom_value(&mut p, "5");
-
-        cb.visit_audio_volume(&mut p.data);
+    fn audio_vbr() {
+        let mut cb = CommandBuilder {
+            ext: "mp3".to_owned(),
+            ..Default::default()
+        };
+        let mut p = AudioVbr::new_parameter();
+        AudioVbr::apply_preset(&cb.ctx, &mut p.data, "high");
 
-        assert_eq!(cb.audio_filters, vec!["volume=5dB"]);
-    }
+        cb.visit_audio_vbr(&mut p.data);
+        cb.visit_last();
 
-    #[test]
-    fn disable_audio_default() {
-        let mut cb = CommandBuilder::default();
-        let mut p = DisableAudio::new_parameter();
+        assert_eq!(cb.pre_output_args, vec!["-q:a", "0"]);
 
-        cb.visit_disable_audio(&mut p.data);
+        let mut cb = CommandBuilder {
+            audio_codec: Some("libopus".to_owned()),
+            ..Default::default()
+        };
+        cb.visit_audio_vbr(&mut p.data);
+        cb.visit_last();
 
-        assert!(!cb.discard_audio);
-        assert!(cb.args.is_empty());
+        assert_eq!(
+            cb.pre_output_args,
+            vec!["-vbr", "on", "-b:a", "160k", "-c:a", "libopus"]
+        );
     }
 
     #[test]
-    fn disable_audio() {
-        let mut cb = CommandBuilder::default();
-        let mut p = DisableAudio::new_parameter();
-        toggle_next(&mut p);
+    fn audio_codec_depends_on_output() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"in.mp3","nb_streams":1},
+            "streams":[{"index":0,"codec_type":"audio"}]}"#,
+        )
+        .expect("valid info");
+        let params = |preset: &str| create_params(&info, Features::default(), Some(preset), "mp3");
+        let enabled =
+            |params: &[Parameter], id: &str| params.iter().any(|p| p.id == id && p.enabled);
+
+        // MP3 container only holds MP3, VBR replaces CBR
+        let mp3 = params("output=mp3;acodec=aac;avbr=high");
+        assert_eq!(
+            mp3.iter()
+                .find(|p| p.id == AudioCodec::ID)
+                .map(Parameter::describe_value)
+                .as_deref(),
+            Some("auto")
+        );
+        assert!(enabled(&mp3, AudioVbr::ID));
+        assert!(!enabled(&mp3, AudioBitrate::ID));
 
-        cb.visit_disable_audio(&mut p.data);
+        // Lossless codecs have neither
+        let flac = params("output=flac");
+        assert!(!enabled(&flac, AudioVbr::ID));
+        assert!(!enabled(&flac, AudioBitrate::ID));
 
-        assert!(cb.discard_audio);
-        assert_eq!(cb.args, vec!["-an"]);
+        let ogg = params("output=ogg;acodec=libopus");
+        assert!(enabled(&ogg, AudioVbr::ID));
+        assert!(enabled(&ogg, AudioBitrate::ID));
     }
 
     #[test]
-    fn disable_audio_should_disable_audio_filters() {
+    fn audio_channels_default() {
         let mut cb = CommandBuilder::default();
-        let mut p = DisableAudio::new_parameter();
-        toggle_next(&mut p);
-        let mut volume = AudioVolume::new_parameter();
-        set_custom_value(&mut volume, "5");
+        let mut p = AudioChannels::new_parameter(&Info::default());
 
-        cb.visit_disable_audio(&mut p.data);
-        cb.visit_audio_volume(&mut volume.data);
+        cb.visit_audio_channels(&mut p.data);
 
-        assert!(cb.discard_audio);
-        assert_eq!(cb.args, vec!["-an"]);
         assert!(cb.audio_filters.is_empty());
+        assert!(cb.args.is_empty());
     }
 
-    // ------ Video ------
-
     #[test]
-    fn video_bitrate_default() {
-        let mut cb = CommandBuilder::default();
-        let mut p = VideoBitrate::new_parameter();
-
-        cb.visit_video_bitrate(&mut p.data);
+    fn audio_channels_options() {
+        let info = |channels: u32| {
+            Info::parse(&format!(
+                r#"{{"format":{{"filename":"in.mp3","nb_streams":1}},
+                "streams":[{{"index":0,"codec_type":"audio","channels":{channels}}}]}}"#
+            ))
+            .expect("valid info")
+        };
+        let values = |p: &Parameter| match &p.data {
+            ParameterData::Select { options, .. } => {
+                options.iter().map(|o| o.value.clone()).collect::<Vec<_>>()
+            }
+            _ => panic!("Unexpected parameter type"),
+        };
 
-        assert!(cb.pre_output_args.is_empty());
+        let mono = AudioChannels::new_parameter(&info(1));
+        assert_eq!(values(&mono), vec!["keep", "stereo"]);
+        assert_eq!(mono.describe_value(), "keep (mono)");
+        let stereo = AudioChannels::new_parameter(&info(2));
+        assert_eq!(
+            values(&stereo),
+            vec!["keep", "mono", "swap", "left", "right"]
+        );
+        let surround = AudioChannels::new_parameter(&info(6));
+        assert_eq!(
+            values(&surround),
+            vec!["keep", "stereo", "mono", "left", "right"]
+        );
+        assert_eq!(surround.describe_value(), "keep (5.1)");
     }
 
     #[test]
-    fn video_bitrate() {
-        let mut cb = CommandBuilder::default();
-        let mut p = VideoBitrate::new_parameter();
-        set_custom_value(&mut p, "2M");
+    fn audio_channels_stereo() {
+        let mut cb = CommandBuilder::new(VisitorContext {
+            channels: Some(2),
+            ..Default::default()
+        });
+        let mut p = AudioChannels::new_parameter(&Info::default());
+        AudioChannels::apply_preset(&cb.ctx, &mut p.data, "swap");
 
-        cb.visit_video_bitrate(&mut p.data);
+        cb.visit_audio_channels(&mut p.data);
 
-        assert_eq!(cb.pre_output_args, vec!["-b:v", "2M"]);
+        assert_eq!(cb.audio_filters, vec!["pan=stereo|c0=c1|c1=c0"]);
     }
 
     #[test]
-    fn video_frame_rate_default() {
-        let mut cb = CommandBuilder::default();
-        let mut p = VideoFrameRate::new_parameter();
+    fn audio_channels_surround_downmix() {
+        let mut cb = CommandBuilder::new(VisitorContext {
+            channels: Some(6),
+            ..Default::default()
+        });
+        let mut p = AudioChannels::new_parameter(&Info::default());
+        AudioChannels::apply_preset(&cb.ctx, &mut p.data, "stereo");
 
-        cb.visit_video_frame_rate(&mut p.data);
+        cb.visit_audio_channels(&mut p.data);
 
-        assert!(cb.args.is_empty());
+        assert_eq!(
+            cb.audio_filters,
+            vec!["pan=stereo|c0<c0+0.707*c2+0.707*c4|c1<c1+0.707*c2+0.707*c5"]
+        );
     }
 
     #[test]
-    fn video_frame_rate() {
+    fn audio_channels_count() {
+        let mut cb = CommandBuilder::new(VisitorContext {
+            channels: Some(8),
+            ..Default::default()
+        });
+        let mut p = AudioChannels::new_parameter(&Info::default());
+        AudioChannels::apply_preset(&cb.ctx, &mut p.data, "mono");
+
+        cb.visit_audio_channels(&mut p.data);
+
+        assert!(cb.audio_filters.is_empty());
+        assert_eq!(cb.args, vec!["-ac", "1"]);
+    }
+
+    #[test]
+    fn audio_sample_rate() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"in.mp3","nb_streams":1},
+            "streams":[{"index":0,"codec_type":"audio","sample_rate":"44100"}]}"#,
+        )
+        .expect("valid info");
+        let mut p = AudioSampleRate::new_parameter(&info);
+        assert_eq!(p.describe_value(), "original (44100)");
+        toggle_next(&mut p);
+        toggle_next(&mut p);
+        assert_eq!(p.describe_value(), "48000 (upsample)");
+
+        let mut cb = CommandBuilder::default();
+        cb.visit_audio_sample_rate(&mut p.data);
+        assert_eq!(cb.audio_filters, vec!["aresample=48000:resampler=soxr"]);
+
+        let mut cb = CommandBuilder::new(VisitorContext {
+            features: Features {
+                soxr: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        cb.visit_audio_sample_rate(&mut p.data);
+        assert!(cb.audio_filters.is_empty());
+        assert_eq!(cb.args, vec!["-ar", "48000"]);
+    }
+
+    #[test]
+    fn audio_bit_depth() {
+        let mut p = AudioBitDepth::new_parameter();
+        AudioBitDepth::apply_preset(&VisitorContext::default(), &mut p.data, "24");
+
+        let mut cb = CommandBuilder {
+            ext: "wav".to_owned(),
+            ..Default::default()
+        };
+        cb.visit_audio_bit_depth(&mut p.data);
+        cb.visit_last();
+        assert_eq!(cb.pre_output_args, vec!["-c:a", "pcm_s24le"]);
+
+        let mut cb = CommandBuilder {
+            ext: "flac".to_owned(),
+            ..Default::default()
+        };
+        cb.visit_audio_bit_depth(&mut p.data);
+        assert_eq!(
+            cb.args,
+            vec!["-sample_fmt", "s32", "-bits_per_raw_sample", "24"]
+        );
+    }
+
+    #[test]
+    fn flac_compression() {
+        let mut cb = CommandBuilder {
+            ext: "flac".to_owned(),
+            ..Default::default()
+        };
+        let mut p = FlacCompression::new_parameter();
+
+        cb.visit_flac_compression(&mut p.data);
+        assert!(cb.args.is_empty());
+
+        toggle_next(&mut p);
+        cb.visit_flac_compression(&mut p.data);
+        assert_eq!(cb.args, vec!["-compression_level", "8"]);
+    }
+
+    #[test]
+    fn lossless_params_depend_on_output() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"in.mp3","nb_streams":1},
+            "streams":[{"index":0,"codec_type":"audio"}]}"#,
+        )
+        .expect("valid info");
+        let enabled = |preset: &str, id: &str| {
+            create_params(&info, Features::default(), Some(preset), "mp3")
+                .iter()
+                .any(|p| p.id == id && p.enabled)
+        };
+
+        assert!(!enabled("output=mp3", AudioBitDepth::ID));
+        assert!(enabled("output=wav", AudioBitDepth::ID));
+        assert!(!enabled("output=wav", FlacCompression::ID));
+        assert!(enabled("output=flac", FlacCompression::ID));
+    }
+
+    #[test]
+    fn voice_cleanup_default() {
+        let mut cb = CommandBuilder::default();
+        let mut p = VoiceCleanup::new_parameter();
+
+        cb.visit_voice_cleanup(&mut p.data);
+
+        assert!(cb.audio_filters.is_empty());
+    }
+
+    #[test]
+    fn voice_cleanup_light() {
+        let mut cb = CommandBuilder::default();
+        let mut p = VoiceCleanup::new_parameter();
+        set_custom_value(&mut p, "light");
+
+        cb.visit_voice_cleanup(&mut p.data);
+
+        assert_eq!(
+            cb.audio_filters,
+            vec![
+                "afftdn=nr=10:nf=-50",
+                "highpass=f=80",
+                "acompressor=threshold=-18dB:ratio=2:attack=20:release=250",
+                "alimiter=limit=-1dB:level=0",
+            ]
+        );
+    }
+
+    #[test]
+    fn voice_cleanup_custom() {
+        let mut cb = CommandBuilder::default();
+        let mut p = VoiceCleanup::new_parameter();
+        set_custom_value(&mut p, "0,-30,6,-2");
+
+        cb.visit_voice_cleanup(&mut p.data);
+
+        assert_eq!(
+            cb.audio_filters,
+            vec![
+                "highpass=f=80",
+                "acompressor=threshold=-30dB:ratio=6:attack=20:release=250",
+                "alimiter=limit=-2dB:level=0",
+            ]
+        );
+        assert_eq!(
+            p.describe_value(),
+            "denoise 0dB, compress -30dB 6:1, limit -2dB"
+        );
+    }
+
+    #[test]
+    fn audio_eq_default() {
+        let mut cb = CommandBuilder::default();
+        let mut p = AudioEq::new_parameter();
+
+        cb.visit_audio_eq(&mut p.data);
+
+        assert!(cb.audio_filters.is_empty());
+    }
+
+    #[test]
+    fn audio_eq_preset() {
+        let mut cb = CommandBuilder::default();
+        let mut p = AudioEq::new_parameter();
+        p.data = ParameterData::Eq(EqData::with_preset(EqPreset::Telephone));
+
+        cb.visit_audio_eq(&mut p.data);
+
+        assert_eq!(cb.audio_filters, vec!["highpass=f=300", "lowpass=f=3400"]);
+    }
+
+    #[test]
+    fn audio_eq_custom() {
+        let mut cb = CommandBuilder::default();
+        let mut p = AudioEq::new_parameter();
+        let bands = EqBands {
+            bass: 4,
+            mid: -2,
+            treble: 3,
+            highpass: 80,
+            lowpass: 0,
+        };
+        p.data = ParameterData::Eq(EqData::with_bands(bands));
+
+        cb.visit_audio_eq(&mut p.data);
+
+        assert_eq!(
+            cb.audio_filters,
+            vec![
+                "highpass=f=80",
+                "bass=g=4",
+                "equalizer=f=1000:t=o:w=2:g=-2",
+                "treble=g=3",
+            ]
+        );
+        assert_eq!(
+            p.describe_value(),
+            "bass +4dB, mid -2dB, treble +3dB, hp 80Hz"
+        );
+    }
+
+    #[test]
+    fn audio_eq_discard_audio() {
+        let mut cb = CommandBuilder {
+            discard_audio: true,
+            ..Default::default()
+        };
+        let mut p = AudioEq::new_parameter();
+        p.data = ParameterData::Eq(EqData::with_preset(EqPreset::BassBoost));
+
+        cb.visit_audio_eq(&mut p.data);
+
+        assert!(cb.audio_filters.is_empty());
+    }
+
+    #[test]
+    fn audio_pitch_default() {
+        let mut cb = CommandBuilder::default();
+        let mut p = AudioPitch::new_parameter(Features::default());
+
+        cb.visit_audio_pitch(&mut p.data);
+
+        assert!(cb.audio_filters.is_empty());
+    }
+
+    #[test]
+    fn audio_pitch() {
+        let mut cb = CommandBuilder::default();
+        let mut p = AudioPitch::new_parameter(Features::default());
+        set_custom_value(&mut p, "1.5");
+
+        cb.visit_audio_pitch(&mut p.data);
+
+        assert_eq!(
+            cb.audio_filters,
+            vec!["rubberband=pitchq=quality:pitch=1.5"]
+        );
+    }
+
+    #[test]
+    fn audio_volume_default() {
+        let mut cb = CommandBuilder::default();
+        let mut p = AudioVolume::new_parameter();
+
+        cb.visit_audio_volume(&mut p.data);
+
+        assert!(cb.audio_filters.is_empty());
+    }
+
+    #[test]
+    fn audio_volume() {
+        let mut cb = CommandBuilder::default();
+        let mut p = AudioVolume::new_parameter();
+        set_custom_value(&mut p, "5");
+
+        cb.visit_audio_volume(&mut p.data);
+
+        assert_eq!(cb.audio_filters, vec!["volume=5dB"]);
+    }
+
+    #[test]
+    fn disable_audio_default() {
+        let mut cb = CommandBuilder::default();
+        let mut p = DisableAudio::new_parameter();
+
+        cb.visit_disable_audio(&mut p.data);
+
+        assert!(!cb.discard_audio);
+        assert!(cb.args.is_empty());
+    }
+
+    #[test]
+    fn disable_audio() {
+        let mut cb = CommandBuilder::default();
+        let mut p = DisableAudio::new_parameter();
+        toggle_next(&mut p);
+
+        cb.visit_disable_audio(&mut p.data);
+
+        assert!(cb.discard_audio);
+        assert_eq!(cb.args, vec!["-an"]);
+    }
+
+    #[test]
+    fn disable_audio_should_disable_audio_filters() {
+        let mut cb = CommandBuilder::default();
+        let mut p = DisableAudio::new_parameter();
+        toggle_next(&mut p);
+        let mut volume = AudioVolume::new_parameter();
+        set_custom_value(&mut volume, "5");
+
+        cb.visit_disable_audio(&mut p.data);
+        cb.visit_audio_volume(&mut volume.data);
+
+        assert!(cb.discard_audio);
+        assert_eq!(cb.args, vec!["-an"]);
+        assert!(cb.audio_filters.is_empty());
+    }
+
+    #[test]
+    fn audio_pitch_without_rubberband() {
+        let mut cb = CommandBuilder::new(VisitorContext {
+            sample_rate: Some(44100),
+            features: Features {
+                rubberband: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let mut p = AudioPitch::new_parameter(cb.ctx.features);
+        set_custom_value(&mut p, "1.25");
+
+        cb.visit_audio_pitch(&mut p.data);
+
+        assert_eq!(
+            cb.audio_filters,
+            vec!["asetrate=44100*1.25", "aresample=44100", "atempo=0.8"]
+        );
+        assert_eq!(p.describe_value(), "1.25 (resample)");
+    }
+
+    #[test]
+    fn remove_silence_default() {
+        let mut cb = CommandBuilder::default();
+        let mut p = RemoveSilence::new_parameter(&Info::default());
+
+        cb.visit_remove_silence(&mut p.data);
+
+        assert!(cb.audio_filters.is_empty());
+    }
+
+    #[test]
+    fn remove_silence_audio() {
+        let mut cb = CommandBuilder::default();
+        cb.audio_filters.push("volume=5dB".to_owned());
+        let mut p = RemoveSilence::new_parameter(&Info::default());
+        p.data = ParameterData::Silence(SilenceData {
+            scope: SilenceScope::All,
+            ..Default::default()
+        });
+
+        cb.visit_remove_silence(&mut p.data);
+
+        assert_eq!(
+            cb.audio_filters,
+            vec![
+                "silenceremove=start_periods=1:start_duration=0.5:start_threshold=-40dB:\
+                 stop_periods=-1:stop_duration=0.5:stop_threshold=-40dB",
+                "volume=5dB",
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_silence_audio_edges() {
+        let mut cb = CommandBuilder::default();
+        let mut p = RemoveSilence::new_parameter(&Info::default());
+        p.data = ParameterData::Silence(SilenceData {
+            scope: SilenceScope::Edges,
+            threshold: -50,
+            duration: 1.0,
+            ..Default::default()
+        });
+
+        cb.visit_remove_silence(&mut p.data);
+
+        let start = "silenceremove=start_periods=1:start_duration=1:start_threshold=-50dB";
+        assert_eq!(
+            cb.audio_filters,
+            vec![format!("{start},areverse,{start},areverse")]
+        );
+    }
+
+    #[test]
+    fn remove_silence_video_not_analyzed() {
+        let mut cb = CommandBuilder::default();
+        let mut p = RemoveSilence::new_parameter(&Info::default());
+        p.data = ParameterData::Silence(SilenceData {
+            scope: SilenceScope::All,
+            video: true,
+            ..Default::default()
+        });
+
+        cb.visit_remove_silence(&mut p.data);
+
+        assert!(cb.audio_filters.is_empty());
+        assert!(cb.video_filters.is_empty());
+        assert_eq!(cb.warnings.len(), 1);
+    }
+
+    #[test]
+    fn remove_silence_video() {
+        let mut cb = CommandBuilder::new(VisitorContext {
+            input_duration: Some(20.0),
+            ..Default::default()
+        });
+        cb.input_seek = Some(1.0);
+        cb.video_filters.push("scale=-2:720".to_owned());
+        let mut p = RemoveSilence::new_parameter(&Info::default());
+        let mut silence = SilenceData {
+            scope: SilenceScope::Edges,
+            video: true,
+            detection: Some(SilenceDetection {
+                threshold: -40,
+                duration: 0.5,
+                ranges: vec![(0.0, 1.5), (8.0, 9.25), (19.5, 20.0)],
+            }),
+            ..Default::default()
+        };
+        p.data = ParameterData::Silence(silence.clone());
+
+        cb.visit_remove_silence(&mut p.data);
+
+        let keep = "not(between(t,0,0.5)+between(t,18.5,19))";
+        assert_eq!(
+            cb.video_filters,
+            vec![
+                format!("select='{keep}'"),
+                "setpts=N/FRAME_RATE/TB".to_owned(),
+                "scale=-2:720".to_owned(),
+            ]
+        );
+        assert_eq!(
+            cb.audio_filters,
+            vec![format!("aselect='{keep}'"), "asetpts=N/SR/TB".to_owned()]
+        );
+
+        silence.scope = SilenceScope::All;
+        assert_eq!(silence.cut_ranges(Some(20.0)).len(), 3);
+        silence.threshold = -50;
+        assert!(silence.needs_detection());
+        assert_eq!(
+            p.describe_value(),
+            "leading/trailing, below -40dB for 0.5s, 3 found"
+        );
+    }
+
+    #[test]
+    fn remove_silence_video_precise_trim() {
+        let mut cb = CommandBuilder::new(VisitorContext {
+            input_duration: Some(20.0),
+            ..Default::default()
+        });
+        cb.args.push("-an".to_owned());
+        let mut trim = Trim::new_parameter();
+        trim.data = ParameterData::Trim(TrimData {
+            ss: Some("5".to_owned()),
+            to: Some("15".to_owned()),
+            use_to: true,
+            precise: true,
+        });
+        let mut p = RemoveSilence::new_parameter(&Info::default());
+        p.data = ParameterData::Silence(SilenceData {
+            scope: SilenceScope::All,
+            video: true,
+            detection: Some(SilenceDetection {
+                threshold: -40,
+                duration: 0.5,
+                ranges: vec![(0.0, 1.5), (4.0, 6.0), (8.0, 9.25), (19.5, 20.0)],
+            }),
+            ..Default::default()
+        });
+
+        cb.visit_trim(&mut trim.data);
+        cb.visit_remove_silence(&mut p.data);
+
+        // The trim is part of the selection, the output is not seeked
+        let keep = "between(t,5,15)*not(between(t,4,6)+between(t,8,9.25))";
+        assert_eq!(
+            cb.video_filters,
+            vec![
+                format!("select='{keep}'"),
+                "setpts=N/FRAME_RATE/TB".to_owned(),
+            ]
+        );
+        assert_eq!(cb.args, vec!["-an"]);
+    }
+
+    #[test]
+    fn audio_offset_filters() {
+        let mut cb = CommandBuilder::default();
+        cb.audio_filters.push("volume=5dB".to_owned());
+        let mut p = AudioOffset::new_parameter();
+        AudioOffset::apply_preset(&cb.ctx, &mut p.data, "250");
+
+        cb.visit_audio_offset(&mut p.data);
+
+        assert_eq!(cb.audio_filters, vec!["adelay=250:all=1", "volume=5dB"]);
+        assert_eq!(p.describe_value(), "+250ms (audio later)");
+
+        let mut cb = CommandBuilder::default();
+        AudioOffset::apply_preset(&cb.ctx, &mut p.data, "-1500");
+
+        cb.visit_audio_offset(&mut p.data);
+
+        assert_eq!(
+            cb.audio_filters,
+            vec!["atrim=start=1.500,asetpts=PTS-STARTPTS"]
+        );
+    }
+
+    #[test]
+    fn audio_offset_copy_with_trim() {
+        let mut cb = CommandBuilder {
+            ext: "mkv".to_owned(),
+            ..Default::default()
+        };
+        let mut trim = Trim::new_parameter();
+        trim.data = ParameterData::Trim(TrimData {
+            ss: Some("10".to_owned()),
+            ..Default::default()
+        });
+        let mut codec = AudioCodec::new_parameter();
+        AudioCodec::apply_preset(&cb.ctx, &mut codec.data, "copy");
+        let mut p = AudioOffset::new_parameter();
+        AudioOffset::apply_preset(&cb.ctx, &mut p.data, "-200");
+
+        cb.visit_audio_codec(&mut codec.data);
+        cb.visit_trim(&mut trim.data);
+        cb.visit_audio_offset(&mut p.data);
+        cb.visit_last();
+
+        assert!(cb.audio_filters.is_empty());
+        assert_eq!(cb.extra_inputs.len(), 1);
+        assert!(cb.extra_inputs[0].source);
+        assert_eq!(
+            cb.extra_inputs[0].args,
+            vec!["-ss", "10", "-itsoffset", "-0.200"]
+        );
+        assert_eq!(
+            cb.build_args(false),
+            vec!["-map", "0:v?", "-map", "1:a?", "-c:a", "copy"]
+        );
+
+        // Precise trim seeks the output, the second input is not seeked
+        let mut cb = CommandBuilder::default();
+        trim.data = ParameterData::Trim(TrimData {
+            ss: Some("10".to_owned()),
+            precise: true,
+            ..Default::default()
+        });
+        cb.visit_audio_codec(&mut codec.data);
+        cb.visit_trim(&mut trim.data);
+        cb.visit_audio_offset(&mut p.data);
+
+        assert_eq!(cb.args, vec!["-ss", "10"]);
+        assert_eq!(cb.extra_inputs[0].args, vec!["-itsoffset", "-0.200"]);
+    }
+
+    // ------ Video ------
+
+    #[test]
+    fn video_bitrate_default() {
+        let mut cb = CommandBuilder::default();
+        let mut p = VideoBitrate::new_parameter(&Info::default());
+
+        cb.visit_video_bitrate(&mut p.data);
+
+        assert!(cb.pre_output_args.is_empty());
+    }
+
+    #[test]
+    fn video_bitrate() {
+        let mut cb = CommandBuilder::default();
+        let mut p = VideoBitrate::new_parameter(&Info::default());
+        set_custom_value(&mut p, "2M");
+
+        cb.visit_video_bitrate(&mut p.data);
+
+        assert_eq!(cb.pre_output_args, vec!["-b:v", "2M"]);
+    }
+
+    #[test]
+    fn video_frame_rate_default() {
+        let mut cb = CommandBuilder::default();
+        let mut p = VideoFrameRate::new_parameter(&Info::default());
+
+        cb.visit_video_frame_rate(&mut p.data);
+
+        assert!(cb.args.is_empty());
+    }
+
+    #[test]
+    fn video_frame_rate() {
         let mut cb = CommandBuilder::default();
-        let mut p = VideoFrameRate::new_parameter();
+        let mut p = VideoFrameRate::new_parameter(&Info::default());
         set_custom_value(&mut p, "25");
 
-        cb.visit_video_frame_rate(&mut p.data);
+        cb.visit_video_frame_rate(&mut p.data);
+
+        assert_eq!(cb.args, vec!["-r", "25"]);
+    }
+
+    #[test]
+    fn video_scale_default() {
+        let mut cb = CommandBuilder::default();
+        let mut p = VideoScale::new_parameter(&Info::default());
+
+        cb.visit_video_scale(&mut p.data);
+
+        assert!(cb.video_filters.is_empty());
+    }
+
+    #[test]
+    fn video_scale() {
+        let mut cb = CommandBuilder::default();
+        let mut p = VideoScale::new_parameter(&Info::default());
+        set_custom_value(&mut p, "600");
+
+        cb.visit_video_scale(&mut p.data);
+
+        assert_eq!(cb.video_filters, vec!["scale=-2:600"]);
+    }
+
+    #[test]
+    fn video_scale_width() {
+        let mut cb = CommandBuilder::default();
+        let mut p = VideoScale::new_parameter(&Info::default());
+        set_custom_value(&mut p, "1280:>");
+
+        cb.visit_video_scale(&mut p.data);
+
+        assert_eq!(cb.video_filters, vec!["scale='min(1280,iw)':-2"]);
+    }
+
+    #[test]
+    fn video_scale_fit_with_algorithm() {
+        let mut cb = CommandBuilder::default();
+        let mut scaler = ScaleAlgorithm::new_parameter();
+        for _ in 0..3 {
+            toggle_next(&mut scaler);
+        }
+        let mut p = VideoScale::new_parameter(&Info::default());
+        set_custom_value(&mut p, "1280x720");
+
+        cb.visit_scale_algorithm(&mut scaler.data);
+        cb.visit_video_scale(&mut p.data);
+
+        assert_eq!(
+            cb.video_filters,
+            vec![
+                "scale=1280:720:force_original_aspect_ratio=decrease:force_divisible_by=2:\
+                 flags=lanczos"
+            ]
+        );
+    }
+
+    #[test]
+    fn video_scale_percent() {
+        let mut cb = CommandBuilder::default();
+        let mut p = VideoScale::new_parameter(&Info::default());
+        set_custom_value(&mut p, "50%");
+
+        cb.visit_video_scale(&mut p.data);
+
+        assert_eq!(cb.video_filters, vec!["scale=-2:trunc(ih*50/200)*2"]);
+    }
+
+    #[test]
+    fn frame_interpolation_default() {
+        let mut cb = CommandBuilder::new(VisitorContext {
+            frame_rate: Some(30.0),
+            ..Default::default()
+        });
+        let mut speed = SpeedFactor::new_parameter();
+        set_custom_value(&mut speed, "0.5");
+        let mut p = FrameInterpolation::new_parameter();
+
+        cb.visit_speed_factor(&mut speed.data);
+        cb.visit_frame_interpolation(&mut p.data);
+
+        assert_eq!(cb.video_filters, vec!["setpts=PTS/0.5"]);
+    }
+
+    #[test]
+    fn frame_interpolation_slow_motion_blend() {
+        let mut cb = CommandBuilder::new(VisitorContext {
+            frame_rate: Some(30000.0 / 1001.0),
+            ..Default::default()
+        });
+        let mut speed = SpeedFactor::new_parameter();
+        set_custom_value(&mut speed, "0.5");
+        let mut p = FrameInterpolation::new_parameter();
+        toggle_next(&mut p);
+
+        cb.visit_speed_factor(&mut speed.data);
+        cb.visit_frame_interpolation(&mut p.data);
+
+        assert_eq!(
+            cb.video_filters,
+            vec!["setpts=PTS/0.5", "framerate=fps=29.97"]
+        );
+        assert!(cb.warnings.is_empty());
+    }
+
+    #[test]
+    fn frame_interpolation_motion() {
+        let mut cb = CommandBuilder::new(VisitorContext {
+            input_duration: Some(60.0),
+            frame_rate: Some(25.0),
+            ..Default::default()
+        });
+        let mut frame_rate = VideoFrameRate::new_parameter(&Info::default());
+        set_custom_value(&mut frame_rate, "50");
+        let mut p = FrameInterpolation::new_parameter();
+        toggle_next(&mut p);
+        toggle_next(&mut p);
+
+        cb.visit_video_frame_rate(&mut frame_rate.data);
+        cb.visit_frame_interpolation(&mut p.data);
+
+        assert_eq!(
+            cb.video_filters,
+            vec!["minterpolate=fps=50:mi_mode=mci:mc_mode=aobmc:me_mode=bidir:vsbmc=1"]
+        );
+        assert_eq!(
+            cb.warnings,
+            vec!["Motion interpolation is slow, rendering may take about 10 min for 60s of output"]
+        );
+    }
+
+    #[test]
+    fn aspect_default() {
+        let mut cb = CommandBuilder::default();
+        let mut p = Aspect::new_parameter(&Info::default());
+
+        cb.visit_aspect(&mut p.data);
+
+        assert!(cb.video_filters.is_empty());
+    }
+
+    #[test]
+    fn aspect_pad() {
+        let mut cb = CommandBuilder::default();
+        let mut p = Aspect::new_parameter(&Info::default());
+        p.data = ParameterData::Aspect(AspectData {
+            ratio: Some((9, 16)),
+            source: Some((1920, 1080)),
+            ..Default::default()
+        });
+
+        cb.visit_aspect(&mut p.data);
+
+        assert_eq!(
+            cb.video_filters,
+            vec![
+                "scale=1080:1920:force_original_aspect_ratio=decrease:force_divisible_by=2",
+                "pad=1080:1920:(ow-iw)/2:(oh-ih)/2:color=black",
+                "setsar=1",
+            ]
+        );
+    }
+
+    #[test]
+    fn aspect_blur() {
+        let mut cb = CommandBuilder::default();
+        let mut p = Aspect::new_parameter(&Info::default());
+        p.data = ParameterData::Aspect(AspectData {
+            ratio: Some((1, 1)),
+            mode: AspectMode::Blur,
+            source: Some((1280, 720)),
+            ..Default::default()
+        });
+
+        cb.visit_aspect(&mut p.data);
+
+        assert_eq!(
+            cb.build_args(false),
+            vec![
+                "-filter_complex",
+                "[0:v]split[asp_a][asp_b];\
+                 [asp_a]scale=1280:1280:force_original_aspect_ratio=increase:force_divisible_by=2,\
+                 crop=1280:1280,boxblur=20:5[asp_bg];\
+                 [asp_b]scale=1280:1280:force_original_aspect_ratio=decrease:force_divisible_by=2[asp_fg];\
+                 [asp_bg][asp_fg]overlay=(W-w)/2:(H-h)/2,setsar=1[vout]",
+                "-map",
+                "[vout]",
+                "-map",
+                "0:a?",
+            ]
+        );
+    }
+
+    #[test]
+    fn aspect_crop() {
+        let mut cb = CommandBuilder::default();
+        let mut p = Aspect::new_parameter(&Info::default());
+        p.data = ParameterData::Aspect(AspectData {
+            ratio: Some((9, 16)),
+            mode: AspectMode::Crop,
+            source: Some((1920, 1080)),
+            ..Default::default()
+        });
+
+        cb.visit_aspect(&mut p.data);
+
+        assert_eq!(cb.video_filters, vec!["crop=606:1080", "setsar=1"]);
+    }
+
+    #[test]
+    fn aspect_same_as_source() {
+        let mut cb = CommandBuilder::default();
+        let mut p = Aspect::new_parameter(&Info::default());
+        p.data = ParameterData::Aspect(AspectData {
+            ratio: Some((16, 9)),
+            mode: AspectMode::Crop,
+            source: Some((1920, 1080)),
+            ..Default::default()
+        });
+
+        cb.visit_aspect(&mut p.data);
+
+        assert!(cb.video_filters.is_empty());
+    }
+
+    #[test]
+    fn overlay_default() {
+        let mut cb = CommandBuilder::default();
+        let mut p = Overlay::new_parameter();
+
+        cb.visit_overlay(&mut p.data);
+
+        assert!(cb.extra_inputs.is_empty());
+        assert!(cb.complex_video_filters.is_empty());
+    }
+
+    #[test]
+    fn overlay() {
+        let mut cb = CommandBuilder::default();
+        let mut p = Overlay::new_parameter();
+        p.data = ParameterData::Overlay(OverlayData {
+            path: Some("logo.png".to_owned()),
+            position: OverlayPosition::TopRight,
+            margin: 20,
+            scale: 15,
+            opacity: 50,
+        });
+
+        cb.visit_overlay(&mut p.data);
+
+        assert_eq!(cb.extra_inputs.len(), 1);
+        assert_eq!(cb.extra_inputs[0].path, "logo.png");
+        assert_eq!(
+            cb.build_args(false),
+            vec![
+                "-filter_complex",
+                "[1:v][0:v]scale2ref=w=oh*mdar:h=ih*0.15[ovs1][ovb1];\
+                 [ovs1]format=rgba,colorchannelmixer=aa=0.5[ova1];\
+                 [ovb1][ova1]overlay=x=W-w-20:y=20[vout]",
+                "-map",
+                "[vout]",
+                "-map",
+                "0:a?",
+            ]
+        );
+    }
+
+    #[test]
+    fn overlay_with_scale_and_speed() {
+        let mut cb = CommandBuilder::default();
+        let mut speed = SpeedFactor::new_parameter();
+        set_custom_value(&mut speed, "2");
+        let mut scale = VideoScale::new_parameter(&Info::default());
+        set_custom_value(&mut scale, "720");
+        let mut p = Overlay::new_parameter();
+        p.data = ParameterData::Overlay(OverlayData {
+            path: Some("logo.png".to_owned()),
+            scale: 0,
+            ..Default::default()
+        });
+
+        cb.visit_speed_factor(&mut speed.data);
+        cb.visit_video_scale(&mut scale.data);
+        cb.visit_overlay(&mut p.data);
+        cb.video_filters.push("format=nv12".to_owned());
+
+        assert_eq!(
+            cb.build_filter_complex(),
+            "[0:v]setpts=PTS/2,scale=-2:720[vc0];\
+             [vc0][1:v]overlay=x=W-w-10:y=H-h-10[v0];\
+             [v0]format=nv12[vout]"
+        );
+    }
+
+    #[test]
+    fn overlay_preset_escaping() {
+        let path = std::env::temp_dir().join("effy;logo,1%.png");
+        std::fs::write(&path, b"").expect("temp file");
+        let path = path.to_string_lossy().into_owned();
+        let ctx = VisitorContext::default();
+        let mut p = Overlay::new_parameter();
+        p.data = ParameterData::Overlay(OverlayData {
+            path: Some(path.clone()),
+            ..Default::default()
+        });
+
+        let preset = Overlay::save_preset(&ctx, &p.data).expect("preset");
+        let mut restored = Overlay::new_parameter();
+        Overlay::apply_preset(&ctx, &mut restored.data, &preset);
+        let _ = std::fs::remove_file(&path);
+
+        assert!(!preset.contains(';'), "{preset}");
+        assert!(preset.ends_with("effy%3Blogo%2C1%25.png"), "{preset}");
+        assert!(matches!(
+            restored.data,
+            ParameterData::Overlay(OverlayData { path: Some(p), .. }) if p == path
+        ));
+    }
+
+    #[test]
+    fn text_default() {
+        let mut cb = CommandBuilder::default();
+        let mut p = Text::new_parameter();
+
+        cb.visit_text(&mut p.data);
+
+        assert!(cb.video_filters.is_empty());
+    }
+
+    #[test]
+    fn text_escaping() {
+        let mut cb = CommandBuilder::default();
+        let mut p = Text::new_parameter();
+        p.data = ParameterData::Text(TextData {
+            text: "It's 100%: done".to_owned(),
+            boxed: true,
+            start: Some("5".to_owned()),
+            end: Some("00:10".to_owned()),
+            ..Default::default()
+        });
+
+        cb.visit_text(&mut p.data);
+
+        assert_eq!(
+            cb.video_filters,
+            vec![
+                "drawtext=text='It\\'\\''s 100\\\\%\\: done':fontsize=32:fontcolor=white:\
+                 box=1:boxcolor=black@0.5:boxborderw=8:x=(w-tw)/2:y=h-th-16:\
+                 enable='between(t,5,10)'"
+            ]
+        );
+    }
+
+    #[test]
+    fn text_preset_escaping() {
+        let font = std::env::temp_dir().join("effy;font,1.ttf");
+        std::fs::write(&font, b"").expect("temp file");
+        let font = font.to_string_lossy().into_owned();
+        let ctx = VisitorContext::default();
+        let mut p = Text::new_parameter();
+        let data = TextData {
+            text: "a;b,c 100%2C".to_owned(),
+            font: Some(font.clone()),
+            ..Default::default()
+        };
+        p.data = ParameterData::Text(data.clone());
+
+        let preset = Text::save_preset(&ctx, &p.data).expect("preset");
+        let mut restored = Text::new_parameter();
+        Text::apply_preset(&ctx, &mut restored.data, &preset);
+        let _ = std::fs::remove_file(&font);
+
+        assert!(!preset.contains(';'), "{preset}");
+        assert!(preset.ends_with("a%3Bb%2Cc 100%252C"), "{preset}");
+        let ParameterData::Text(restored) = restored.data else {
+            panic!("text data expected");
+        };
+        assert_eq!(restored.text, data.text);
+        assert_eq!(restored.font, data.font);
+    }
+
+    #[test]
+    fn text_timecode() {
+        let mut cb = CommandBuilder::default();
+        let mut p = Text::new_parameter();
+        p.data = ParameterData::Text(TextData {
+            timecode: true,
+            position: OverlayPosition::TopLeft,
+            ..Default::default()
+        });
+
+        cb.visit_text(&mut p.data);
+
+        assert_eq!(
+            cb.video_filters,
+            vec!["drawtext=text='%{pts\\:hms}':fontsize=32:fontcolor=white:x=16:y=16"]
+        );
+    }
+
+    #[test]
+    fn text_timecode_after_seek() {
+        let mut cb = CommandBuilder::default();
+        let mut trim = Trim::new_parameter();
+        trim.data = ParameterData::Trim(TrimData {
+            ss: Some("01:30".to_owned()),
+            ..Default::default()
+        });
+        let mut p = Text::new_parameter();
+        p.data = ParameterData::Text(TextData {
+            text: "Take 1".to_owned(),
+            timecode: true,
+            ..Default::default()
+        });
+
+        cb.visit_trim(&mut trim.data);
+        cb.visit_text(&mut p.data);
+
+        assert_eq!(cb.pre_input_args, vec!["-ss", "01:30"]);
+        assert_eq!(
+            cb.video_filters,
+            vec![
+                "drawtext=text='Take 1 %{pts\\:hms\\:90}':fontsize=32:fontcolor=white:\
+                 x=(w-tw)/2:y=h-th-16"
+            ]
+        );
+    }
+
+    // ------ Common ------
+
+    #[test]
+    fn loop_stream() {
+        let info = Info::default();
+        let mut cb = CommandBuilder::default();
+        cb.ctx.input_duration = Some(4.0);
+        let mut p = Loop::new_parameter(&info);
+        Loop::apply_preset(&cb.ctx, &mut p.data, "3");
+
+        cb.visit_loop(&mut p.data);
+        cb.visit_last();
+
+        assert_eq!(cb.ctx.input_duration, Some(12.0));
+        assert_eq!(cb.pre_input_args, vec!["-stream_loop", "2"]);
+        assert!(cb.args.is_empty());
+    }
+
+    #[test]
+    fn loop_target_with_filters() {
+        let info = Info::default();
+        let mut cb = CommandBuilder {
+            ext: "mkv".to_owned(),
+            ..Default::default()
+        };
+        cb.ctx.input_duration = Some(4.0);
+        cb.ctx.frame_rate = Some(25.0);
+        cb.ctx.sample_rate = Some(48000);
+        cb.video_filters.push("scale=-2:480".to_owned());
+        let mut p = Loop::new_parameter(&info);
+        Loop::apply_preset(&cb.ctx, &mut p.data, "10s");
+        let mut trim = Trim::new_parameter();
+        trim.data = ParameterData::Trim(TrimData {
+            ss: Some("50%".to_owned()),
+            ..Default::default()
+        });
+
+        cb.visit_loop(&mut p.data);
+        cb.visit_trim(&mut trim.data);
+        cb.visit_last();
+
+        assert_eq!(cb.ctx.input_duration, Some(10.0));
+        // Trim percents are of the looped duration and applied to the output
+        assert!(cb.pre_input_args.is_empty());
+        assert_eq!(cb.args, vec!["-ss", "5", "-t", "10"]);
+        assert_eq!(
+            cb.video_filters,
+            vec![
+                "loop=loop=2:size=100:start=0",
+                "setpts=N/FRAME_RATE/TB",
+                "scale=-2:480"
+            ]
+        );
+        assert_eq!(
+            cb.audio_filters,
+            vec!["aloop=loop=2:size=192000:start=0", "asetpts=N/SR/TB"]
+        );
+    }
+
+    #[test]
+    fn metadata_tags() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"in.mp3","nb_streams":2,"tags":{"TITLE":"Demo","artist":"Band"}},
+            "streams":[
+                {"index":0,"codec_type":"audio","tags":{"language":"eng"}},
+                {"index":1,"codec_type":"video","disposition":{"attached_pic":1}}
+            ]}"#,
+        )
+        .expect("valid info");
+        assert_eq!(info.format_tag("title"), Some("Demo"));
+        assert!(info.has_cover_art());
+        let mut p = Metadata::new_parameter(&info);
+        let ParameterData::Metadata(meta) = &mut p.data else {
+            panic!("Unexpected parameter type");
+        };
+        assert_eq!(meta.tags.language, "eng");
+        meta.tags.title = "Live demo".to_owned();
+        meta.tags.artist.clear();
+        meta.tags.language = "deu".to_owned();
+        let mut cb = CommandBuilder {
+            ext: "mp3".to_owned(),
+            ..Default::default()
+        };
+
+        cb.visit_metadata(&mut p.data);
+
+        assert_eq!(
+            cb.build_args(true),
+            vec![
+                "-map",
+                "0:a?",
+                "-map",
+                "0:v:0",
+                "-metadata",
+                "\"title=Live demo\"",
+                "-metadata",
+                "\"artist=\"",
+                "-metadata:s:a",
+                "\"language=deu\"",
+                "-c:v",
+                "copy",
+                "-disposition:v:0",
+                "attached_pic"
+            ]
+        );
+        assert_eq!(p.describe_value(), "3 tags");
+    }
+
+    #[test]
+    fn metadata_preset_escaping() {
+        let path = std::env::temp_dir().join("effy;cover|1.jpg");
+        let path = path.to_string_lossy().into_owned();
+        let ctx = VisitorContext::default();
+        let mut p = Metadata::new_parameter(&Info::default());
+        p.data = ParameterData::Metadata(MetadataData {
+            cover: CoverArt::Replace,
+            cover_path: Some(path.clone()),
+            tags: MetadataTags {
+                title: "Live | Remastered; 50%".to_owned(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let preset = Metadata::save_preset(&ctx, &p.data).expect("preset");
+        let mut restored = Metadata::new_parameter(&Info::default());
+        Metadata::apply_preset(&ctx, &mut restored.data, &preset);
+
+        assert!(!preset.contains(';'), "{preset}");
+        assert_eq!(preset.matches('|').count(), 8, "{preset}");
+        let ParameterData::Metadata(meta) = &restored.data else {
+            panic!("metadata data expected");
+        };
+        assert_eq!(meta.cover, CoverArt::Replace);
+        assert_eq!(meta.cover_path.as_deref(), Some(path.as_str()));
+        assert_eq!(meta.tags.title, "Live | Remastered; 50%");
+    }
+
+    #[test]
+    fn metadata_strip_and_cover() {
+        let mut p = Metadata::new_parameter(&Info::default());
+        p.data = ParameterData::Metadata(MetadataData::with_source(
+            MetadataTags {
+                title: "Private".to_owned(),
+                ..Default::default()
+            },
+            true,
+        ));
+        let ctx = VisitorContext::default();
+        Metadata::apply_preset(&ctx, &mut p.data, "s|remove||||||2024|");
+        assert_eq!(
+            Metadata::save_preset(&ctx, &p.data).as_deref(),
+            Some("s|remove||||||2024|")
+        );
+        let mut cb = CommandBuilder {
+            ext: "ogg".to_owned(),
+            ..Default::default()
+        };
+
+        cb.visit_metadata(&mut p.data);
 
-        assert_eq!(cb.args, vec!["-r", "25"]);
+        // Unchanged source title is stripped, not written
+        assert_eq!(
+            cb.args,
+            vec!["-map_metadata", "-1", "-map_chapters", "-1", "-vn"]
+        );
+        assert_eq!(
+            cb.metadata,
+            vec![("-metadata".to_owned(), "date=2024".to_owned())]
+        );
+        assert_eq!(p.describe_value(), "strip, date \"2024\", no cover");
     }
 
     #[test]
-    fn video_scale_default() {
-        let mut cb = CommandBuilder::default();
-        let mut p = VideoScale::new_parameter();
+    fn split_by_chapters() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"in.mkv","nb_streams":1},
+            "streams":[{"index":0,"codec_type":"video","avg_frame_rate":"25/1"}],
+            "chapters":[
+                {"id":0,"start_time":"0.000000","end_time":"60.000000","tags":{"title":"Intro: A/B"}},
+                {"id":1,"start_time":"60.000000","end_time":"90.500000"}
+            ]}"#,
+        )
+        .expect("valid info");
+        assert_eq!(info.chapters[1].title(1), "Chapter 2");
+        let mut cb = CommandBuilder::new(VisitorContext::new(&info, Features::default()));
+        cb.speed_factor = Some(2.0);
+        cb.video_filters.push("scale=-2:480".to_owned());
+        let mut p = Split::new_parameter(&info);
+        Split::apply_preset(&cb.ctx, &mut p.data, "chapters");
+
+        cb.visit_split(&mut p.data);
+
+        let suffixes: Vec<_> = cb.output_parts.iter().map(|p| p.suffix.as_str()).collect();
+        assert_eq!(suffixes, vec!["_01_Intro_ A_B", "_02_Chapter 2"]);
+        assert_eq!(
+            cb.build_parts_args(false),
+            vec![
+                vec![
+                    "-filter_complex",
+                    "[0:v]scale=-2:480[vout];[vout]split=2[vout0][vout1]",
+                    "-ss",
+                    "0.000",
+                    "-to",
+                    "30.000",
+                    "-map",
+                    "[vout0]",
+                    "-map",
+                    "0:a?"
+                ],
+                vec![
+                    "-ss", "30.000", "-to", "45.250", "-map", "[vout1]", "-map", "0:a?"
+                ],
+            ]
+        );
+    }
 
-        cb.visit_video_scale(&mut p.data);
+    #[test]
+    fn split_by_duration() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"in.mp4","nb_streams":1,"duration":"250.0"},
+            "streams":[{"index":0,"codec_type":"video","avg_frame_rate":"25/1"}]}"#,
+        )
+        .expect("valid info");
+        let mut cb = CommandBuilder::new(VisitorContext::new(&info, Features::default()));
+        cb.ext = "mp4".to_owned();
+        let mut p = Split::new_parameter(&info);
+        Split::apply_preset(&cb.ctx, &mut p.data, "duration,60");
+        assert_eq!(
+            Split::save_preset(&cb.ctx, &p.data).as_deref(),
+            Some("duration,60")
+        );
 
-        assert!(cb.video_filters.is_empty());
+        cb.visit_split(&mut p.data);
+        cb.visit_last();
+
+        assert_eq!(
+            cb.pre_output_args,
+            vec![
+                "-force_key_frames",
+                "expr:gte(t,n_forced*60)",
+                "-f",
+                "segment",
+                "-segment_time",
+                "60",
+                "-segment_start_number",
+                "1",
+                "-reset_timestamps",
+                "1",
+                "-segment_format_options",
+                "movflags=+faststart"
+            ]
+        );
+        let files = cb.output_files();
+        assert_eq!(files, OutputFiles::Segments(5));
+        assert_eq!(files.names("in_out")[4], "in_out_005");
+        assert_eq!(OutputFiles::segment_pattern("in_out"), "in_out_%03d");
+        assert_eq!(
+            OutputFiles::segment_pattern("100% {n:02} part"),
+            "100%% %02d part"
+        );
+        assert_eq!(
+            OutputFiles::Segments(2).names("{n} of 2"),
+            vec!["1 of 2", "2 of 2"]
+        );
+        assert_eq!(p.describe_value(), "every 1m");
     }
 
     #[test]
-    fn video_scale() {
-        let mut cb = CommandBuilder::default();
-        let mut p = VideoScale::new_parameter();
-        set_custom_value(&mut p, "600");
+    fn split_by_size() {
+        let mut cb = CommandBuilder {
+            ext: "mp3".to_owned(),
+            ..Default::default()
+        };
+        cb.pre_output_args
+            .extend(["-b:a".to_owned(), "128k".to_owned()]);
+        let mut p = Parameter::new(
+            Split::ID,
+            Split::NAME,
+            ParameterData::Split(SplitData::default()),
+        );
+        Split::apply_preset(&cb.ctx, &mut p.data, "size,10");
 
-        cb.visit_video_scale(&mut p.data);
+        cb.visit_split(&mut p.data);
 
-        assert_eq!(cb.video_filters, vec!["scale=-2:600"]);
+        // 10MB minus 5% at 128 kbps
+        assert_eq!(
+            cb.pre_output_args[2..6],
+            ["-f", "segment", "-segment_time", "593"]
+        );
+        assert!(cb.warnings.is_empty());
     }
 
-    // ------ Common ------
+    #[test]
+    fn split_by_scene() {
+        let mut cb = CommandBuilder {
+            ext: "mkv".to_owned(),
+            input_seek: Some(2.0),
+            speed_factor: Some(2.0),
+            ..Default::default()
+        };
+        let mut data = SplitData {
+            video: true,
+            ..Default::default()
+        };
+        let mut p = Parameter::new(Split::ID, Split::NAME, ParameterData::Split(data.clone()));
+        Split::apply_preset(&cb.ctx, &mut p.data, "scene,0.3");
+        assert_eq!(p.describe_value(), "scene changes above 0.3, not analyzed");
+
+        cb.visit_split(&mut p.data);
+
+        assert_eq!(cb.segments, None);
+        assert_eq!(cb.warnings.len(), 1);
+
+        data.scene = 0.3;
+        data.mode = SplitMode::Scene;
+        data.detection = Some(SceneDetection {
+            threshold: 0.3,
+            times: vec![1.5, 6.0, 12.5],
+        });
+        let mut cb = CommandBuilder {
+            ext: "mkv".to_owned(),
+            input_seek: Some(2.0),
+            speed_factor: Some(2.0),
+            ..Default::default()
+        };
+
+        cb.visit_split(&mut ParameterData::Split(data));
+
+        assert_eq!(
+            cb.pre_output_args[..6],
+            [
+                "-force_key_frames",
+                "2.000,5.250",
+                "-f",
+                "segment",
+                "-segment_times",
+                "2.000,5.250"
+            ]
+        );
+        assert_eq!(cb.output_files(), OutputFiles::Segments(3));
+    }
+
+    #[test]
+    fn split_by_scene_after_precise_trim() {
+        let data = SplitData {
+            video: true,
+            mode: SplitMode::Scene,
+            scene: 0.3,
+            detection: Some(SceneDetection {
+                threshold: 0.3,
+                times: vec![1.5, 6.0, 12.5, 30.0],
+            }),
+            ..Default::default()
+        };
+        let mut trim = Trim::new_parameter();
+        trim.data = ParameterData::Trim(TrimData {
+            ss: Some("4".to_owned()),
+            to: Some("20".to_owned()),
+            use_to: true,
+            precise: true,
+        });
+        let mut cb = CommandBuilder {
+            ext: "mkv".to_owned(),
+            ..Default::default()
+        };
+
+        cb.visit_trim(&mut trim.data);
+        cb.visit_split(&mut ParameterData::Split(data.clone()));
+
+        // Cut points are in the output timeline, it starts at the trim start
+        assert_eq!(cb.args, vec!["-ss", "4", "-to", "20"]);
+        assert_eq!(
+            cb.pre_output_args[..6],
+            [
+                "-force_key_frames",
+                "2.000,8.500",
+                "-f",
+                "segment",
+                "-segment_times",
+                "2.000,8.500"
+            ]
+        );
+        assert_eq!(cb.output_files(), OutputFiles::Segments(3));
+
+        let mut timelapse = Timelapse::new_parameter(&Info::default());
+        set_custom_value(&mut timelapse, "10");
+        let mut cb = CommandBuilder {
+            ext: "mkv".to_owned(),
+            ..Default::default()
+        };
+
+        cb.visit_timelapse(&mut timelapse.data);
+        cb.visit_split(&mut ParameterData::Split(data));
+
+        assert_eq!(cb.segments, None);
+        assert_eq!(cb.warnings.len(), 1);
+    }
+
+    #[test]
+    fn hls_renditions() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"in.mp4","nb_streams":2},
+            "streams":[
+                {"index":0,"codec_type":"video","avg_frame_rate":"25/1","width":1920,"height":1080},
+                {"index":1,"codec_type":"audio","sample_rate":"48000"}
+            ]}"#,
+        )
+        .expect("valid info");
+        let mut cb = CommandBuilder::new(VisitorContext::new(&info, Features::default()));
+        cb.ext = "m3u8".to_owned();
+        cb.video_filters.push("fps=30".to_owned());
+        let mut p = Renditions::new_parameter(&info);
+        Renditions::apply_preset(&cb.ctx, &mut p.data, "360p@600k,720p");
+        assert_eq!(
+            Renditions::save_preset(&cb.ctx, &p.data).as_deref(),
+            Some("360p@600k,720p@2800k")
+        );
+
+        cb.visit_renditions(&mut p.data);
+        cb.visit_last();
+
+        assert_eq!(
+            cb.build_args(true),
+            vec![
+                "-filter_complex",
+                "\"[0:v]fps=30[vout];[vout]split=2[s0][s1];[s0]scale=-2:360[r0];[s1]scale=-2:720[r1]\"",
+                "-map",
+                "\"[r0]\"",
+                "-map",
+                "\"[r1]\"",
+                "-map",
+                "0:a?",
+                "-map",
+                "0:a?",
+                "-b:v:0",
+                "600k",
+                "-b:v:1",
+                "2800k",
+                "-force_key_frames",
+                "expr:gte(t,n_forced*6)",
+                "-f",
+                "hls",
+                "-hls_time",
+                "6",
+                "-hls_playlist_type",
+                "vod",
+                "-master_pl_name",
+                "master.m3u8",
+                "-var_stream_map",
+                "\"v:0,a:0 v:1,a:1\""
+            ]
+        );
+        let files = cb.output_files();
+        assert_eq!(files, OutputFiles::Package(PackageFormat::Hls));
+        assert_eq!(files.names("in_out"), vec!["in_out/master"]);
+    }
+
+    #[test]
+    fn dash_single_rendition() {
+        let mut cb = CommandBuilder {
+            ext: "mpd".to_owned(),
+            ctx: VisitorContext {
+                frame_rate: Some(25.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        cb.visit_last();
+
+        assert_eq!(
+            cb.build_args(false),
+            vec![
+                "-force_key_frames",
+                "expr:gte(t,n_forced*6)",
+                "-f",
+                "dash",
+                "-seg_duration",
+                "6",
+                "-use_template",
+                "1",
+                "-use_timeline",
+                "1",
+                "-adaptation_sets",
+                "id=0,streams=v"
+            ]
+        );
+    }
 
     #[test]
     fn speed_factor_default() {
@@ -346,6 +2549,118 @@ mod tests {
         assert_eq!(cb.speed_factor, Some(2.0));
     }
 
+    #[test]
+    fn speed_factor_atempo_chain() {
+        let mut cb = CommandBuilder::default();
+        let mut p = SpeedFactor::new_parameter();
+        set_custom_value(&mut p, "0.25");
+
+        cb.visit_speed_factor(&mut p.data);
+
+        assert_eq!(cb.audio_filters, vec!["atempo=0.5", "atempo=0.5"]);
+        assert_eq!(
+            SpeedFactor::atempo_chain(5.0),
+            vec!["atempo=2", "atempo=2", "atempo=1.25"]
+        );
+        assert_eq!(
+            SpeedFactor::atempo_chain(0.3),
+            vec!["atempo=0.5", "atempo=0.6"]
+        );
+    }
+
+    #[test]
+    fn speed_factor_change_pitch() {
+        let mut cb = CommandBuilder::new(VisitorContext {
+            sample_rate: Some(48000),
+            ..Default::default()
+        });
+        let mut keep_pitch = PreservePitch::new_parameter();
+        toggle_next(&mut keep_pitch);
+        let mut pitch = AudioPitch::new_parameter(Features::default());
+        set_custom_value(&mut pitch, "0.8");
+        let mut p = SpeedFactor::new_parameter();
+        set_custom_value(&mut p, "1.5");
+
+        cb.visit_preserve_pitch(&mut keep_pitch.data);
+        cb.visit_audio_pitch(&mut pitch.data);
+        cb.visit_speed_factor(&mut p.data);
+
+        assert_eq!(
+            cb.audio_filters,
+            vec![
+                "rubberband=pitchq=quality:pitch=0.8",
+                "asetrate=48000*1.5",
+                "aresample=48000"
+            ]
+        );
+        assert_eq!(cb.video_filters, vec!["setpts=PTS/1.5"]);
+    }
+
+    #[test]
+    fn speed_factor_change_pitch_unknown_sample_rate() {
+        let mut cb = CommandBuilder::default();
+        let mut keep_pitch = PreservePitch::new_parameter();
+        toggle_next(&mut keep_pitch);
+        let mut p = SpeedFactor::new_parameter();
+        set_custom_value(&mut p, "1.5");
+
+        cb.visit_preserve_pitch(&mut keep_pitch.data);
+        cb.visit_speed_factor(&mut p.data);
+
+        assert_eq!(cb.audio_filters, vec!["atempo=1.5"]);
+        assert_eq!(cb.warnings.len(), 1);
+    }
+
+    #[test]
+    fn timelapse_every_nth_frame() {
+        let mut cb = CommandBuilder::default();
+        let mut volume = AudioVolume::new_parameter();
+        set_custom_value(&mut volume, "2");
+        let mut p = Timelapse::new_parameter(&Info::default());
+        set_custom_value(&mut p, "30");
+
+        cb.visit_audio_volume(&mut volume.data);
+        cb.visit_timelapse(&mut p.data);
+
+        assert_eq!(
+            cb.build_args(false),
+            vec![
+                "-an",
+                "-vf",
+                "select='not(mod(n,30))',setpts=N/FRAME_RATE/TB"
+            ]
+        );
+        assert_eq!(cb.speed_factor, Some(30.0));
+    }
+
+    #[test]
+    fn timelapse_interval() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"in.mp4","nb_streams":1,"duration":"3600"},
+            "streams":[{"index":0,"codec_type":"video","avg_frame_rate":"30/1"}]}"#,
+        )
+        .expect("valid info");
+        let mut cb = CommandBuilder::new(VisitorContext::new(&info, Features::default()));
+        let mut speed = SpeedFactor::new_parameter();
+        set_custom_value(&mut speed, "2");
+        let mut p = Timelapse::new_parameter(&info);
+        set_custom_value(&mut p, "2s");
+
+        cb.visit_speed_factor(&mut speed.data);
+        cb.visit_timelapse(&mut p.data);
+
+        assert_eq!(
+            cb.video_filters,
+            vec![
+                "setpts=PTS/2",
+                "select='isnan(prev_selected_t)+gte(t-prev_selected_t,1)'",
+                "setpts=N/FRAME_RATE/TB"
+            ]
+        );
+        assert_eq!(cb.speed_factor, Some(60.0));
+        assert_eq!(p.describe_value(), "every 2s \u{2192} 1m 0s");
+    }
+
     #[test]
     fn hardware_acceleration() {
         let mut cb = CommandBuilder::default();
@@ -378,6 +2693,96 @@ mod tests {
         assert_eq!(cb.ext, "mp4");
     }
 
+    #[test]
+    fn output_format_options() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"in.mkv","nb_streams":2},
+            "streams":[
+                {"index":0,"codec_type":"video","width":640,"height":360},
+                {"index":1,"codec_type":"audio"}
+            ]}"#,
+        )
+        .expect("valid info");
+        let p = OutputFormat::new_parameter(&info, "mkv");
+        let ParameterData::Select {
+            options,
+            selected_index,
+        } = &p.data
+        else {
+            panic!("Unexpected parameter type");
+        };
+        let values: Vec<_> = options.iter().map(|o| o.value.as_str()).collect();
+        assert_eq!(values.len(), CONTAINERS.len());
+        assert_eq!(values[*selected_index], "mkv");
+
+        let p = OutputFormat::new_parameter(&info, "avi");
+        assert_eq!(p.describe_value(), "avi");
+        assert!(!OutputFormat::is_audio("avi"));
+        assert!(OutputFormat::is_audio("opus"));
+    }
+
+    #[test]
+    fn container_compatibility() {
+        assert!(AudioCodec::is_compatible("libopus", "webm"));
+        assert!(!AudioCodec::is_compatible("aac", "webm"));
+        assert!(AudioCodec::is_compatible("aac", "avi"));
+        assert_eq!(AudioCodec::default_for("m4a"), Some("aac"));
+        assert!(!HardwareAcceleration::is_supported("webm"));
+        assert!(HardwareAcceleration::is_supported("mkv"));
+
+        let mut cb = CommandBuilder {
+            ext: "webm".to_owned(),
+            ..Default::default()
+        };
+        cb.visit_last();
+        assert!(cb.pre_output_args.is_empty());
+
+        let mut cb = CommandBuilder {
+            ext: "m4a".to_owned(),
+            ..Default::default()
+        };
+        cb.visit_last();
+        assert_eq!(cb.pre_output_args, vec!["-movflags", "faststart"]);
+    }
+
+    #[test]
+    fn source_aware_options() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"in.mp4","nb_streams":2,"bit_rate":"1200000"},
+            "streams":[
+                {"index":0,"codec_type":"video","width":640,"height":360,"avg_frame_rate":"24000/1001"},
+                {"index":1,"codec_type":"audio","bit_rate":"128000"}
+            ]}"#,
+        )
+        .expect("valid info");
+        let values = |p: &Parameter| match &p.data {
+            ParameterData::CustomSelect { options, .. } => {
+                options.iter().map(|o| o.value.clone()).collect::<Vec<_>>()
+            }
+            _ => panic!("Unexpected parameter type"),
+        };
+
+        let scale = VideoScale::new_parameter(&info);
+        assert_eq!(values(&scale), vec!["144", "240", "0"]);
+        assert_eq!(scale.describe_value(), "original (640x360)");
+
+        let frame_rate = VideoFrameRate::new_parameter(&info);
+        assert_eq!(values(&frame_rate), vec!["5", "10", "15", "20", "0"]);
+        assert_eq!(frame_rate.describe_value(), "original (23.976)");
+
+        let video_bitrate = VideoBitrate::new_parameter(&info);
+        assert_eq!(
+            values(&video_bitrate),
+            vec!["16k", "32k", "0", "64k", "128k", "256k", "512k", "1M"]
+        );
+        assert_eq!(video_bitrate.describe_value(), "auto (source 1072k)");
+
+        let mut audio_bitrate = AudioBitrate::new_parameter(&info);
+        assert_eq!(values(&audio_bitrate), vec!["4", "16", "32", "0", "64"]);
+        set_custom_value(&mut audio_bitrate, "192");
+        assert_eq!(audio_bitrate.describe_value(), "192k (above source)");
+    }
+
     #[test]
     fn build_args_with_filters() {
         let mut cb = CommandBuilder::default();
@@ -424,6 +2829,297 @@ mod tests {
         assert_eq!(cb.pre_output_args, vec!["-movflags", "faststart"]);
     }
 
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn build_args_quoted() {
+        let cb = CommandBuilder {
+            video_filters: vec!["drawtext=text='$HOME \\\"`'".to_owned()],
+            ..Default::default()
+        };
+        let result = cb.build_args(true);
+        assert_eq!(
+            result,
+            vec!["-vf", "\"drawtext=text='\\$HOME \\\\\\\"\\`'\""]
+        );
+    }
+
+    #[test]
+    fn frames_every_interval() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"in.mp4","nb_streams":1,"duration":"250.0"},
+            "streams":[{"index":0,"codec_type":"video","avg_frame_rate":"25/1"}]}"#,
+        )
+        .expect("valid info");
+        let mut cb = CommandBuilder::new(VisitorContext::new(&info, Features::default()));
+        cb.ext = "jpg".to_owned();
+        cb.input_seek = Some(50.0);
+        cb.video_filters.push("scale=-2:480".to_owned());
+        let mut p = Frames::new_parameter(&info);
+        Frames::apply_preset(&cb.ctx, &mut p.data, "every,10");
+        assert_eq!(
+            Frames::save_preset(&cb.ctx, &p.data).as_deref(),
+            Some("every,10")
+        );
+        let mut quality = ImageQuality::new_parameter();
+        ImageQuality::apply_preset(&cb.ctx, &mut quality.data, "75");
+
+        cb.visit_frames(&mut p.data);
+        cb.visit_image_quality(&mut quality.data);
+        cb.visit_last();
+
+        assert_eq!(
+            cb.build_args(false),
+            vec!["-an", "-q:v", "9", "-vf", "scale=-2:480,fps=1/10"]
+        );
+        let files = cb.output_files();
+        assert_eq!(files, OutputFiles::Frames(Some(20)));
+        assert_eq!(files.names("in_out"), vec!["in_out_00001"]);
+        assert_eq!(OutputFiles::frame_pattern("in_out"), "in_out_%05d");
+        assert_eq!(OutputFiles::frame_pattern("shot {n}"), "shot %d");
+    }
+
+    #[test]
+    fn frames_trimmed_range() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"in.mp4","nb_streams":1,"duration":"250.0"},
+            "streams":[{"index":0,"codec_type":"video","avg_frame_rate":"25/1"}]}"#,
+        )
+        .expect("valid info");
+        let mut trim = Trim::new_parameter();
+        trim.data = ParameterData::Trim(TrimData {
+            ss: Some("30".to_owned()),
+            to: Some("90".to_owned()),
+            use_to: true,
+            precise: true,
+        });
+        let mut p = Frames::new_parameter(&info);
+        let ctx = VisitorContext::new(&info, Features::default());
+        Frames::apply_preset(&ctx, &mut p.data, "every,10");
+        let mut cb = CommandBuilder::new(ctx.clone());
+        cb.ext = "png".to_owned();
+
+        cb.visit_trim(&mut trim.data);
+        cb.visit_frames(&mut p.data);
+
+        assert_eq!(cb.output_files(), OutputFiles::Frames(Some(6)));
+
+        // Sheet samples start at the precise trim start
+        Frames::apply_preset(&ctx, &mut p.data, "sheet,3x2,0,0");
+        let mut cb = CommandBuilder::new(ctx);
+        cb.ext = "png".to_owned();
+
+        cb.visit_trim(&mut trim.data);
+        cb.visit_frames(&mut p.data);
+
+        assert_eq!(
+            cb.video_filters[0],
+            "select='gte(t,35.000+selected_n*10.000)'"
+        );
+    }
+
+    #[test]
+    fn frames_output_params() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"in.mp4","nb_streams":2,"duration":"60.0"},
+            "streams":[
+                {"index":0,"codec_type":"video","avg_frame_rate":"25/1","width":1280,"height":720},
+                {"index":1,"codec_type":"audio","sample_rate":"48000","channels":2}
+            ]}"#,
+        )
+        .expect("valid info");
+        let mut params = create_params(
+            &info,
+            Features::default(),
+            Some("output=png;frames=scene,0.3;iquality=50"),
+            "mp4",
+        );
+        let enabled =
+            |params: &[Parameter], id: &str| params.iter().any(|p| p.id == id && p.enabled);
+        assert!(enabled(&params, Frames::ID));
+        assert!(!enabled(&params, ImageQuality::ID), "PNG is lossless");
+        for id in [AudioCodec::ID, DisableAudio::ID, Split::ID, Metadata::ID] {
+            assert!(!enabled(&params, id), "{id}");
+        }
+        let mut cb = CommandBuilder::new(VisitorContext::new(&info, Features::default()));
+
+        apply_visitor(&mut cb, &mut params);
+
+        assert_eq!(
+            cb.build_args(false),
+            vec!["-an", "-vf", "select='gt(scene,0.3)'", "-fps_mode", "vfr"]
+        );
+        assert_eq!(cb.output_files(), OutputFiles::Frames(None));
+
+        let mut params = create_params(&info, Features::default(), Some("output=webp"), "mp4");
+        let mut cb = CommandBuilder::new(VisitorContext::new(&info, Features::default()));
+        apply_visitor(&mut cb, &mut params);
+        assert_eq!(
+            cb.build_args(false),
+            vec!["-an", "-frames:v", "1", "-update", "1"]
+        );
+        assert_eq!(cb.output_files(), OutputFiles::Single);
+    }
+
+    #[test]
+    fn frames_contact_sheet() {
+        let info = Info::parse(
+            r#"{"format":{"filename":"/videos/in.mp4","nb_streams":1,"duration":"80.0","size":"12345678"},
+            "streams":[
+                {"index":0,"codec_type":"video","avg_frame_rate":"25/1","width":1280,"height":720}
+            ]}"#,
+        )
+        .expect("valid info");
+        let mut params = create_params(
+            &info,
+            Features::default(),
+            Some("output=jpg;frames=sheet,4x3,1,1"),
+            "mp4",
+        );
+        let mut cb = CommandBuilder::new(VisitorContext::new(&info, Features::default()));
+
+        apply_visitor(&mut cb, &mut params);
+
+        assert_eq!(
+            cb.build_args(false),
+            vec![
+                "-an",
+                "-vf",
+                "select='gte(t,3.333+selected_n*6.667)',scale=320:-2,\
+                 drawtext=text='%{pts\\:hms}':fontsize=16:fontcolor=white:box=1:boxcolor=black@0.5:boxborderw=4:x=w-tw-8:y=h-th-8,\
+                 tile=4x3:padding=4:margin=4,pad=iw:ih+36:0:36:color=black,\
+                 drawtext=text='in.mp4 | 00\\:01\\:20.000 | 1280x720 | 12.3 MB':fontsize=20:fontcolor=white:x=4:y=(36-th)/2",
+                "-frames:v",
+                "1",
+                "-update",
+                "1"
+            ]
+        );
+        assert_eq!(cb.output_files(), OutputFiles::Single);
+    }
+
+    #[test]
+    fn variant_inputs_and_labels() {
+        // The preceding variants added two inputs
+        let mut cb = CommandBuilder {
+            variant: 1,
+            input_offset: 2,
+            ..Default::default()
+        };
+        let mut p = Overlay::new_parameter();
+        p.data = ParameterData::Overlay(OverlayData {
+            path: Some("logo.png".to_owned()),
+            scale: 0,
+            opacity: 50,
+            ..Default::default()
+        });
+        cb.video_filters.push("scale=-2:480".to_owned());
+
+        cb.visit_overlay(&mut p.data);
+
+        assert_eq!(cb.extra_inputs.len(), 1);
+        assert_eq!(
+            cb.build_args(false),
+            vec![
+                "-filter_complex",
+                "[0:v]scale=-2:480[o1vc0];\
+                 [3:v]format=rgba,colorchannelmixer=aa=0.5[ova3];\
+                 [o1vc0][ova3]overlay=x=W-w-10:y=H-h-10[o1vout]",
+                "-map",
+                "[o1vout]",
+                "-map",
+                "0:a?",
+            ]
+        );
+        assert_eq!(
+            cb.push_input(ExtraInput::default()),
+            4,
+            "next input follows the overlay"
+        );
+    }
+
+    #[test]
+    fn variant_parts_labels() {
+        let cb = CommandBuilder {
+            variant: 2,
+            video_filters: vec!["scale=-2:480".to_owned()],
+            output_parts: vec![OutputPart::default(), OutputPart::default()],
+            discard_audio: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            cb.build_parts_args(false),
+            vec![
+                vec![
+                    "-filter_complex",
+                    "[0:v]scale=-2:480[o2vout];[o2vout]split=2[o2vout0][o2vout1]",
+                    "-map",
+                    "[o2vout0]",
+                ],
+                vec!["-map", "[o2vout1]"],
+            ]
+        );
+    }
+
+    #[test]
+    fn variant_shared_input_seeks_output() {
+        let mut trim = Trim::new_parameter();
+        trim.data = ParameterData::Trim(TrimData {
+            ss: Some("10".to_owned()),
+            to: Some("20".to_owned()),
+            use_to: true,
+            precise: false,
+        });
+        let mut cb = CommandBuilder {
+            seek_output: true,
+            ..Default::default()
+        };
+
+        cb.visit_trim(&mut trim.data);
+
+        assert!(cb.pre_input_args.is_empty());
+        assert_eq!(cb.args, vec!["-ss", "10", "-to", "20"]);
+        assert_eq!(cb.input_seek, None);
+    }
+
+    #[test]
+    fn variant_shared_split_graph() {
+        let mut first = CommandBuilder::default();
+        first.video_filters.push("scale=-2:720".to_owned());
+        first.audio_filters.push("volume=2".to_owned());
+        let mut second = CommandBuilder {
+            variant: 1,
+            ..Default::default()
+        };
+        second.video_filters.push("scale=-2:480".to_owned());
+        let mut third = CommandBuilder {
+            variant: 2,
+            ..Default::default()
+        };
+
+        let graph = CommandBuilder::share_source(vec![&mut first, &mut second, &mut third]);
+
+        assert_eq!(
+            graph.as_deref(),
+            Some(
+                "[0:v]split=2[sv0][sv1];\
+                 [sv0]scale=-2:720[vout];\
+                 [0:a]volume=2[aout];\
+                 [sv1]scale=-2:480[o1vout]"
+            )
+        );
+        assert_eq!(
+            first.build_args(false),
+            vec!["-map", "[vout]", "-map", "[aout]"]
+        );
+        assert_eq!(
+            second.build_args(false),
+            vec!["-map", "[o1vout]", "-map", "0:a?"]
+        );
+        assert!(third.shared.is_none());
+        assert!(third.build_args(false).is_empty());
+    }
+
     // ------ Private ------
 
     fn set_custom_value(param: &mut Parameter, new_value: &str) {
@@ -1,17 +1,40 @@
 use crate::params::ParameterData;
 
 pub(crate) trait ParameterVisitor {
+    fn visit_loop(&mut self, data: &mut ParameterData);
     fn visit_trim(&mut self, data: &mut ParameterData);
     fn visit_disable_audio(&mut self, data: &mut ParameterData);
     fn visit_audio_bitrate(&mut self, data: &mut ParameterData);
+    fn visit_audio_codec(&mut self, data: &mut ParameterData);
+    fn visit_audio_vbr(&mut self, data: &mut ParameterData);
+    fn visit_audio_channels(&mut self, data: &mut ParameterData);
+    fn visit_audio_offset(&mut self, data: &mut ParameterData);
+    fn visit_audio_sample_rate(&mut self, data: &mut ParameterData);
+    fn visit_audio_bit_depth(&mut self, data: &mut ParameterData);
+    fn visit_flac_compression(&mut self, data: &mut ParameterData);
+    fn visit_voice_cleanup(&mut self, data: &mut ParameterData);
     fn visit_audio_crystalizer(&mut self, data: &mut ParameterData);
+    fn visit_audio_eq(&mut self, data: &mut ParameterData);
     fn visit_audio_volume(&mut self, data: &mut ParameterData);
     fn visit_audio_pitch(&mut self, data: &mut ParameterData);
+    fn visit_remove_silence(&mut self, data: &mut ParameterData);
     fn visit_speed_factor(&mut self, data: &mut ParameterData);
+    fn visit_preserve_pitch(&mut self, data: &mut ParameterData);
+    fn visit_timelapse(&mut self, data: &mut ParameterData);
     fn visit_video_bitrate(&mut self, data: &mut ParameterData);
     fn visit_video_frame_rate(&mut self, data: &mut ParameterData);
+    fn visit_aspect(&mut self, data: &mut ParameterData);
     fn visit_video_scale(&mut self, data: &mut ParameterData);
+    fn visit_scale_algorithm(&mut self, data: &mut ParameterData);
+    fn visit_frame_interpolation(&mut self, data: &mut ParameterData);
+    fn visit_overlay(&mut self, data: &mut ParameterData);
+    fn visit_text(&mut self, data: &mut ParameterData);
     fn visit_hardware_acceleration(&mut self, data: &mut ParameterData);
+    fn visit_split(&mut self, data: &mut ParameterData);
+    fn visit_frames(&mut self, data: &mut ParameterData);
+    fn visit_image_quality(&mut self, data: &mut ParameterData);
+    fn visit_renditions(&mut self, data: &mut ParameterData);
+    fn visit_metadata(&mut self, data: &mut ParameterData);
     fn visit_output_format(&mut self, data: &mut ParameterData);
     fn visit_last(&mut self);
 }
@@ -24,6 +47,40 @@ mod visitor_context;
 pub const PRESET_SEPARATOR: &str = ";";
 pub const PRESET_VALUE_SEPARATOR: &str = "=";
 
+/// Percent-encode the separators in free-form preset values like paths and text
+pub(crate) fn escape_preset_value(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace(';', "%3B")
+        .replace(',', "%2C")
+        .replace('|', "%7C")
+}
+
+/// Decode the separators encoded by `escape_preset_value`, other `%` sequences are kept
+pub(crate) fn unescape_preset_value(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(pos) = rest.find('%') {
+        result.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let decoded = match rest.get(..3) {
+            Some("%25") => '%',
+            Some("%3B") => ';',
+            Some("%2C") => ',',
+            Some("%7C") => '|',
+            _ => {
+                result.push('%');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        result.push(decoded);
+        rest = &rest[3..];
+    }
+    result.push_str(rest);
+    result
+}
+
 pub(crate) use command_builder::*;
 pub(crate) use preset_applier::*;
 pub(crate) use preset_saver::*;
@@ -24,6 +24,12 @@ impl<'a> PresetApplier<'a> {
 }
 
 impl ParameterVisitor for PresetApplier<'_> {
+    fn visit_loop(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(Loop::ID) {
+            Loop::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
     fn visit_trim(&mut self, data: &mut ParameterData) {
         if let Some(preset_value) = self.preset_map.get(Trim::ID) {
             Trim::apply_preset(&self.ctx, data, preset_value);
@@ -42,12 +48,66 @@ impl ParameterVisitor for PresetApplier<'_> {
         }
     }
 
+    fn visit_audio_codec(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(AudioCodec::ID) {
+            AudioCodec::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_audio_vbr(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(AudioVbr::ID) {
+            AudioVbr::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_audio_channels(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(AudioChannels::ID) {
+            AudioChannels::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_audio_offset(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(AudioOffset::ID) {
+            AudioOffset::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_audio_sample_rate(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(AudioSampleRate::ID) {
+            AudioSampleRate::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_audio_bit_depth(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(AudioBitDepth::ID) {
+            AudioBitDepth::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_flac_compression(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(FlacCompression::ID) {
+            FlacCompression::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_voice_cleanup(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(VoiceCleanup::ID) {
+            VoiceCleanup::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
     fn visit_audio_crystalizer(&mut self, data: &mut ParameterData) {
         if let Some(preset_value) = self.preset_map.get(AudioCrystalizer::ID) {
             AudioCrystalizer::apply_preset(&self.ctx, data, preset_value);
         }
     }
 
+    fn visit_audio_eq(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(AudioEq::ID) {
+            AudioEq::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
     fn visit_audio_volume(&mut self, data: &mut ParameterData) {
         if let Some(preset_value) = self.preset_map.get(AudioVolume::ID) {
             AudioVolume::apply_preset(&self.ctx, data, preset_value);
@@ -60,12 +120,30 @@ impl ParameterVisitor for PresetApplier<'_> {
         }
     }
 
+    fn visit_remove_silence(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(RemoveSilence::ID) {
+            RemoveSilence::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
     fn visit_speed_factor(&mut self, data: &mut ParameterData) {
         if let Some(preset_value) = self.preset_map.get(SpeedFactor::ID) {
             SpeedFactor::apply_preset(&self.ctx, data, preset_value);
         }
     }
 
+    fn visit_preserve_pitch(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(PreservePitch::ID) {
+            PreservePitch::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_timelapse(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(Timelapse::ID) {
+            Timelapse::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
     fn visit_video_bitrate(&mut self, data: &mut ParameterData) {
         if let Some(preset_value) = self.preset_map.get(VideoBitrate::ID) {
             VideoBitrate::apply_preset(&self.ctx, data, preset_value);
@@ -78,18 +156,78 @@ impl ParameterVisitor for PresetApplier<'_> {
         }
     }
 
+    fn visit_aspect(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(Aspect::ID) {
+            Aspect::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
     fn visit_video_scale(&mut self, data: &mut ParameterData) {
         if let Some(preset_value) = self.preset_map.get(VideoScale::ID) {
             VideoScale::apply_preset(&self.ctx, data, preset_value);
         }
     }
 
+    fn visit_scale_algorithm(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(ScaleAlgorithm::ID) {
+            ScaleAlgorithm::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_frame_interpolation(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(FrameInterpolation::ID) {
+            FrameInterpolation::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_overlay(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(Overlay::ID) {
+            Overlay::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_text(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(Text::ID) {
+            Text::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
     fn visit_hardware_acceleration(&mut self, data: &mut ParameterData) {
         if let Some(preset_value) = self.preset_map.get(HardwareAcceleration::ID) {
             HardwareAcceleration::apply_preset(&self.ctx, data, preset_value);
         }
     }
 
+    fn visit_split(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(Split::ID) {
+            Split::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_frames(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(Frames::ID) {
+            Frames::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_image_quality(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(ImageQuality::ID) {
+            ImageQuality::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_renditions(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(Renditions::ID) {
+            Renditions::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
+    fn visit_metadata(&mut self, data: &mut ParameterData) {
+        if let Some(preset_value) = self.preset_map.get(Metadata::ID) {
+            Metadata::apply_preset(&self.ctx, data, preset_value);
+        }
+    }
+
     fn visit_output_format(&mut self, data: &mut ParameterData) {
         if let Some(preset_value) = self.preset_map.get(OutputFormat::ID) {
             OutputFormat::apply_preset(&self.ctx, data, preset_value);
@@ -30,6 +30,12 @@ impl PresetSaver {
 }
 
 impl ParameterVisitor for PresetSaver {
+    fn visit_loop(&mut self, data: &mut ParameterData) {
+        if let Some(v) = Loop::save_preset(&self.ctx, data) {
+            self.add(Loop::ID, &v);
+        }
+    }
+
     fn visit_trim(&mut self, data: &mut ParameterData) {
         if let Some(v) = Trim::save_preset(&self.ctx, data) {
             self.add(Trim::ID, &v);
@@ -48,12 +54,66 @@ impl ParameterVisitor for PresetSaver {
         }
     }
 
+    fn visit_audio_codec(&mut self, data: &mut ParameterData) {
+        if let Some(v) = AudioCodec::save_preset(&self.ctx, data) {
+            self.add(AudioCodec::ID, &v);
+        }
+    }
+
+    fn visit_audio_vbr(&mut self, data: &mut ParameterData) {
+        if let Some(v) = AudioVbr::save_preset(&self.ctx, data) {
+            self.add(AudioVbr::ID, &v);
+        }
+    }
+
+    fn visit_audio_channels(&mut self, data: &mut ParameterData) {
+        if let Some(v) = AudioChannels::save_preset(&self.ctx, data) {
+            self.add(AudioChannels::ID, &v);
+        }
+    }
+
+    fn visit_audio_offset(&mut self, data: &mut ParameterData) {
+        if let Some(v) = AudioOffset::save_preset(&self.ctx, data) {
+            self.add(AudioOffset::ID, &v);
+        }
+    }
+
+    fn visit_audio_sample_rate(&mut self, data: &mut ParameterData) {
+        if let Some(v) = AudioSampleRate::save_preset(&self.ctx, data) {
+            self.add(AudioSampleRate::ID, &v);
+        }
+    }
+
+    fn visit_audio_bit_depth(&mut self, data: &mut ParameterData) {
+        if let Some(v) = AudioBitDepth::save_preset(&self.ctx, data) {
+            self.add(AudioBitDepth::ID, &v);
+        }
+    }
+
+    fn visit_flac_compression(&mut self, data: &mut ParameterData) {
+        if let Some(v) = FlacCompression::save_preset(&self.ctx, data) {
+            self.add(FlacCompression::ID, &v);
+        }
+    }
+
+    fn visit_voice_cleanup(&mut self, data: &mut ParameterData) {
+        if let Some(v) = VoiceCleanup::save_preset(&self.ctx, data) {
+            self.add(VoiceCleanup::ID, &v);
+        }
+    }
+
     fn visit_audio_crystalizer(&mut self, data: &mut ParameterData) {
         if let Some(v) = AudioCrystalizer::save_preset(&self.ctx, data) {
             self.add(AudioCrystalizer::ID, &v);
         }
     }
 
+    fn visit_audio_eq(&mut self, data: &mut ParameterData) {
+        if let Some(v) = AudioEq::save_preset(&self.ctx, data) {
+            self.add(AudioEq::ID, &v);
+        }
+    }
+
     fn visit_audio_volume(&mut self, data: &mut ParameterData) {
         if let Some(v) = AudioVolume::save_preset(&self.ctx, data) {
             self.add(AudioVolume::ID, &v);
@@ -66,12 +126,30 @@ impl ParameterVisitor for PresetSaver {
         }
     }
 
+    fn visit_remove_silence(&mut self, data: &mut ParameterData) {
+        if let Some(v) = RemoveSilence::save_preset(&self.ctx, data) {
+            self.add(RemoveSilence::ID, &v);
+        }
+    }
+
     fn visit_speed_factor(&mut self, data: &mut ParameterData) {
         if let Some(v) = SpeedFactor::save_preset(&self.ctx, data) {
             self.add(SpeedFactor::ID, &v);
         }
     }
 
+    fn visit_preserve_pitch(&mut self, data: &mut ParameterData) {
+        if let Some(v) = PreservePitch::save_preset(&self.ctx, data) {
+            self.add(PreservePitch::ID, &v);
+        }
+    }
+
+    fn visit_timelapse(&mut self, data: &mut ParameterData) {
+        if let Some(v) = Timelapse::save_preset(&self.ctx, data) {
+            self.add(Timelapse::ID, &v);
+        }
+    }
+
     fn visit_video_bitrate(&mut self, data: &mut ParameterData) {
         if let Some(v) = VideoBitrate::save_preset(&self.ctx, data) {
             self.add(VideoBitrate::ID, &v);
@@ -84,18 +162,78 @@ impl ParameterVisitor for PresetSaver {
         }
     }
 
+    fn visit_aspect(&mut self, data: &mut ParameterData) {
+        if let Some(v) = Aspect::save_preset(&self.ctx, data) {
+            self.add(Aspect::ID, &v);
+        }
+    }
+
     fn visit_video_scale(&mut self, data: &mut ParameterData) {
         if let Some(v) = VideoScale::save_preset(&self.ctx, data) {
             self.add(VideoScale::ID, &v);
         }
     }
 
+    fn visit_scale_algorithm(&mut self, data: &mut ParameterData) {
+        if let Some(v) = ScaleAlgorithm::save_preset(&self.ctx, data) {
+            self.add(ScaleAlgorithm::ID, &v);
+        }
+    }
+
+    fn visit_frame_interpolation(&mut self, data: &mut ParameterData) {
+        if let Some(v) = FrameInterpolation::save_preset(&self.ctx, data) {
+            self.add(FrameInterpolation::ID, &v);
+        }
+    }
+
+    fn visit_overlay(&mut self, data: &mut ParameterData) {
+        if let Some(v) = Overlay::save_preset(&self.ctx, data) {
+            self.add(Overlay::ID, &v);
+        }
+    }
+
+    fn visit_text(&mut self, data: &mut ParameterData) {
+        if let Some(v) = Text::save_preset(&self.ctx, data) {
+            self.add(Text::ID, &v);
+        }
+    }
+
     fn visit_hardware_acceleration(&mut self, data: &mut ParameterData) {
         if let Some(v) = HardwareAcceleration::save_preset(&self.ctx, data) {
             self.add(HardwareAcceleration::ID, &v);
         }
     }
 
+    fn visit_split(&mut self, data: &mut ParameterData) {
+        if let Some(v) = Split::save_preset(&self.ctx, data) {
+            self.add(Split::ID, &v);
+        }
+    }
+
+    fn visit_frames(&mut self, data: &mut ParameterData) {
+        if let Some(v) = Frames::save_preset(&self.ctx, data) {
+            self.add(Frames::ID, &v);
+        }
+    }
+
+    fn visit_image_quality(&mut self, data: &mut ParameterData) {
+        if let Some(v) = ImageQuality::save_preset(&self.ctx, data) {
+            self.add(ImageQuality::ID, &v);
+        }
+    }
+
+    fn visit_renditions(&mut self, data: &mut ParameterData) {
+        if let Some(v) = Renditions::save_preset(&self.ctx, data) {
+            self.add(Renditions::ID, &v);
+        }
+    }
+
+    fn visit_metadata(&mut self, data: &mut ParameterData) {
+        if let Some(v) = Metadata::save_preset(&self.ctx, data) {
+            self.add(Metadata::ID, &v);
+        }
+    }
+
     fn visit_output_format(&mut self, data: &mut ParameterData) {
         if let Some(v) = OutputFormat::save_preset(&self.ctx, data) {
             self.add(OutputFormat::ID, &v);
@@ -1,17 +1,38 @@
-use crate::info::Info;
+use std::sync::Arc;
+
+use crate::{
+    features::Features,
+    info::{Info, InfoChapter},
+};
 
 /// Shared context for visitors
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct VisitorContext {
     // from input info
     pub(crate) input_duration: Option<f64>,
+    pub(crate) frame_rate: Option<f64>,
+    pub(crate) sample_rate: Option<u32>,
+    pub(crate) channels: Option<u32>,
+    // Bits per second
+    pub(crate) video_bitrate: Option<u64>,
+    pub(crate) audio_bitrate: Option<u64>,
+    pub(crate) chapters: Arc<[InfoChapter]>,
+    // from ffmpeg build
+    pub(crate) features: Features,
 }
 
 impl VisitorContext {
-    pub(crate) fn new(info: &Info) -> Self {
+    pub(crate) fn new(info: &Info, features: Features) -> Self {
         Self {
             input_duration: info.get_duration(),
+            frame_rate: info.frame_rate(),
+            sample_rate: info.sample_rate(),
+            channels: info.audio_channels(),
+            video_bitrate: info.video_bitrate(),
+            audio_bitrate: info.audio_bitrate(),
+            chapters: Arc::from(info.chapters.as_slice()),
+            features,
         }
     }
 }